@@ -7,11 +7,13 @@ use std::sync::Arc;
 
 use iced::{Element, Subscription, Task};
 
+use crate::commands::handoff::{self, Inbox};
 use crate::config::AppConfig;
 use crate::message::Message;
-use crate::persistence::Db;
-use crate::state::Model;
+use crate::persistence::{Db, DbError, SnapshotReason, SnapshotStore};
+use crate::state::{Edit, Model, Recovery, SessionMarker};
 use crate::ui::AppTheme;
+use crate::ui::notification::{Notification, NotificationAction};
 use crate::ui::state::UiState;
 use crate::ui::words::WordsMessage;
 use crate::ui::{self, compositor};
@@ -22,30 +24,14 @@ pub struct App {
     pub config: AppConfig,
     pub model: Model,
     pub ui: UiState,
+    pub session: SessionMarker,
 }
 
 impl App {
-    /// Creates a new App instance.
-    pub fn new(config: AppConfig) -> (Self, iced::Task<Message>) {
-        // Initialize database
-        let db_path = config.data_dir.join("data.redb");
-        tracing::debug!("Initializing database at {:?}", db_path);
-        let db = Db::new(&db_path).expect("Failed to create database");
-
-        // Create model with config
-        let mut model = Model::new(db, config.clone());
-        model.generator.load_from_config(&config.ai);
-
-        // Load existing data from database
-        tracing::debug!("Loading data from database");
-        model.load_all();
-        tracing::debug!(
-            "Data loaded: {} words, {} meanings, {} tags, {} clozes",
-            model.word_registry.count(),
-            model.meaning_registry.count(),
-            model.tag_registry.count(),
-            model.cloze_registry.count(),
-        );
+    /// Creates a new App instance around the opened library.
+    pub fn new(config: AppConfig, model: Model) -> (Self, iced::Task<Message>) {
+        let (session, previous) = SessionMarker::begin(&config.library_dir());
+        let recovery = previous.map(|info| Recovery::from_session(&model, &info));
 
         // Initialize UI state with theme and locale from config
         let mut ui = UiState {
@@ -55,7 +41,20 @@ impl App {
         };
         ui.i18n.set_locale(config.locale.to_langid());
 
-        let app = Self { config, model, ui };
+        if let Some(recovery) = recovery {
+            tracing::warn!(?recovery, "Previous session did not shut down cleanly");
+            ui.push_notification(
+                crate::ui::notification::NotificationLevel::Warning,
+                recovery.summary(),
+            );
+        }
+
+        let app = Self {
+            config,
+            model,
+            ui,
+            session,
+        };
 
         (app, Task::none())
     }
@@ -74,7 +73,15 @@ impl App {
             }
 
             // Queue panel
-            Message::Queue(msg) => ui::queue::update(msg, &mut self.model),
+            Message::Queue(msg) => {
+                // Selecting items and starting a batch are not library edits
+                if msg.is_undoable() {
+                    let (task, _) = self.model.record(|m| ui::queue::update(msg, m));
+                    task
+                } else {
+                    ui::queue::update(msg, &mut self.model)
+                }
+            }
 
             // Tags panel
             Message::Tags(msg) => match msg {
@@ -93,8 +100,22 @@ impl App {
                     }
                     Task::none()
                 }
-                other => crate::ui::tags::update(&mut self.ui.tags, other, &mut self.model)
-                    .map(Message::Tags),
+                other => {
+                    let tags = &mut self.ui.tags;
+                    let (task, edit) = self
+                        .model
+                        .record(|m| crate::ui::tags::update(tags, other, m));
+                    let task = task.map(Message::Tags);
+                    if edit.is_some_and(Edit::deletes) {
+                        let notification = Notification::info(0, "Tag moved to trash")
+                            .with_action(NotificationAction::Undo);
+                        return Task::batch([
+                            task,
+                            Task::done(Message::PushNotification(notification)),
+                        ]);
+                    }
+                    task
+                }
             },
 
             // Practice panel
@@ -102,25 +123,61 @@ impl App {
                 compositor::update_practice(&mut self.ui.practice, msg, &mut self.model)
             }
 
+            // Trash panel
+            Message::Trash(msg) => {
+                let trash = &mut self.ui.trash;
+                let (task, edit) = self
+                    .model
+                    .record(|m| crate::ui::trash::update(trash, msg, m));
+                if edit.is_some() {
+                    self.refresh_words();
+                }
+                task
+            }
+
             // Settings panel
-            Message::Settings(msg) => {
-                compositor::update_settings(&mut self.ui.settings, msg, &mut self.model)
+            Message::Settings(msg) => compositor::update_settings(
+                &mut self.ui.settings,
+                msg,
+                &mut self.model,
+                &self.ui.i18n,
+            ),
+
+            // Profile switcher
+            Message::SwitchProfile(profile) => {
+                self.switch_profile(profile);
+                Task::none()
             }
 
             // Navigation
             Message::Navigate(nav_item) => {
                 self.ui.current_view = nav_item;
+                match nav_item {
+                    crate::ui::nav::NavItem::Settings => {
+                        self.ui.settings.snapshots =
+                            SnapshotStore::new(&self.model.app_config.library_dir()).list();
+                    }
+                    crate::ui::nav::NavItem::Trash => {
+                        self.model.purge_trash();
+                    }
+                    _ => {}
+                }
                 Task::none()
             }
 
             // Global messages
             Message::QueueGenerationResult(result) => match result {
-                crate::state::QueueGenerationResult::Success { item_id, cloze } => {
-                    self.model.queue_registry.set_completed(item_id);
-                    self.model.cloze_registry.add(cloze);
+                crate::state::QueueGenerationResult::Success {
+                    item_id,
+                    candidates,
+                } => {
+                    let count = candidates.len();
+                    self.model
+                        .queue_registry
+                        .set_candidates(item_id, candidates);
                     self.ui.push_notification(
                         crate::ui::notification::NotificationLevel::Info,
-                        "Cloze generated successfully",
+                        format!("{} candidate(s) ready for review", count),
                     );
                     Task::none()
                 }
@@ -132,12 +189,51 @@ impl App {
                     );
                     Task::none()
                 }
+                crate::state::QueueGenerationResult::Regenerated {
+                    item_id,
+                    replaces,
+                    cloze,
+                } => {
+                    if !self
+                        .model
+                        .queue_registry
+                        .replace_candidate(item_id, replaces, cloze)
+                    {
+                        tracing::debug!(%item_id, "Regenerated candidate was already resolved");
+                    }
+                    Task::none()
+                }
+                crate::state::QueueGenerationResult::RegenerateFailed { item_id, error } => {
+                    tracing::warn!(%item_id, error = %error, "Candidate regeneration failed");
+                    self.ui.push_notification(
+                        crate::ui::notification::NotificationLevel::Error,
+                        format!("Regeneration failed: {}", error),
+                    );
+                    Task::none()
+                }
             },
 
             // Notification management
             Message::PushNotification(notification) => {
-                self.ui
-                    .push_notification(notification.level, notification.message);
+                self.ui.push(notification);
+                Task::none()
+            }
+
+            // Undo/redo of library edits
+            Message::Undo => {
+                let message = match self.model.undo() {
+                    Some(summary) => format!("Undone: {}", summary),
+                    None => "Nothing to undo".to_string(),
+                };
+                self.after_history_change(message);
+                Task::none()
+            }
+            Message::Redo => {
+                let message = match self.model.redo() {
+                    Some(summary) => format!("Redone: {}", summary),
+                    None => "Nothing to redo".to_string(),
+                };
+                self.after_history_change(message);
                 Task::none()
             }
             Message::DismissNotification(id) => {
@@ -149,6 +245,49 @@ impl App {
                 Task::none()
             }
 
+            // Autosave - flush unsaved changes in the background of the session
+            Message::AutosaveTick => {
+                if self.model.has_dirty() {
+                    match self.model.flush_all() {
+                        Ok(()) => self.session.saved(),
+                        Err(e) => {
+                            tracing::error!("Autosave failed: {}", e);
+                            self.ui.push_notification(
+                                crate::ui::notification::NotificationLevel::Error,
+                                format!("Autosave failed: {}", e),
+                            );
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            // Daily snapshot - taken when the newest snapshot is a day old
+            Message::SnapshotTick => {
+                let store = SnapshotStore::new(&self.model.app_config.library_dir());
+                if store.is_due(chrono::TimeDelta::days(1))
+                    && let Err(e) = self.model.snapshot(SnapshotReason::Daily)
+                {
+                    tracing::error!("Daily snapshot failed: {}", e);
+                    self.ui.push_notification(
+                        crate::ui::notification::NotificationLevel::Error,
+                        format!("Daily snapshot failed: {}", e),
+                    );
+                }
+                Task::none()
+            }
+
+            // Requests from a second instance started against this library
+            Message::InboxTick => {
+                let inbox = Inbox::new(&self.model.app_config.library_dir());
+                let tasks: Vec<Task<Message>> = inbox
+                    .drain()
+                    .into_iter()
+                    .map(|request| self.handle_request(request))
+                    .collect();
+                Task::batch(tasks)
+            }
+
             // Close requested - exit the application
             Message::CloseRequested => {
                 self.on_exit();
@@ -212,7 +351,7 @@ impl App {
 
     /// Returns the application subscription.
     pub fn subscription(&self) -> Subscription<Message> {
-        let event_sub = iced::event::listen_with(|event, _status, _id| match event {
+        let event_sub = iced::event::listen_with(|event, status, _id| match event {
             iced::Event::Window(iced::window::Event::CloseRequested) => {
                 Some(Message::CloseRequested)
             }
@@ -223,20 +362,203 @@ impl App {
                 key: iced::keyboard::Key::Named(iced::keyboard::key::Named::Tab),
                 ..
             }) => Some(Message::TabPressed),
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key: iced::keyboard::Key::Character(c),
+                modifiers,
+                ..
+            }) if modifiers.command()
+                && c.eq_ignore_ascii_case("z")
+                && status == iced::event::Status::Ignored =>
+            {
+                Some(if modifiers.shift() {
+                    Message::Redo
+                } else {
+                    Message::Undo
+                })
+            }
             _ => None,
         });
 
         let tick =
             iced::time::every(std::time::Duration::from_secs(2)).map(|_| Message::NotificationTick);
 
-        Subscription::batch(vec![event_sub, tick])
+        let snapshot_tick = iced::time::every(std::time::Duration::from_secs(
+            crate::config::constants::backup::CHECK_HOURS * 60 * 60,
+        ))
+        .map(|_| Message::SnapshotTick);
+
+        let inbox_tick = iced::time::every(std::time::Duration::from_millis(
+            crate::config::constants::inbox::POLL_MILLIS,
+        ))
+        .map(|_| Message::InboxTick);
+
+        let mut subscriptions = vec![event_sub, tick, snapshot_tick, inbox_tick];
+        if self.model.app_config.autosave_secs > 0 {
+            subscriptions.push(
+                iced::time::every(std::time::Duration::from_secs(
+                    self.model.app_config.autosave_secs,
+                ))
+                .map(|_| Message::AutosaveTick),
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+
+    /// Reports an undo/redo and resets panel state that may refer to
+    /// entities it removed or brought back.
+    fn after_history_change(&mut self, message: String) {
+        self.ui
+            .push_notification(crate::ui::notification::NotificationLevel::Info, message);
+        let words = &mut self.ui.words;
+        words.pending_delete = None;
+        words.selection.clear_all();
+        words.panel.close();
+        self.refresh_words();
+        let tags = &mut self.ui.tags;
+        tags.pending_delete = None;
+        if tags
+            .selected
+            .is_some_and(|id| self.model.tag_registry.get(id).is_none())
+        {
+            tags.selected = None;
+        }
+    }
+
+    /// Opens the database of the active profile and loads it into a model.
+    fn open_library(config: AppConfig) -> Result<Model, DbError> {
+        let db_path = config
+            .library_dir()
+            .join(crate::config::constants::db::NAME);
+        tracing::debug!("Initializing database at {:?}", db_path);
+        let db = Db::new(&db_path)?;
+
+        // Create model with config
+        let mut model = Model::new(db, config);
+        model.generator.load_from_config(&model.app_config);
+
+        // Load existing data from database
+        tracing::debug!("Loading data from database");
+        model.load_all();
+        tracing::debug!(
+            "Data loaded: {} words, {} meanings, {} tags, {} clozes",
+            model.word_registry.count(),
+            model.meaning_registry.count(),
+            model.tag_registry.count(),
+            model.cloze_registry.count(),
+        );
+
+        if let Err(e) = model.snapshot(SnapshotReason::Startup) {
+            tracing::error!(error = %e, "Failed to snapshot database on startup");
+        }
+        model.purge_trash();
+        Ok(model)
+    }
+
+    /// Saves the current library and replaces it with the one of `profile`
+    /// (`None` for the library in the data directory).
+    fn switch_profile(&mut self, profile: Option<String>) {
+        use crate::ui::notification::NotificationLevel;
+
+        if profile == self.model.app_config.profile {
+            return;
+        }
+        if let Err(e) = self.model.flush_all() {
+            tracing::error!(error = %e, "Failed to save library before switching profile");
+            self.ui.push_notification(
+                NotificationLevel::Error,
+                format!("Profile not switched, saving failed: {}", e),
+            );
+            return;
+        }
+
+        let mut config = (*self.model.app_config).clone();
+        config.profile = profile;
+        let model = match Self::open_library(config.clone()) {
+            Ok(model) => model,
+            Err(e) => {
+                tracing::error!(error = %e, profile = ?config.profile, "Failed to open profile");
+                self.ui.push_notification(
+                    NotificationLevel::Error,
+                    format!("Failed to open profile: {}", e),
+                );
+                return;
+            }
+        };
+        self.session.end();
+        self.model = model;
+        config.save_to_file();
+
+        let (session, previous) = SessionMarker::begin(&config.library_dir());
+        self.session = session;
+        if let Some(info) = previous {
+            let recovery = Recovery::from_session(&self.model, &info);
+            tracing::warn!(?recovery, "Profile was not closed cleanly");
+            self.ui
+                .push_notification(NotificationLevel::Warning, recovery.summary());
+        }
+
+        self.ui.reset_panels();
+        if self.ui.current_view == crate::ui::nav::NavItem::Settings {
+            self.ui.settings.snapshots = SnapshotStore::new(&config.library_dir()).list();
+        }
+        self.refresh_words();
+        tracing::info!(profile = ?config.profile, "Switched profile");
+        self.ui.push_notification(
+            NotificationLevel::Info,
+            format!(
+                "Opened {}",
+                config.profile.as_deref().unwrap_or("default library")
+            ),
+        );
+        self.config = config;
+    }
+
+    /// Carries out a request handed over by another process.
+    fn handle_request(&mut self, request: handoff::Request) -> Task<Message> {
+        use crate::ui::notification::NotificationLevel;
+
+        match request {
+            handoff::Request::Focus => {}
+            handoff::Request::AddWord(args) => {
+                tracing::info!(word = %args.word, "Adding word from another process");
+                let (result, _) = self.model.record(|m| crate::commands::word_add(m, args));
+                match result {
+                    Ok(report) => {
+                        self.refresh_words();
+                        self.ui
+                            .push_notification(NotificationLevel::Info, report.join("; "));
+                    }
+                    Err(e) => {
+                        self.ui.push_notification(
+                            NotificationLevel::Error,
+                            format!("Failed to add word from the command line: {}", e),
+                        );
+                    }
+                }
+            }
+        }
+        iced::window::latest().and_then(iced::window::gain_focus)
+    }
+
+    /// Re-runs the words search after entities changed outside the panel.
+    fn refresh_words(&mut self) {
+        self.ui.words.search.invalidate();
+        self.ui.words.search.execute(
+            &self.model.word_registry,
+            &self.model.meaning_registry,
+            &self.model.cloze_registry,
+            &self.model.queue_registry,
+            &self.model.tag_registry,
+        );
     }
 
     /// Called when the application is closing.
     pub fn on_exit(&mut self) {
         tracing::debug!("Flushing dirty data on shutdown");
-        if let Err(e) = self.model.flush_all() {
-            tracing::error!("Failed to flush data on shutdown: {}", e);
+        match self.model.flush_all() {
+            Ok(()) => self.session.end(),
+            Err(e) => tracing::error!("Failed to flush data on shutdown: {}", e),
         }
         if let Some(c) = Arc::get_mut(&mut self.model.app_config) {
             c.save_to_file();
@@ -245,8 +567,51 @@ impl App {
     }
 
     /// Runs the application with the given configuration.
+    ///
+    /// If another instance has the library open, asks it to come to the
+    /// front instead of opening a second window. Fails with the lock error
+    /// if no running GUI answers.
     pub fn run(config: AppConfig) {
-        let _ = iced::application(move || App::new(config.clone()), App::update, App::view)
+        let model = match Self::open_library(config.clone()) {
+            Ok(model) => model,
+            Err(DbError::Locked(path)) => {
+                let library_dir = config.library_dir();
+                if !SessionMarker::is_present(&library_dir) {
+                    eprintln!("Error: {}", DbError::Locked(path));
+                    std::process::exit(1);
+                }
+                let timeout =
+                    std::time::Duration::from_millis(crate::config::constants::inbox::ACK_MILLIS);
+                match Inbox::new(&library_dir).deliver(&handoff::Request::Focus, timeout) {
+                    Ok(true) => println!("Clozer is already running; switched to its window"),
+                    // The marker is stale and another command holds the library
+                    Ok(false) => {
+                        eprintln!("Error: {}", DbError::Locked(path));
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("Clozer is already running, but could not reach it: {}", e)
+                    }
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("Error: Failed to open the library: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        // The boot closure is only called once; it takes the opened library
+        let model = std::sync::Mutex::new(Some(model));
+        let boot = move || {
+            let model = model
+                .lock()
+                .ok()
+                .and_then(|mut m| m.take())
+                .expect("application booted twice");
+            App::new(config.clone(), model)
+        };
+        let _ = iced::application(boot, App::update, App::view)
             .window(iced::window::Settings {
                 exit_on_close_request: false,
                 ..Default::default()
@@ -0,0 +1,94 @@
+//! Versioned archive format.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::BackupError;
+use crate::persistence::{
+    CardScheduleDto, ClozeDto, MeaningDto, QueueItemDto, ReviewLogDto, TagDto, WordDto,
+};
+use crate::state::Model;
+
+/// Current archive format version. Bump when the layout changes in a way
+/// older readers cannot handle.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A full snapshot of the library.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Archive {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub words: Vec<WordDto>,
+    pub meanings: Vec<MeaningDto>,
+    pub tags: Vec<TagDto>,
+    pub clozes: Vec<ClozeDto>,
+    #[serde(default)]
+    pub queue: Vec<QueueItemDto>,
+    #[serde(default)]
+    pub schedules: Vec<CardScheduleDto>,
+    #[serde(default)]
+    pub reviews: Vec<ReviewLogDto>,
+}
+
+impl Archive {
+    /// Captures every registry of the model.
+    pub fn from_model(model: &Model) -> Self {
+        Self {
+            version: ARCHIVE_VERSION,
+            created_at: Utc::now(),
+            words: model.word_registry.iter().map(|(_, w)| w.into()).collect(),
+            meanings: model
+                .meaning_registry
+                .iter()
+                .map(|(_, m)| m.into())
+                .collect(),
+            tags: model.tag_registry.iter().map(|(_, t)| t.into()).collect(),
+            clozes: model.cloze_registry.iter().map(|(_, c)| c.into()).collect(),
+            queue: model.queue_registry.get_items().map(Into::into).collect(),
+            schedules: model
+                .schedule_registry
+                .iter()
+                .map(|(_, s)| s.into())
+                .collect(),
+            reviews: model
+                .review_registry
+                .iter()
+                .map(|(_, r)| r.into())
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, BackupError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses an archive, rejecting versions newer than this build understands.
+    pub fn from_json(json: &str) -> Result<Self, BackupError> {
+        let archive: Archive = serde_json::from_str(json)?;
+        if archive.version > ARCHIVE_VERSION {
+            return Err(BackupError::UnsupportedVersion {
+                found: archive.version,
+                supported: ARCHIVE_VERSION,
+            });
+        }
+        Ok(archive)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BackupError> {
+        std::fs::write(path, self.to_json()?)?;
+        tracing::info!(
+            path = ?path,
+            words = self.words.len(),
+            clozes = self.clozes.len(),
+            "Wrote backup archive"
+        );
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BackupError> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json)
+    }
+}
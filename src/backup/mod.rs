@@ -0,0 +1,26 @@
+//! Whole-library backup and restore.
+//!
+//! Archives are versioned JSON documents built from the `persistence::dto`
+//! types, so the on-disk format is independent of the in-memory models.
+
+mod archive;
+mod restore;
+
+pub use archive::{ARCHIVE_VERSION, Archive};
+pub use restore::{RestoreMode, RestoreSummary, restore, validate};
+
+/// Backup and restore errors.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported archive version {found} (supported up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Archive has {} invalid reference(s): {}", .0.len(), .0.join("; "))]
+    InvalidReferences(Vec<String>),
+}
@@ -0,0 +1,541 @@
+//! Archive validation and restore.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use uuid::Uuid;
+
+use super::{Archive, BackupError};
+use crate::models::{CardSchedule, Cloze, Meaning, ReviewLog, Tag, TagId, Word};
+use crate::persistence::TagDto;
+use crate::registry::QueueItem;
+use crate::state::Model;
+
+/// How an archive is combined with the existing library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum RestoreMode {
+    /// Keep existing data; archive entities replace those with the same id
+    /// and tags join existing tags with the same path
+    #[default]
+    Merge,
+    /// Discard the existing library before restoring
+    Replace,
+}
+
+/// Counts of restored entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RestoreSummary {
+    pub words: usize,
+    pub meanings: usize,
+    pub tags: usize,
+    pub clozes: usize,
+    pub queue_items: usize,
+}
+
+impl fmt::Display for RestoreSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} words, {} meanings, {} tags, {} clozes, {} queue items",
+            self.words, self.meanings, self.tags, self.clozes, self.queue_items
+        )
+    }
+}
+
+/// Checks that every reference in the archive resolves.
+///
+/// In merge mode references may also point at entities already in the model.
+pub fn validate(archive: &Archive, mode: RestoreMode, model: &Model) -> Result<(), BackupError> {
+    let merge = mode == RestoreMode::Merge;
+    let ids = |archive_ids: Vec<Uuid>, existing: Vec<Uuid>| -> BTreeSet<Uuid> {
+        let mut set: BTreeSet<Uuid> = archive_ids.into_iter().collect();
+        if merge {
+            set.extend(existing);
+        }
+        set
+    };
+
+    let words = ids(
+        archive.words.iter().map(|w| w.id).collect(),
+        model.word_registry.iter().map(|(id, _)| id.0).collect(),
+    );
+    let meanings = ids(
+        archive.meanings.iter().map(|m| m.id).collect(),
+        model.meaning_registry.iter().map(|(id, _)| id.0).collect(),
+    );
+    let tags = ids(
+        archive.tags.iter().map(|t| t.id).collect(),
+        model.tag_registry.iter().map(|(id, _)| id.0).collect(),
+    );
+    let clozes = ids(
+        archive.clozes.iter().map(|c| c.id).collect(),
+        model.cloze_registry.iter().map(|(id, _)| id.0).collect(),
+    );
+
+    let mut issues = Vec::new();
+    let mut check = |set: &BTreeSet<Uuid>, id: &Uuid, what: String| {
+        if !set.contains(id) {
+            issues.push(what);
+        }
+    };
+
+    for word in &archive.words {
+        for meaning_id in &word.meaning_ids {
+            check(
+                &meanings,
+                meaning_id,
+                format!("word {} references missing meaning {}", word.id, meaning_id),
+            );
+        }
+    }
+    for meaning in &archive.meanings {
+        check(
+            &words,
+            &meaning.word_id,
+            format!(
+                "meaning {} references missing word {}",
+                meaning.id, meaning.word_id
+            ),
+        );
+        for tag_id in &meaning.tag_ids {
+            check(
+                &tags,
+                tag_id,
+                format!("meaning {} references missing tag {}", meaning.id, tag_id),
+            );
+        }
+    }
+    for tag in &archive.tags {
+        if let Some(parent_id) = &tag.parent_id {
+            check(
+                &tags,
+                parent_id,
+                format!("tag {} references missing parent {}", tag.id, parent_id),
+            );
+        }
+        for child_id in &tag.children_ids {
+            check(
+                &tags,
+                child_id,
+                format!("tag {} references missing child {}", tag.id, child_id),
+            );
+        }
+    }
+    for cloze in &archive.clozes {
+        check(
+            &meanings,
+            &cloze.meaning_id,
+            format!(
+                "cloze {} references missing meaning {}",
+                cloze.id, cloze.meaning_id
+            ),
+        );
+    }
+    for item in &archive.queue {
+        check(
+            &meanings,
+            &item.meaning_id,
+            format!(
+                "queue item {} references missing meaning {}",
+                item.id, item.meaning_id
+            ),
+        );
+    }
+    for schedule in &archive.schedules {
+        check(
+            &clozes,
+            &schedule.cloze_id,
+            format!("schedule references missing cloze {}", schedule.cloze_id),
+        );
+    }
+    for review in &archive.reviews {
+        check(
+            &clozes,
+            &review.cloze_id,
+            format!(
+                "review {} references missing cloze {}",
+                review.id, review.cloze_id
+            ),
+        );
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(BackupError::InvalidReferences(issues))
+    }
+}
+
+/// Validates the archive and loads it into the model. The caller is
+/// responsible for flushing the model afterwards.
+pub fn restore(
+    model: &mut Model,
+    archive: Archive,
+    mode: RestoreMode,
+) -> Result<RestoreSummary, BackupError> {
+    validate(&archive, mode, model)?;
+
+    if mode == RestoreMode::Replace {
+        clear(model);
+    }
+
+    let summary = RestoreSummary {
+        words: archive.words.len(),
+        meanings: archive.meanings.len(),
+        tags: archive.tags.len(),
+        clozes: archive.clozes.len(),
+        queue_items: archive.queue.len(),
+    };
+
+    let tag_ids = restore_tags(model, archive.tags);
+    for dto in archive.words {
+        let mut word = Word::from(dto);
+        if let Some(existing) = model.word_registry.get(word.id) {
+            word.meaning_ids
+                .extend(existing.meaning_ids.iter().copied());
+        }
+        model.word_registry.add(word);
+    }
+    for dto in archive.meanings {
+        let mut meaning = Meaning::from(dto);
+        meaning.tag_ids = meaning
+            .tag_ids
+            .iter()
+            .map(|id| *tag_ids.get(id).unwrap_or(id))
+            .collect();
+        // A meaning moved to another word leaves the old one
+        if let Some(old_word_id) = model.meaning_registry.get(meaning.id).map(|m| m.word_id)
+            && old_word_id != meaning.word_id
+        {
+            model.word_registry.remove_meaning(old_word_id, meaning.id);
+        }
+        let linked = model
+            .word_registry
+            .get(meaning.word_id)
+            .is_some_and(|w| w.meaning_ids.contains(&meaning.id));
+        if !linked {
+            model.word_registry.add_meaning(meaning.word_id, meaning.id);
+        }
+        // Re-adding keeps the word and tag indexes in sync
+        model.meaning_registry.delete(meaning.id);
+        model.meaning_registry.add(meaning);
+    }
+    for dto in archive.clozes {
+        let cloze = Cloze::from(dto);
+        model.cloze_registry.delete(cloze.id);
+        model.cloze_registry.add(cloze);
+    }
+    for dto in archive.queue {
+        model.queue_registry.insert(QueueItem::from(dto));
+    }
+    for dto in archive.schedules {
+        model.schedule_registry.upsert(CardSchedule::from(dto));
+    }
+    for dto in archive.reviews {
+        let review = ReviewLog::from(dto);
+        model.review_registry.delete(review.id);
+        model.review_registry.add(review);
+    }
+
+    tracing::info!(?mode, %summary, "Restored archive");
+    Ok(summary)
+}
+
+/// Adds archive tags and returns the local id of each. A tag whose id is new
+/// but whose path already exists is mapped onto the existing tag instead of
+/// being duplicated. Parent and child links are kept consistent on both
+/// sides, including for tags the archive moves to another parent.
+fn restore_tags(model: &mut Model, dtos: Vec<TagDto>) -> HashMap<TagId, TagId> {
+    // Parents first, so each tag's parent is mapped before it is looked up
+    let archive_ids: HashSet<TagId> = dtos.iter().map(|t| TagId(t.id)).collect();
+    let mut pending: Vec<Tag> = dtos.into_iter().map(Tag::from).collect();
+    let mut ordered = Vec::with_capacity(pending.len());
+    let mut placed = HashSet::new();
+    while !pending.is_empty() {
+        let (ready, rest): (Vec<Tag>, Vec<Tag>) = pending.into_iter().partition(|t| {
+            t.parent_id
+                .is_none_or(|p| !archive_ids.contains(&p) || placed.contains(&p))
+        });
+        // Tags in a parent cycle never become ready; take them as they are
+        if ready.is_empty() {
+            ordered.extend(rest);
+            break;
+        }
+        placed.extend(ready.iter().map(|t| t.id));
+        ordered.extend(ready);
+        pending = rest;
+    }
+
+    let mut ids: HashMap<TagId, TagId> = HashMap::new();
+    for tag in &ordered {
+        let parent_id = tag.parent_id.map(|p| *ids.get(&p).unwrap_or(&p));
+        let local_id = if model.tag_registry.exists(tag.id) {
+            tag.id
+        } else {
+            model
+                .tag_registry
+                .find_child(parent_id, &tag.name)
+                .unwrap_or(tag.id)
+        };
+        ids.insert(tag.id, local_id);
+    }
+
+    let local = |id: &TagId| *ids.get(id).unwrap_or(id);
+    let mut links = Vec::new();
+    for mut tag in ordered {
+        if local(&tag.id) != tag.id {
+            continue;
+        }
+        tag.parent_id = tag.parent_id.as_ref().map(local);
+        tag.children_ids = tag.children_ids.iter().map(local).collect();
+        let old_parent_id = model.tag_registry.get(tag.id).and_then(|existing| {
+            tag.children_ids
+                .extend(existing.children_ids.iter().copied());
+            existing.parent_id
+        });
+        links.push((tag.id, old_parent_id, tag.parent_id));
+        model.tag_registry.add(tag);
+    }
+
+    // Link both sides once every tag is in place
+    for (id, old_parent_id, parent_id) in links {
+        if let Some(old_parent_id) = old_parent_id
+            && parent_id != Some(old_parent_id)
+            && let Some(old_parent) = model.tag_registry.get_mut(old_parent_id)
+        {
+            old_parent.children_ids.remove(&id);
+        }
+        if let Some(parent_id) = parent_id
+            && model
+                .tag_registry
+                .get(parent_id)
+                .is_some_and(|p| !p.children_ids.contains(&id))
+            && let Some(parent) = model.tag_registry.get_mut(parent_id)
+        {
+            parent.children_ids.insert(id);
+        }
+    }
+    ids
+}
+
+/// Removes every entity, marking each one dirty so the deletion is persisted.
+fn clear(model: &mut Model) {
+    let words: Vec<_> = model.word_registry.iter().map(|(id, _)| *id).collect();
+    for id in words {
+        model.word_registry.delete(id);
+    }
+    let meanings: Vec<_> = model.meaning_registry.iter().map(|(id, _)| *id).collect();
+    for id in meanings {
+        model.meaning_registry.delete(id);
+    }
+    let tags: Vec<_> = model.tag_registry.iter().map(|(id, _)| *id).collect();
+    for id in tags {
+        model.tag_registry.delete(id);
+    }
+    let clozes: Vec<_> = model.cloze_registry.iter().map(|(id, _)| *id).collect();
+    for id in clozes {
+        model.cloze_registry.delete(id);
+    }
+    let queue: Vec<_> = model.queue_registry.get_items().map(|i| i.id).collect();
+    for id in queue {
+        model.queue_registry.remove(id);
+    }
+    let schedules: Vec<_> = model.schedule_registry.iter().map(|(id, _)| *id).collect();
+    for id in schedules {
+        model.schedule_registry.delete(id);
+    }
+    let reviews: Vec<_> = model.review_registry.iter().map(|(id, _)| *id).collect();
+    for id in reviews {
+        model.review_registry.delete(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::models::PartOfSpeech;
+    use crate::persistence::Db;
+    use test_case::test_case;
+
+    fn model() -> Model {
+        Model::new(Db::in_memory().unwrap(), AppConfig::default())
+    }
+
+    fn sample_model() -> Model {
+        let mut model = model();
+        let tag = Tag::builder().name("food".to_string()).build();
+        let word = Word::builder().content("apple".to_string()).build();
+        let meaning = Meaning::builder()
+            .word_id(word.id)
+            .definition("a fruit".to_string())
+            .pos(PartOfSpeech::Noun)
+            .build();
+        let cloze = Cloze::builder()
+            .meaning_id(meaning.id)
+            .segments(Cloze::parse_from_sentence("An [apple] a day"))
+            .build();
+        let (word_id, meaning_id, tag_id) = (word.id, meaning.id, tag.id);
+        model.tag_registry.add(tag);
+        model.word_registry.add(word);
+        model.meaning_registry.add(meaning);
+        model.word_registry.add_meaning(word_id, meaning_id);
+        model.meaning_registry.add_tag(meaning_id, tag_id);
+        model.cloze_registry.add(cloze);
+        model.queue_registry.enqueue(meaning_id);
+        model
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let archive = Archive::from_model(&sample_model());
+        let parsed = Archive::from_json(&archive.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, archive);
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let mut archive = Archive::from_model(&model());
+        archive.version = crate::backup::ARCHIVE_VERSION + 1;
+        let json = archive.to_json().unwrap();
+        assert!(matches!(
+            Archive::from_json(&json),
+            Err(BackupError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test_case(RestoreMode::Merge; "merge")]
+    #[test_case(RestoreMode::Replace; "replace")]
+    fn test_restore_into_empty(mode: RestoreMode) {
+        let archive = Archive::from_model(&sample_model());
+        let mut target = model();
+        let summary = restore(&mut target, archive, mode).unwrap();
+        assert_eq!(summary.words, 1);
+        assert_eq!(target.word_registry.count(), 1);
+        assert_eq!(target.meaning_registry.count(), 1);
+        assert_eq!(target.cloze_registry.count(), 1);
+        assert_eq!(target.queue_registry.len(), 1);
+        let tag_id = *target.tag_registry.iter().next().unwrap().0;
+        assert_eq!(target.meaning_registry.iter_by_tag(tag_id).count(), 1);
+    }
+
+    #[test_case(RestoreMode::Merge, 2; "merge keeps existing")]
+    #[test_case(RestoreMode::Replace, 1; "replace discards existing")]
+    fn test_restore_modes(mode: RestoreMode, expected_words: usize) {
+        let archive = Archive::from_model(&sample_model());
+        let mut target = sample_model();
+        restore(&mut target, archive, mode).unwrap();
+        assert_eq!(target.word_registry.count(), expected_words);
+    }
+
+    #[test]
+    fn test_merge_keeps_links_consistent() {
+        // Local: food/fruit and "apple" with a meaning tagged food
+        let mut target = model();
+        let food = Tag::builder().name("food".to_string()).build();
+        let fruit = Tag::builder()
+            .name("fruit".to_string())
+            .parent_id(Some(food.id))
+            .build();
+        let (food_id, fruit_id) = (food.id, fruit.id);
+        target.tag_registry.add(food);
+        target.tag_registry.add(fruit);
+        target
+            .tag_registry
+            .get_mut(food_id)
+            .unwrap()
+            .children_ids
+            .insert(fruit_id);
+        let apple = Word::builder().content("apple".to_string()).build();
+        let meaning = Meaning::builder()
+            .word_id(apple.id)
+            .definition("a fruit".to_string())
+            .pos(PartOfSpeech::Noun)
+            .build();
+        let (apple_id, meaning_id) = (apple.id, meaning.id);
+        target.word_registry.add(apple);
+        target.meaning_registry.add(meaning);
+        target.word_registry.add_meaning(apple_id, meaning_id);
+        target.meaning_registry.add_tag(meaning_id, food_id);
+
+        // Archive: fruit moved under travel, the meaning moved to "pear"
+        // and tagged with a "Food" tag that has its own id
+        let mut source = model();
+        let other_food = Tag::builder().name("Food".to_string()).build();
+        let travel = Tag::builder().name("travel".to_string()).build();
+        let moved_fruit = Tag::builder()
+            .id(fruit_id)
+            .name("fruit".to_string())
+            .parent_id(Some(travel.id))
+            .build();
+        let (other_food_id, travel_id) = (other_food.id, travel.id);
+        source.tag_registry.add(other_food);
+        source.tag_registry.add(travel);
+        source.tag_registry.add(moved_fruit);
+        source
+            .tag_registry
+            .get_mut(travel_id)
+            .unwrap()
+            .children_ids
+            .insert(fruit_id);
+        let pear = Word::builder().content("pear".to_string()).build();
+        let moved_meaning = Meaning::builder()
+            .id(meaning_id)
+            .word_id(pear.id)
+            .definition("a fruit".to_string())
+            .pos(PartOfSpeech::Noun)
+            .build();
+        let pear_id = pear.id;
+        source.word_registry.add(pear);
+        source.meaning_registry.add(moved_meaning);
+        source.word_registry.add_meaning(pear_id, meaning_id);
+        source.meaning_registry.add_tag(meaning_id, other_food_id);
+
+        let archive = Archive::from_model(&source);
+        restore(&mut target, archive, RestoreMode::Merge).unwrap();
+
+        let tags = &target.tag_registry;
+        assert_eq!(tags.count(), 3);
+        assert!(!tags.exists(other_food_id));
+        assert_eq!(tags.get(fruit_id).unwrap().parent_id, Some(travel_id));
+        assert!(
+            tags.get(travel_id)
+                .unwrap()
+                .children_ids
+                .contains(&fruit_id)
+        );
+        assert!(!tags.get(food_id).unwrap().children_ids.contains(&fruit_id));
+
+        let meaning = target.meaning_registry.get(meaning_id).unwrap();
+        assert_eq!(meaning.word_id, pear_id);
+        assert_eq!(meaning.tag_ids, BTreeSet::from([food_id]));
+        assert_eq!(target.meaning_registry.iter_by_tag(food_id).count(), 1);
+        let words = &target.word_registry;
+        assert!(
+            words
+                .get(pear_id)
+                .unwrap()
+                .meaning_ids
+                .contains(&meaning_id)
+        );
+        assert!(
+            !words
+                .get(apple_id)
+                .unwrap()
+                .meaning_ids
+                .contains(&meaning_id)
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_dangling_references() {
+        let mut archive = Archive::from_model(&sample_model());
+        archive.words.clear();
+        archive.tags.clear();
+        let err = validate(&archive, RestoreMode::Replace, &model()).unwrap_err();
+        let BackupError::InvalidReferences(issues) = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(issues.len(), 2);
+    }
+}
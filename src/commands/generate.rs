@@ -0,0 +1,104 @@
+//! Headless cloze generation over the persistent queue.
+
+use std::collections::HashSet;
+
+use super::CommandError;
+use crate::models::MeaningId;
+use crate::registry::QueueItemStatus;
+use crate::state::{GenerationRequest, Model};
+
+/// Generates candidate clozes for pending queue items, one request at a time.
+///
+/// With a tag path, tagged meanings that have no clozes and are not queued
+/// are enqueued first, and only items for tagged meanings are processed.
+/// Candidates are left for review in the queue panel unless `accept` is set,
+/// in which case they are added as clozes directly. Individual failures are
+/// recorded on the queue item and reported; they do not abort the run.
+pub fn generate(model: &mut Model, tag: Option<&str>, accept: bool) -> Result<(), CommandError> {
+    let generator = model
+        .generator
+        .generator()
+        .ok_or(CommandError::NoGenerator)?;
+    let count = model.app_config.ai.candidates();
+
+    let scope: Option<HashSet<MeaningId>> = match tag {
+        Some(path) => {
+            let tag_id = model
+                .tag_registry
+                .find_by_path(path)
+                .ok_or_else(|| CommandError::Invalid(format!("Tag \"{}\" not found", path)))?;
+            let meanings: HashSet<MeaningId> = model
+                .meaning_registry
+                .iter_by_tag(tag_id)
+                .map(|(id, _)| *id)
+                .collect();
+            for meaning_id in &meanings {
+                if model.cloze_registry.count_by_meaning(*meaning_id) == 0
+                    && !model.queue_registry.contains(*meaning_id)
+                {
+                    model.queue_registry.enqueue(*meaning_id);
+                }
+            }
+            Some(meanings)
+        }
+        None => None,
+    };
+
+    let items: Vec<_> = model
+        .queue_registry
+        .get_items()
+        .filter(|item| item.status == QueueItemStatus::Pending)
+        .filter(|item| scope.as_ref().is_none_or(|s| s.contains(&item.meaning_id)))
+        .map(|item| (item.id, item.meaning_id))
+        .collect();
+    println!("Generating for {} queue item(s)", items.len());
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let (mut generated, mut failed) = (0, 0);
+    for (item_id, meaning_id) in items {
+        let request = match GenerationRequest::new(model, meaning_id, count) {
+            Ok(request) => request,
+            Err(error) => {
+                model.queue_registry.set_failed(item_id, error);
+                failed += 1;
+                continue;
+            }
+        };
+        let word = &request.word;
+
+        model.queue_registry.set_processing(item_id);
+        match runtime.block_on(generator.generate(&request)) {
+            Ok(candidates) => {
+                for cloze in &candidates {
+                    println!("  {}: {}", word.content, cloze.render_answers());
+                }
+                generated += candidates.len();
+                if accept {
+                    model.queue_registry.set_completed(item_id);
+                    for cloze in candidates {
+                        model.cloze_registry.add(cloze);
+                    }
+                } else {
+                    model.queue_registry.set_candidates(item_id, candidates);
+                }
+            }
+            Err(e) => {
+                eprintln!("  {}: {}", word.content, e);
+                model.queue_registry.set_failed(item_id, e.to_string());
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Generated {} {}, failed {}",
+        generated,
+        if accept {
+            "cloze(s)"
+        } else {
+            "candidate(s) for review"
+        },
+        failed
+    );
+    Ok(())
+}
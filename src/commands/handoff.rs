@@ -0,0 +1,172 @@
+//! Requests handed from a second Clozer process to the running GUI.
+//!
+//! Only one process can hold a library's database. A process that finds it
+//! held by the GUI drops a request file into the library's inbox directory;
+//! the GUI polls the inbox and carries the requests out. A request nobody
+//! picks up in time is withdrawn, since the marker that suggested a running
+//! GUI may be left over from a crash.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::library::WordAdd;
+use crate::config::constants::paths;
+
+/// A request for the running GUI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Request {
+    /// Bring the window to the front
+    Focus,
+    /// Run `word add` against the open library
+    AddWord(WordAdd),
+}
+
+/// Inbox directory of a library.
+#[derive(Debug, Clone)]
+pub struct Inbox {
+    dir: PathBuf,
+}
+
+impl Inbox {
+    /// Inbox of the library in `library_dir`.
+    pub fn new(library_dir: &Path) -> Self {
+        Self {
+            dir: library_dir.join(paths::INBOX),
+        }
+    }
+
+    /// Queues a request and returns its file. The file is renamed into
+    /// place so the GUI never reads it half-written.
+    pub fn send(&self, request: &Request) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let id = Uuid::new_v4();
+        let partial = self.dir.join(format!("{}.tmp", id));
+        let path = self.dir.join(format!("{}.json", id));
+        let content = serde_json::to_string(request).map_err(std::io::Error::other)?;
+        std::fs::write(&partial, content)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// Queues a request and waits up to `timeout` for a running GUI to pick
+    /// it up. Returns false, with the request withdrawn, if nothing did.
+    pub fn deliver(&self, request: &Request, timeout: Duration) -> std::io::Result<bool> {
+        let path = self.send(request)?;
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if !path.exists() {
+                return Ok(true);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                tracing::warn!(path = ?path, "No running instance picked up the request");
+                Ok(false)
+            }
+            // Drained just as the wait ran out
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Removes and returns the queued requests, oldest first. Unreadable
+    /// requests are logged and dropped.
+    pub fn drain(&self) -> Vec<Request> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .map(|path| {
+                let modified = std::fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                (modified, path)
+            })
+            .collect();
+        files.sort();
+
+        files
+            .into_iter()
+            .filter_map(|(_, path)| {
+                let content = std::fs::read_to_string(&path);
+                if let Err(e) = std::fs::remove_file(&path) {
+                    tracing::warn!(error = %e, path = ?path, "Failed to remove inbox request");
+                }
+                match content.map(|c| serde_json::from_str(&c)) {
+                    Ok(Ok(request)) => Some(request),
+                    Ok(Err(e)) => {
+                        tracing::warn!(error = %e, path = ?path, "Ignoring unreadable inbox request");
+                        None
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, path = ?path, "Failed to read inbox request");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::data_dir;
+
+    #[test]
+    fn test_sent_requests_are_drained_once() {
+        let dir = data_dir("inbox");
+        let inbox = Inbox::new(&dir);
+        assert!(inbox.drain().is_empty());
+
+        inbox.send(&Request::Focus).unwrap();
+        inbox
+            .send(&Request::AddWord(WordAdd {
+                word: "run".to_string(),
+                ..WordAdd::default()
+            }))
+            .unwrap();
+
+        let requests = inbox.drain();
+        assert_eq!(requests.len(), 2);
+        assert!(
+            requests
+                .iter()
+                .any(|r| matches!(r, Request::AddWord(args) if args.word == "run"))
+        );
+        assert!(inbox.drain().is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_undelivered_request_is_withdrawn() {
+        let dir = data_dir("inbox-stale");
+        let inbox = Inbox::new(&dir);
+        let delivered = inbox
+            .deliver(&Request::Focus, Duration::from_millis(50))
+            .unwrap();
+        assert!(!delivered);
+        assert!(inbox.drain().is_empty());
+
+        let receiver = inbox.clone();
+        let gui = std::thread::spawn(move || {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while receiver.drain().is_empty() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+        let delivered = inbox
+            .deliver(&Request::Focus, Duration::from_secs(5))
+            .unwrap();
+        gui.join().unwrap();
+        assert!(delivered);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
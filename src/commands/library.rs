@@ -0,0 +1,242 @@
+//! Word management, listing and export commands.
+
+use std::path::Path;
+
+use langtag::LangTagBuf;
+use serde::{Deserialize, Serialize};
+
+use super::CommandError;
+use crate::export::{self, ExportFormat};
+use crate::models::{CefrLevel, ClozeId, PartOfSpeech, WordId};
+use crate::query::{Query, QueryEngine, TagResolver, parse_query_with_diagnostics};
+use crate::state::Model;
+
+/// Arguments of `word add`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WordAdd {
+    pub word: String,
+    pub definition: Option<String>,
+    pub pos: Option<String>,
+    pub cefr: Option<String>,
+    pub language: Option<String>,
+    pub tags: Vec<String>,
+    pub enqueue: bool,
+}
+
+/// Adds a word, reusing an existing word with the same content, and
+/// optionally a tagged meaning. Returns a line describing each change.
+pub fn word_add(model: &mut Model, args: WordAdd) -> Result<Vec<String>, CommandError> {
+    let pos = match &args.pos {
+        Some(pos) => PartOfSpeech::try_from_str(pos)
+            .ok_or_else(|| CommandError::Invalid(format!("Unknown part of speech \"{}\"", pos)))?,
+        None => PartOfSpeech::default(),
+    };
+    let cefr = match &args.cefr {
+        Some(cefr) => Some(
+            CefrLevel::try_from_str(cefr)
+                .ok_or_else(|| CommandError::Invalid(format!("Unknown CEFR level \"{}\"", cefr)))?,
+        ),
+        None => None,
+    };
+    let language = match &args.language {
+        Some(lang) => Some(
+            lang.parse::<LangTagBuf>()
+                .map_err(|_| CommandError::Invalid(format!("Invalid language tag \"{}\"", lang)))?,
+        ),
+        None => None,
+    };
+
+    let (word_id, created) = match model.word_registry.find_by_content(args.word.trim()) {
+        Some(id) => (id, false),
+        None => {
+            let id = model
+                .word_registry
+                .create_word(&args.word, language)
+                .ok_or_else(|| CommandError::Invalid("Word must not be empty".to_string()))?;
+            (id, true)
+        }
+    };
+    let mut report = Vec::new();
+    if created {
+        report.push(format!("Added word \"{}\"", args.word.trim()));
+    }
+
+    let Some(definition) = &args.definition else {
+        if !created {
+            report.push(format!("Word \"{}\" already exists", args.word.trim()));
+        }
+        return Ok(report);
+    };
+    let meaning_id = model
+        .meaning_registry
+        .create_meaning(word_id, definition, pos, cefr)
+        .ok_or_else(|| CommandError::Invalid("Definition must not be empty".to_string()))?;
+    model.word_registry.add_meaning(word_id, meaning_id);
+
+    for path in &args.tags {
+        let tag_id = model
+            .tag_registry
+            .get_or_create_path(path)
+            .ok_or_else(|| CommandError::Invalid(format!("Invalid tag path \"{}\"", path)))?;
+        model.meaning_registry.add_tag(meaning_id, tag_id);
+    }
+    if args.enqueue {
+        model.queue_registry.enqueue(meaning_id);
+    }
+
+    report.push(format!(
+        "Added meaning ({}) \"{}\"{}",
+        pos,
+        definition.trim(),
+        if args.enqueue { ", queued" } else { "" }
+    ));
+    Ok(report)
+}
+
+/// Prints the words matching `query` with their meanings.
+pub fn list(model: &Model, query: Option<&str>) {
+    let results = matching_words(model, query);
+    for word_id in &results {
+        let Some(word) = model.word_registry.get(*word_id) else {
+            continue;
+        };
+        println!("{}", word.content);
+        for (meaning_id, meaning) in model.meaning_registry.iter_by_word(*word_id) {
+            let cefr = meaning
+                .cefr_level
+                .map(|level| format!(" [{}]", level))
+                .unwrap_or_default();
+            println!(
+                "  ({}){} {} - {} cloze(s)",
+                meaning.pos,
+                cefr,
+                meaning.definition,
+                model.cloze_registry.count_by_meaning(*meaning_id)
+            );
+        }
+    }
+    println!("{} word(s)", results.len());
+}
+
+/// Exports the clozes of words matching `query` to `path`, or stdout.
+pub fn export(
+    model: &Model,
+    path: Option<&Path>,
+    format: ExportFormat,
+    query: Option<&str>,
+) -> Result<(), CommandError> {
+    let cloze_ids: Vec<ClozeId> = matching_words(model, query)
+        .into_iter()
+        .flat_map(|word_id| model.meaning_registry.iter_by_word(word_id))
+        .flat_map(|(meaning_id, _)| model.cloze_registry.iter_by_meaning_id(*meaning_id))
+        .map(|(cloze_id, _)| *cloze_id)
+        .collect();
+    let clozes = cloze_ids
+        .iter()
+        .filter_map(|id| model.cloze_registry.get(*id));
+
+    let output = match format {
+        ExportFormat::Anki => export::to_anki_tsv(
+            clozes,
+            &model.word_registry,
+            &model.meaning_registry,
+            &model.tag_registry,
+        ),
+        ExportFormat::Plaintext => {
+            let mut lines: Vec<String> = clozes.map(|c| c.render_answers()).collect();
+            lines.push(String::new());
+            lines.join("\n")
+        }
+    };
+
+    match path {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            println!(
+                "Exported {} cloze(s) to {}",
+                cloze_ids.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Runs a search-bar query against the model; no query matches every word.
+fn matching_words(model: &Model, query: Option<&str>) -> Vec<WordId> {
+    let query = match query.map(str::trim).filter(|q| !q.is_empty()) {
+        Some(text) => {
+            let mut resolver = TagResolver::new(&model.tag_registry);
+            let (query, diagnostics) = parse_query_with_diagnostics(text, &mut resolver);
+            for diagnostic in diagnostics {
+                eprintln!(
+                    "Warning: {} (column {})",
+                    diagnostic,
+                    diagnostic.span.start + 1
+                );
+            }
+            query
+        }
+        None => Query::empty(),
+    };
+    let engine = QueryEngine::new(
+        &model.word_registry,
+        &model.meaning_registry,
+        &model.cloze_registry,
+        &model.queue_registry,
+    );
+    engine
+        .execute(&query)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::persistence::Db;
+
+    fn model() -> Model {
+        Model::new(Db::in_memory().unwrap(), AppConfig::default())
+    }
+
+    #[test]
+    fn test_word_add_reuses_existing_word() {
+        let mut model = model();
+        let add = |definition: &str| WordAdd {
+            word: "run".to_string(),
+            definition: Some(definition.to_string()),
+            pos: Some("verb".to_string()),
+            tags: vec!["Verbs/Motion".to_string()],
+            enqueue: true,
+            ..WordAdd::default()
+        };
+        word_add(&mut model, add("to move fast")).unwrap();
+        word_add(&mut model, add("to operate")).unwrap();
+
+        assert_eq!(model.word_registry.count(), 1);
+        assert_eq!(model.meaning_registry.count(), 2);
+        assert_eq!(model.queue_registry.len(), 2);
+        let motion = model.tag_registry.find_by_path("Verbs/Motion").unwrap();
+        assert_eq!(model.meaning_registry.iter_by_tag(motion).count(), 2);
+    }
+
+    #[test]
+    fn test_word_add_rejects_unknown_pos() {
+        let mut model = model();
+        let args = WordAdd {
+            word: "run".to_string(),
+            definition: Some("to move fast".to_string()),
+            pos: Some("verbish".to_string()),
+            ..WordAdd::default()
+        };
+        assert!(matches!(
+            word_add(&mut model, args),
+            Err(CommandError::Invalid(_))
+        ));
+        assert_eq!(model.word_registry.count(), 0);
+    }
+}
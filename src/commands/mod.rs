@@ -0,0 +1,240 @@
+//! Headless runner for CLI subcommands.
+//!
+//! Commands operate on the same database as the GUI and flush their changes
+//! before returning; read-only ones leave it untouched. While the GUI holds
+//! the database, read-only commands read a copy of it and `word add` is
+//! handed to the GUI.
+
+mod generate;
+pub mod handoff;
+mod library;
+
+pub use library::{WordAdd, word_add};
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::backup::{self, Archive, BackupError, RestoreMode};
+use crate::config::constants::{db, inbox};
+use crate::config::{AppConfig, Command, WordCommand};
+use crate::integrity::{self, RepairOptions};
+use crate::persistence::{Db, DbError, SnapshotReason};
+use crate::state::{Model, SessionMarker};
+
+/// Command errors.
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error(transparent)]
+    Db(#[from] DbError),
+
+    #[error(transparent)]
+    Backup(#[from] BackupError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Invalid(String),
+
+    #[error("No AI model configured; add a provider and model in the settings")]
+    NoGenerator,
+
+    #[error(
+        "The library is open in another Clozer instance; close it first, or use --read-only for commands that only read"
+    )]
+    Locked,
+}
+
+/// Runs a subcommand against the configured data directory.
+///
+/// With `read_only`, the command runs against a copy of the library and
+/// commands that change it are refused.
+pub fn run(command: Command, config: AppConfig, read_only: bool) -> Result<(), CommandError> {
+    if read_only && !command.is_read_only() {
+        return Err(CommandError::Invalid(
+            "This command changes the library and cannot run with --read-only".to_string(),
+        ));
+    }
+    // Read-only commands, including a doctor dry run, never flush
+    let writes = !command.is_read_only();
+    let mut model = if read_only {
+        open_copy(config)?
+    } else {
+        match open_model(config.clone()) {
+            Ok(model) => model,
+            Err(DbError::Locked(_)) => return run_locked(command, config),
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    match command {
+        Command::Backup { path } => run_backup(&model, &path)?,
+        Command::Restore { path, mode } => run_restore(&mut model, &path, mode)?,
+        Command::Doctor {
+            repair,
+            delete_orphans,
+        } => run_doctor(
+            &mut model,
+            RepairOptions {
+                fix_links: repair,
+                delete_orphans,
+            },
+        ),
+        Command::Word {
+            command:
+                WordCommand::Add {
+                    word,
+                    definition,
+                    pos,
+                    cefr,
+                    language,
+                    tags,
+                    enqueue,
+                },
+        } => {
+            let report = library::word_add(
+                &mut model,
+                library::WordAdd {
+                    word,
+                    definition,
+                    pos,
+                    cefr,
+                    language,
+                    tags,
+                    enqueue,
+                },
+            )?;
+            for line in report {
+                println!("{}", line);
+            }
+        }
+        Command::List { query } => library::list(&model, query.as_deref()),
+        Command::Generate { tag, accept } => {
+            generate::generate(&mut model, tag.as_deref(), accept)?
+        }
+        Command::Export {
+            path,
+            format,
+            query,
+        } => library::export(&model, path.as_deref(), format, query.as_deref())?,
+    }
+
+    if writes {
+        model.flush_all()?;
+    }
+    Ok(())
+}
+
+/// Handles a command whose library is held by another process: `word add`
+/// is handed to a running GUI if one picks it up, and read-only commands
+/// run against a copy.
+fn run_locked(command: Command, config: AppConfig) -> Result<(), CommandError> {
+    let library_dir = config.library_dir();
+    if let Command::Word {
+        command:
+            WordCommand::Add {
+                word,
+                definition,
+                pos,
+                cefr,
+                language,
+                tags,
+                enqueue,
+            },
+    } = &command
+        && SessionMarker::is_present(&library_dir)
+    {
+        let request = handoff::Request::AddWord(library::WordAdd {
+            word: word.clone(),
+            definition: definition.clone(),
+            pos: pos.clone(),
+            cefr: cefr.clone(),
+            language: language.clone(),
+            tags: tags.clone(),
+            enqueue: *enqueue,
+        });
+        let timeout = Duration::from_millis(inbox::ACK_MILLIS);
+        if handoff::Inbox::new(&library_dir).deliver(&request, timeout)? {
+            println!("Clozer is running; sent \"{}\" to it", word.trim());
+            return Ok(());
+        }
+    }
+    if !command.is_read_only() {
+        return Err(CommandError::Locked);
+    }
+    tracing::warn!("Library is open in another instance; reading its last saved state");
+    run(command, config, true)
+}
+
+/// Opens the database and loads every registry.
+pub fn open_model(config: AppConfig) -> Result<Model, DbError> {
+    let db_path = config.library_dir().join(db::NAME);
+    tracing::debug!("Opening database at {:?}", db_path);
+    let db = Db::new(&db_path)?;
+    Ok(load_model(db, config))
+}
+
+/// Loads a copy of the database, leaving the file itself untouched.
+fn open_copy(config: AppConfig) -> Result<Model, DbError> {
+    let db_path = config.library_dir().join(db::NAME);
+    tracing::debug!("Reading a copy of the database at {:?}", db_path);
+    let db = Db::open_copy(&db_path)?;
+    Ok(load_model(db, config))
+}
+
+fn load_model(db: Db, config: AppConfig) -> Model {
+    let mut model = Model::new(db, config);
+    model.generator.load_from_config(&model.app_config);
+    model.load_all();
+    model
+}
+
+fn run_backup(model: &Model, path: &Path) -> Result<(), BackupError> {
+    let archive = Archive::from_model(model);
+    archive.save(path)?;
+    println!(
+        "Backed up {} words, {} meanings, {} tags, {} clozes to {}",
+        archive.words.len(),
+        archive.meanings.len(),
+        archive.tags.len(),
+        archive.clozes.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn run_restore(model: &mut Model, path: &Path, mode: RestoreMode) -> Result<(), CommandError> {
+    let archive = Archive::load(path)?;
+    backup::validate(&archive, mode, model)?;
+    let snapshot = model.snapshot(SnapshotReason::Import)?;
+    println!("Saved snapshot {}", snapshot.path.display());
+    let summary = backup::restore(model, archive, mode)?;
+    println!("Restored {}", summary);
+    Ok(())
+}
+
+/// Reports integrity issues, then repairs those allowed by `options`.
+fn run_doctor(model: &mut Model, options: RepairOptions) {
+    let issues = integrity::check(model);
+    if issues.is_empty() {
+        println!("No problems found");
+        return;
+    }
+    for issue in &issues {
+        let action = if issue.is_orphan() { "delete" } else { "fix" };
+        println!("  [{}] {}", action, issue);
+    }
+    println!("Found {} problem(s)", issues.len());
+
+    if options == RepairOptions::default() {
+        println!("Dry run; use --repair (and --delete-orphans) to fix");
+        return;
+    }
+    let fixed = integrity::repair(model, options);
+    let remaining = integrity::check(model).len();
+    println!(
+        "Repaired {} problem(s), {} remaining",
+        fixed.len(),
+        remaining
+    );
+}
@@ -4,9 +4,11 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
+use crate::backup::RestoreMode;
 use crate::config::LogLevel;
+use crate::export::ExportFormat;
 use crate::i18n::LocaleDto;
 use crate::ui::theme::AppTheme;
 
@@ -32,6 +34,117 @@ pub struct CliConfig {
     /// UI locale (en-us, zh-cn, ja-jp, ko-kr)
     #[clap(long)]
     pub locale: Option<LocaleDto>,
+
+    /// Profile to open, created if it does not exist
+    #[clap(short, long)]
+    pub profile: Option<String>,
+
+    /// Read a copy of the library instead of opening it, so commands can
+    /// run while the GUI has it open
+    #[clap(long)]
+    pub read_only: bool,
+
+    /// Run a command instead of starting the GUI
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Headless subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Write a JSON backup of the whole library
+    Backup {
+        /// Destination file
+        path: PathBuf,
+    },
+    /// Restore a JSON backup into the library
+    Restore {
+        /// Archive to restore
+        path: PathBuf,
+        /// Merge into the existing library or replace it
+        #[clap(long, value_enum, default_value_t)]
+        mode: RestoreMode,
+    },
+    /// Check the library for broken references
+    Doctor {
+        /// Fix broken links between words, meanings and tags
+        #[clap(long)]
+        repair: bool,
+        /// Also delete meanings, clozes and review data whose owner is gone
+        #[clap(long, requires = "repair")]
+        delete_orphans: bool,
+    },
+    /// Manage words
+    Word {
+        #[clap(subcommand)]
+        command: WordCommand,
+    },
+    /// List words matching a query
+    List {
+        /// Search query, using the same syntax as the search bar
+        #[clap(short, long)]
+        query: Option<String>,
+    },
+    /// Generate candidate clozes for pending queue items
+    Generate {
+        /// Only generate for meanings with this tag path (e.g. Travel/Food);
+        /// tagged meanings without clozes are queued first
+        #[clap(short, long)]
+        tag: Option<String>,
+        /// Add every candidate as a cloze instead of leaving them for review
+        #[clap(long)]
+        accept: bool,
+    },
+    /// Export clozes of matching words
+    Export {
+        /// Destination file (stdout if omitted)
+        path: Option<PathBuf>,
+        /// Output format
+        #[clap(short, long, value_enum, default_value_t)]
+        format: ExportFormat,
+        /// Search query selecting the words to export
+        #[clap(short, long)]
+        query: Option<String>,
+    },
+}
+
+impl Command {
+    /// Returns true if the command never changes the library.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Command::Backup { .. } | Command::List { .. } | Command::Export { .. } => true,
+            Command::Doctor { repair, .. } => !repair,
+            Command::Restore { .. } | Command::Word { .. } | Command::Generate { .. } => false,
+        }
+    }
+}
+
+/// Word subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum WordCommand {
+    /// Add a word, or a meaning to an existing word
+    Add {
+        /// Word content
+        word: String,
+        /// Meaning definition
+        #[clap(short, long)]
+        definition: Option<String>,
+        /// Part of speech (noun, verb, ...)
+        #[clap(short, long, requires = "definition")]
+        pos: Option<String>,
+        /// CEFR level (A1-C2)
+        #[clap(long, requires = "definition")]
+        cefr: Option<String>,
+        /// Language tag (e.g. en, fr)
+        #[clap(short, long)]
+        language: Option<String>,
+        /// Tag path for the meaning; repeatable
+        #[clap(short, long = "tag", requires = "definition")]
+        tags: Vec<String>,
+        /// Queue the meaning for cloze generation
+        #[clap(short, long, requires = "definition")]
+        enqueue: bool,
+    },
 }
 
 impl CliConfig {
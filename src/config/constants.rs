@@ -15,6 +15,14 @@ pub mod paths {
 
     pub const DATA: &str = "data";
     pub const CONFIG: &str = "clozer.toml";
+    /// Marker file present while the GUI is running, inside the data directory
+    pub const SESSION: &str = "session.json";
+    /// Database snapshots, inside the data directory
+    pub const BACKUPS: &str = "backups";
+    /// Requests for the running GUI from other processes, inside the data directory
+    pub const INBOX: &str = "inbox";
+    /// Profile libraries, inside the data directory
+    pub const PROFILES: &str = "profiles";
 
     #[cfg(debug_assertions)]
     pub const DEBUG_DATA: &str = ".clozer-data";
@@ -47,3 +55,32 @@ pub mod paths {
 pub mod db {
     pub const NAME: &str = "data.redb";
 }
+
+pub mod autosave {
+    /// Seconds between autosaves when not configured
+    pub const DEFAULT_SECS: u64 = 30;
+}
+
+pub mod history {
+    /// Undoable edits kept in memory
+    pub const MAX_UNDO: usize = 100;
+}
+
+pub mod backup {
+    /// Snapshots kept when not configured
+    pub const DEFAULT_RETENTION: usize = 10;
+    /// Hours between daily snapshot checks
+    pub const CHECK_HOURS: u64 = 1;
+}
+
+pub mod inbox {
+    /// Milliseconds between checks for requests from other processes
+    pub const POLL_MILLIS: u64 = 500;
+    /// Milliseconds a handed-off request waits for the GUI to pick it up
+    pub const ACK_MILLIS: u64 = 3000;
+}
+
+pub mod trash {
+    /// Days deleted items are kept when not configured
+    pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+}
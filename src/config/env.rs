@@ -15,6 +15,7 @@ pub struct EnvConfig {
     pub log_level: Option<super::LogLevel>,
     pub theme: Option<AppTheme>,
     pub locale: Option<LocaleDto>,
+    pub profile: Option<String>,
 }
 
 impl EnvConfig {
@@ -114,6 +114,31 @@ pub struct ModelConfig {
 
     /// Model ID on the provider platform
     pub model_id: String,
+
+    /// Prompt template used by this model (built-in default if unset)
+    pub template_id: Option<Uuid>,
+}
+
+/// Prompt template for cloze generation.
+///
+/// Placeholders: `{word}`, `{definition}`, `{pos}`, `{cefr}`, `{language}`,
+/// `{tags}` and `{count}`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PromptTemplateConfig {
+    /// Unique identifier for this template
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+
+    /// Display name
+    pub name: String,
+
+    /// Template text
+    pub template: String,
+
+    /// Tag paths (e.g. "Travel/Food") whose meanings use this template,
+    /// including meanings of descendant tags
+    pub tags: Vec<String>,
 }
 
 impl From<&ModelConfig> for Model {
@@ -133,8 +158,14 @@ impl From<ModelConfig> for Model {
     }
 }
 
+/// Default number of candidate sentences requested per meaning.
+pub const DEFAULT_CANDIDATES_PER_REQUEST: usize = 3;
+
+/// Upper bound for candidates per request.
+pub const MAX_CANDIDATES_PER_REQUEST: usize = 10;
+
 /// AI configuration for LLM settings.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AiConfig {
     /// List of providers
@@ -148,4 +179,30 @@ pub struct AiConfig {
     /// Currently selected model ID for cloze generation
     #[serde(default)]
     pub selected_model_id: Option<Uuid>,
+
+    /// Candidate sentences requested per meaning, reviewed in the queue
+    pub candidates_per_request: usize,
+
+    /// Prompt templates, selected per tag or per model
+    pub templates: Vec<PromptTemplateConfig>,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            providers: Vec::new(),
+            models: Vec::new(),
+            selected_model_id: None,
+            candidates_per_request: DEFAULT_CANDIDATES_PER_REQUEST,
+            templates: Vec::new(),
+        }
+    }
+}
+
+impl AiConfig {
+    /// Candidates per request, clamped to `1..=MAX_CANDIDATES_PER_REQUEST`.
+    pub fn candidates(&self) -> usize {
+        self.candidates_per_request
+            .clamp(1, MAX_CANDIDATES_PER_REQUEST)
+    }
 }
@@ -16,4 +16,12 @@ pub struct GeneralConfig {
     pub log_level: Option<LogLevel>,
     pub theme: Option<AppTheme>,
     pub locale: Option<LocaleDto>,
+    /// Seconds between autosaves of unsaved changes (0 disables)
+    pub autosave_secs: Option<u64>,
+    /// Number of database snapshots to keep in the backups directory
+    pub backup_retention: Option<usize>,
+    /// Days a deleted item stays in the trash before it is purged
+    pub trash_retention_days: Option<u32>,
+    /// Active profile; the library in `data_dir` itself when unset
+    pub profile: Option<String>,
 }
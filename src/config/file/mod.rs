@@ -4,9 +4,11 @@
 
 pub mod ai;
 pub mod general;
+pub mod profile;
 
-pub use ai::{AiConfig, ModelConfig, ProviderConfig};
+pub use ai::{AiConfig, ModelConfig, PromptTemplateConfig, ProviderConfig};
 pub use general::GeneralConfig;
+pub use profile::ProfileConfig;
 
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +18,7 @@ use serde::{Deserialize, Serialize};
 pub struct FileConfig {
     pub general: GeneralConfig,
     pub ai: AiConfig,
+    pub profiles: Vec<ProfileConfig>,
 }
 
 impl FileConfig {
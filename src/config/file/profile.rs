@@ -0,0 +1,37 @@
+//! Profile configuration section.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named library with its own database.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProfileConfig {
+    /// Display name, also the directory name under `<data_dir>/profiles`
+    pub name: String,
+    /// Library directory, overriding `<data_dir>/profiles/<name>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
+    /// Model used for cloze generation in this profile, overriding
+    /// `ai.selected_model_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_model_id: Option<Uuid>,
+}
+
+impl ProfileConfig {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            data_dir: None,
+            selected_model_id: None,
+        }
+    }
+
+    /// Returns true if `name` can be used as a profile name: non-empty
+    /// after trimming and usable as a single directory name.
+    pub fn is_valid_name(name: &str) -> bool {
+        let name = name.trim();
+        !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':'])
+    }
+}
@@ -14,11 +14,14 @@ pub mod file;
 use crate::i18n::LocaleDto;
 use crate::ui::theme::AppTheme;
 use clap::ValueEnum;
-pub use cli::CliConfig;
+pub use cli::{CliConfig, Command, WordCommand};
 pub use constants::paths;
 pub use env::EnvConfig;
 pub use error::ConfigError;
-pub use file::{AiConfig, FileConfig, GeneralConfig, ModelConfig, ProviderConfig};
+pub use file::{
+    AiConfig, FileConfig, GeneralConfig, ModelConfig, ProfileConfig, PromptTemplateConfig,
+    ProviderConfig,
+};
 use serde::{Deserialize, Serialize};
 
 use std::path::PathBuf;
@@ -73,8 +76,23 @@ pub struct AppConfig {
     /// UI locale
     pub locale: LocaleDto,
 
+    /// Seconds between autosaves (0 disables)
+    pub autosave_secs: u64,
+
+    /// Database snapshots to keep per reason (at least 1)
+    pub backup_retention: usize,
+
+    /// Days deleted items stay in the trash (at least 1)
+    pub trash_retention_days: u32,
+
     /// AI configuration
     pub ai: AiConfig,
+
+    /// Named libraries besides the one in `data_dir`
+    pub profiles: Vec<ProfileConfig>,
+
+    /// Active profile; None for the library in `data_dir`
+    pub profile: Option<String>,
 }
 
 impl Default for AppConfig {
@@ -85,12 +103,62 @@ impl Default for AppConfig {
             log_level: LogLevel::DEFAULT,
             theme: AppTheme::Light,
             locale: LocaleDto::default(),
+            autosave_secs: constants::autosave::DEFAULT_SECS,
+            backup_retention: constants::backup::DEFAULT_RETENTION,
+            trash_retention_days: constants::trash::DEFAULT_RETENTION_DAYS,
             ai: AiConfig::default(),
+            profiles: Vec::new(),
+            profile: None,
         }
     }
 }
 
 impl AppConfig {
+    /// Returns the active profile, if any.
+    pub fn active_profile(&self) -> Option<&ProfileConfig> {
+        let name = self.profile.as_deref()?;
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Directory of the active library: the active profile's, or `data_dir`.
+    pub fn library_dir(&self) -> PathBuf {
+        self.profile_dir(self.profile.as_deref())
+    }
+
+    /// Directory of a profile's library; `None` is the library in `data_dir`.
+    pub fn profile_dir(&self, name: Option<&str>) -> PathBuf {
+        let Some(name) = name else {
+            return self.data_dir.clone();
+        };
+        self.profiles
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.data_dir.clone())
+            .unwrap_or_else(|| self.data_dir.join(paths::PROFILES).join(name))
+    }
+
+    /// Model selected for cloze generation in the active profile, falling
+    /// back to the global selection.
+    pub fn selected_model_id(&self) -> Option<uuid::Uuid> {
+        self.active_profile()
+            .and_then(|p| p.selected_model_id)
+            .or(self.ai.selected_model_id)
+    }
+
+    /// Selects the generation model; inside a profile only that profile's
+    /// selection changes.
+    pub fn set_selected_model_id(&mut self, id: Option<uuid::Uuid>) {
+        let name = self.profile.clone();
+        match self
+            .profiles
+            .iter_mut()
+            .find(|p| Some(&p.name) == name.as_ref())
+        {
+            Some(profile) => profile.selected_model_id = id,
+            None => self.ai.selected_model_id = id,
+        }
+    }
+
     /// Saves the current configuration to the config file.
     pub fn save_to_file(&self) {
         tracing::debug!("Saving configuration to file: {:?}", self.config_file);
@@ -140,8 +208,13 @@ impl AppConfig {
                 log_level: Some(self.log_level),
                 theme: Some(self.theme),
                 locale: Some(self.locale),
+                autosave_secs: Some(self.autosave_secs),
+                backup_retention: Some(self.backup_retention),
+                trash_retention_days: Some(self.trash_retention_days),
+                profile: self.profile.clone(),
             },
             ai: self.ai.clone(),
+            profiles: self.profiles.clone(),
         }
     }
 
@@ -212,12 +285,50 @@ impl AppConfig {
             .or(file_config.general.locale)
             .unwrap_or_default();
 
+        let autosave_secs = file_config
+            .general
+            .autosave_secs
+            .unwrap_or(constants::autosave::DEFAULT_SECS);
+
+        let backup_retention = file_config
+            .general
+            .backup_retention
+            .unwrap_or(constants::backup::DEFAULT_RETENTION)
+            .max(1);
+
+        let trash_retention_days = file_config
+            .general
+            .trash_retention_days
+            .unwrap_or(constants::trash::DEFAULT_RETENTION_DAYS)
+            .max(1);
+
+        // Resolve profile with priority: CLI > env > file; unknown names are added
+        let mut profiles = file_config.profiles;
+        let profile = cli
+            .profile
+            .or(env.profile)
+            .or(file_config.general.profile)
+            .map(|name| name.trim().to_string())
+            .filter(|name| {
+                let valid = ProfileConfig::is_valid_name(name);
+                if !valid {
+                    tracing::warn!(profile = %name, "Ignoring invalid profile name");
+                }
+                valid
+            });
+        if let Some(name) = &profile
+            && !profiles.iter().any(|p| &p.name == name)
+        {
+            profiles.push(ProfileConfig::new(name.clone()));
+        }
+
         tracing::info!(
-            "Configuration loaded: data_dir={:?}, log_level={:?}, theme={:?}, locale={:?}",
+            "Configuration loaded: data_dir={:?}, log_level={:?}, theme={:?}, locale={:?}, profile={:?}",
             data_dir,
             log_level,
             theme,
-            locale
+            locale,
+            profile
         );
 
         Self {
@@ -226,7 +337,70 @@ impl AppConfig {
             log_level,
             theme,
             locale,
+            autosave_secs,
+            backup_retention,
+            trash_retention_days,
             ai: file_config.ai,
+            profiles,
+            profile,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_dir_defaults_under_data_dir() {
+        let mut config = AppConfig {
+            data_dir: PathBuf::from("/data"),
+            ..AppConfig::default()
+        };
+        let mut custom = ProfileConfig::new("work");
+        custom.data_dir = Some(PathBuf::from("/elsewhere"));
+        config.profiles = vec![ProfileConfig::new("travel"), custom];
+
+        assert_eq!(config.library_dir(), PathBuf::from("/data"));
+        assert_eq!(
+            config.profile_dir(Some("travel")),
+            PathBuf::from("/data/profiles/travel")
+        );
+        assert_eq!(
+            config.profile_dir(Some("work")),
+            PathBuf::from("/elsewhere")
+        );
+
+        config.profile = Some("travel".to_string());
+        assert_eq!(config.library_dir(), PathBuf::from("/data/profiles/travel"));
+    }
+
+    #[test]
+    fn test_profile_model_selection_falls_back_to_global() {
+        let global = uuid::Uuid::new_v4();
+        let own = uuid::Uuid::new_v4();
+        let mut config = AppConfig {
+            profiles: vec![ProfileConfig::new("travel")],
+            ..AppConfig::default()
+        };
+        config.ai.selected_model_id = Some(global);
+
+        config.profile = Some("travel".to_string());
+        assert_eq!(config.selected_model_id(), Some(global));
+
+        config.set_selected_model_id(Some(own));
+        assert_eq!(config.selected_model_id(), Some(own));
+        assert_eq!(config.ai.selected_model_id, Some(global));
+
+        config.profile = None;
+        assert_eq!(config.selected_model_id(), Some(global));
+    }
+
+    #[test]
+    fn test_profile_names_must_be_single_directories() {
+        assert!(ProfileConfig::is_valid_name("Spanish"));
+        assert!(!ProfileConfig::is_valid_name("  "));
+        assert!(!ProfileConfig::is_valid_name(".."));
+        assert!(!ProfileConfig::is_valid_name("a/b"));
+    }
+}
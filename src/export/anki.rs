@@ -0,0 +1,168 @@
+//! Anki-compatible TSV export.
+//!
+//! Produces a tab-separated file with Anki's file headers so it can be
+//! imported directly via *File → Import*. Each row holds the cloze text in
+//! `{{c1::answer}}` syntax followed by the word, definition, part of speech,
+//! CEFR level and space-separated hierarchical tags (`Parent::Child`).
+//! Map the columns to a cloze-type note with matching fields on import.
+
+use crate::models::{Cloze, ClozeSegment, TagId};
+use crate::registry::{MeaningRegistry, TagRegistry, WordRegistry};
+
+/// Column names, in output order.
+pub const COLUMNS: [&str; 6] = ["Text", "Word", "Definition", "POS", "CEFR", "Tags"];
+
+/// Renders a cloze in Anki cloze syntax. All blanks share `c1` so they are
+/// hidden together, matching practice mode.
+pub fn cloze_to_anki(cloze: &Cloze) -> String {
+    cloze
+        .segments
+        .iter()
+        .map(|s| match s {
+            ClozeSegment::Text(t) => t.clone(),
+            ClozeSegment::Blank(a) => format!("{{{{c1::{}}}}}", a),
+        })
+        .collect()
+}
+
+/// Builds the full `Parent::Child` path of a tag as an Anki tag.
+pub fn tag_path(tag_id: TagId, tags: &TagRegistry) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut current = Some(tag_id);
+    while let Some(id) = current {
+        // Guard against malformed parent cycles
+        if parts.len() > tags.count() {
+            break;
+        }
+        let tag = tags.get(id)?;
+        parts.push(anki_tag_part(&tag.name));
+        current = tag.parent_id;
+    }
+    parts.reverse();
+    Some(parts.join("::"))
+}
+
+/// Exports clozes as an Anki-importable TSV document.
+pub fn to_anki_tsv<'a>(
+    clozes: impl IntoIterator<Item = &'a Cloze>,
+    words: &WordRegistry,
+    meanings: &MeaningRegistry,
+    tags: &TagRegistry,
+) -> String {
+    let mut out = String::new();
+    out.push_str("#separator:tab\n");
+    out.push_str("#html:false\n");
+    out.push_str(&format!("#columns:{}\n", COLUMNS.join("\t")));
+    out.push_str(&format!("#tags column:{}\n", COLUMNS.len()));
+
+    for cloze in clozes {
+        let meaning = meanings.get(cloze.meaning_id);
+        let word = meaning.and_then(|m| words.get(m.word_id));
+
+        let tag_list: Vec<String> = meaning
+            .map(|m| {
+                m.tag_ids
+                    .iter()
+                    .filter_map(|id| tag_path(*id, tags))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fields = [
+            cloze_to_anki(cloze),
+            word.map(|w| w.content.clone()).unwrap_or_default(),
+            meaning.map(|m| m.definition.clone()).unwrap_or_default(),
+            meaning.map(|m| m.pos.to_string()).unwrap_or_default(),
+            meaning
+                .and_then(|m| m.cefr_level)
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            tag_list.join(" "),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+        out.push_str(&row.join("\t"));
+        out.push('\n');
+    }
+    out
+}
+
+/// Tabs and newlines would break the row layout.
+fn escape_field(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Anki tags are space-separated and use `::` as the hierarchy separator.
+fn anki_tag_part(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .replace("::", "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CefrLevel, Meaning, PartOfSpeech, Tag, Word};
+    use test_case::test_case;
+
+    #[test_case("The [cat] sat", "The {{c1::cat}} sat"; "single blank")]
+    #[test_case("[A] and [b]", "{{c1::A}} and {{c1::b}}"; "multiple blanks")]
+    #[test_case("no blanks", "no blanks"; "plain text")]
+    fn test_cloze_to_anki(sentence: &str, expected: &str) {
+        let cloze = Cloze::builder()
+            .meaning_id(Default::default())
+            .segments(Cloze::parse_from_sentence(sentence))
+            .build();
+        assert_eq!(cloze_to_anki(&cloze), expected);
+    }
+
+    #[test]
+    fn test_tag_path_nested() {
+        let mut tags = TagRegistry::new();
+        let parent = Tag::builder().name("Exam Prep".to_string()).build();
+        let child = Tag::builder()
+            .name("IELTS".to_string())
+            .parent_id(Some(parent.id))
+            .build();
+        let child_id = child.id;
+        tags.add(parent);
+        tags.add(child);
+        assert_eq!(
+            tag_path(child_id, &tags).as_deref(),
+            Some("Exam_Prep::IELTS")
+        );
+    }
+
+    #[test]
+    fn test_to_anki_tsv_row() {
+        let mut words = WordRegistry::new();
+        let mut meanings = MeaningRegistry::new();
+        let mut tags = TagRegistry::new();
+
+        let tag = Tag::builder().name("travel".to_string()).build();
+        let word = Word::builder().content("ticket".to_string()).build();
+        let meaning = Meaning::builder()
+            .word_id(word.id)
+            .definition("a pass\tfor travel".to_string())
+            .pos(PartOfSpeech::Noun)
+            .cefr_level(Some(CefrLevel::A2))
+            .build();
+        let cloze = Cloze::builder()
+            .meaning_id(meaning.id)
+            .segments(Cloze::parse_from_sentence("Buy a [ticket]."))
+            .build();
+        let (tag_id, meaning_id) = (tag.id, meaning.id);
+        tags.add(tag);
+        words.add(word);
+        meanings.add(meaning);
+        meanings.add_tag(meaning_id, tag_id);
+
+        let tsv = to_anki_tsv([&cloze], &words, &meanings, &tags);
+        let row = tsv.lines().last().unwrap();
+        assert_eq!(
+            row,
+            "Buy a {{c1::ticket}}.\tticket\ta pass for travel\tNoun\tA2\ttravel"
+        );
+        assert!(tsv.starts_with("#separator:tab\n"));
+    }
+}
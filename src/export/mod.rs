@@ -0,0 +1,13 @@
+pub mod anki;
+
+pub use anki::{cloze_to_anki, tag_path, to_anki_tsv};
+
+/// Output formats for cloze exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Anki-compatible TSV with note fields and tags
+    #[default]
+    Anki,
+    /// One answered sentence per line
+    Plaintext,
+}
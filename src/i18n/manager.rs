@@ -38,21 +38,19 @@ impl I18nManager {
     /// Looks up a translation key in the current locale, falling back to en-US.
     pub fn tr(&self, key: &str) -> Cow<'static, str> {
         // Try current locale
-        if let Some(bundle) = self.bundles.get(&self.current_locale) {
-            if let Some(value) = bundle.get(key) {
-                return Cow::Borrowed(value);
-            }
+        if let Some(bundle) = self.bundles.get(&self.current_locale)
+            && let Some(value) = bundle.get(key)
+        {
+            return Cow::Borrowed(value);
         }
 
         // Fallback to en-US
-        if let Ok(en) = "en-US".parse::<LanguageIdentifier>() {
-            if self.current_locale != en {
-                if let Some(bundle) = self.bundles.get(&en) {
-                    if let Some(value) = bundle.get(key) {
-                        return Cow::Borrowed(value);
-                    }
-                }
-            }
+        if let Ok(en) = "en-US".parse::<LanguageIdentifier>()
+            && self.current_locale != en
+            && let Some(bundle) = self.bundles.get(&en)
+            && let Some(value) = bundle.get(key)
+        {
+            return Cow::Borrowed(value);
         }
 
         // Return the key itself if no translation found
@@ -78,7 +76,7 @@ impl I18nManager {
     pub fn available_locales(&self) -> Vec<LocaleDto> {
         self.bundles
             .keys()
-            .filter_map(|langid| LocaleDto::from_langid(langid))
+            .filter_map(LocaleDto::from_langid)
             .collect()
     }
 }
@@ -16,14 +16,18 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("nav-tags", "Tags"),
             ("nav-practice", "Practice"),
             ("nav-settings", "Settings"),
+            ("nav-trash", "Trash"),
             // Sidebar
             ("sidebar-brand", "clozer"),
             ("sidebar-brand-collapsed", "C"),
+            ("sidebar-profile", "Profile"),
+            ("profile-default", "Default library"),
             ("sidebar-shortcut-words", "Ctrl+1"),
             ("sidebar-shortcut-queue", "Ctrl+2"),
             ("sidebar-shortcut-tags", "Ctrl+3"),
             ("sidebar-shortcut-practice", "Ctrl+4"),
             ("sidebar-shortcut-settings", "Ctrl+5"),
+            ("sidebar-shortcut-trash", "Ctrl+6"),
             // Settings
             ("settings-title", "Settings"),
             ("settings-general", "General"),
@@ -35,7 +39,46 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-active-model", "Active Model"),
             ("settings-data", "Data"),
             ("settings-selected", "Selected: "),
+            ("settings-candidates", "Candidates per request:"),
+            ("settings-template", "Template:"),
+            ("settings-templates", "Prompt Templates"),
+            ("settings-template-default", "Built-in default"),
+            ("settings-template-preview", "Preview:"),
+            (
+                "settings-template-placeholders",
+                "Edit templates in the [ai] section of the config file. Placeholders: {0}",
+            ),
             ("settings-directory", "Directory: "),
+            ("settings-backup", "Back Up…"),
+            ("settings-restore-merge", "Restore (Merge)…"),
+            ("settings-restore-replace", "Restore (Replace)…"),
+            ("settings-integrity-check", "Check Integrity"),
+            ("settings-integrity-repair-links", "Repair Links"),
+            ("settings-integrity-repair-all", "Repair and Delete Orphans"),
+            (
+                "settings-integrity-repair-all-confirm",
+                "Delete orphaned words, meanings and clozes along with the repair?",
+            ),
+            ("settings-snapshots", "Snapshots"),
+            ("settings-snapshot-refresh", "Refresh"),
+            ("settings-snapshot-now", "Snapshot Now"),
+            ("settings-snapshot-empty", "No snapshots yet"),
+            ("settings-snapshot-restore", "Restore"),
+            (
+                "settings-snapshot-confirm",
+                "Replace the library with this snapshot? The current library is snapshotted first.",
+            ),
+            ("settings-snapshot-reason-startup", "Startup"),
+            ("settings-snapshot-reason-daily", "Daily"),
+            ("settings-snapshot-reason-migration", "Before migration"),
+            ("settings-snapshot-reason-import", "Before import"),
+            ("settings-snapshot-reason-restore", "Before restore"),
+            ("settings-snapshot-reason-manual", "Manual"),
+            ("settings-profiles", "Profiles"),
+            ("settings-profile-open", "Open"),
+            ("settings-profile-active", "Open now"),
+            ("settings-profile-name", "New profile name"),
+            ("settings-profile-add", "Add Profile"),
             ("settings-none", "<none>"),
             ("settings-unknown-provider", "<unknown>"),
             ("settings-add-provider", "Add Provider"),
@@ -48,6 +91,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-delete", "Delete"),
             ("settings-save", "Save"),
             ("settings-cancel", "Cancel"),
+            ("notification-undo", "Undo"),
             ("settings-select", "Select"),
             ("settings-active", "Active"),
             ("settings-type", "Type:"),
@@ -69,6 +113,25 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("queue-done", "Done"),
             ("queue-failed", "Failed: {}"),
             ("queue-status", "Total: {0} | Pending: {1} | Selected: {2}"),
+            ("queue-review", "Review"),
+            ("queue-accept", "Accept"),
+            ("queue-reject", "Reject"),
+            ("queue-regenerate", "Regenerate"),
+            // Trash
+            ("trash-title", "Trash"),
+            ("trash-status", "{0} item(s), purged after {1} days"),
+            ("trash-restore", "Restore"),
+            ("trash-delete", "Delete Forever"),
+            ("trash-empty", "Empty Trash"),
+            (
+                "trash-empty-confirm",
+                "Permanently delete everything in the trash?",
+            ),
+            ("trash-none", "The trash is empty"),
+            ("trash-words", "{0} word(s)"),
+            ("trash-meanings", "{0} meaning(s)"),
+            ("trash-tags", "{0} tag(s)"),
+            ("trash-clozes", "{0} cloze(s)"),
             // Tags
             ("tags-new-tag", "New Tag"),
             ("tags-confirm-deletion", "Confirm Deletion"),
@@ -110,6 +173,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ),
             ("practice-filter", "Filter: "),
             ("practice-clear-filter", "Clear Filter"),
+            ("practice-include-subtags", "Include subtags"),
             ("practice-start-session", "Start Session"),
             ("practice-end-session", "End Session"),
             ("practice-check-answer", "Check Answer"),
@@ -120,7 +184,8 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("practice-back-to-setup", "Back to Setup"),
             ("practice-all-clozes", "All Clozes"),
             ("practice-blank-placeholder", "Blank {}:"),
-            ("practice-clozes-available", "{} clozes available"),
+            ("practice-clozes-available", "{} clozes due for review"),
+            ("practice-next-review", "Next review in {0} day(s)"),
             ("practice-progress", "{} / {}"),
             ("practice-score", "Score: {}/{} ({:.0}%)"),
             (
@@ -135,12 +200,37 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("words-add-word", "+ Add Word"),
             ("words-add-meaning", "+ Add Meaning"),
             ("words-export", "Export"),
+            ("words-export-anki", "Export to Anki"),
+            ("words-import", "Import…"),
+            ("words-import-title", "Import {0}"),
+            (
+                "words-import-summary",
+                "{0} new, {1} new meanings, {2} duplicates (skipped), {3} errors",
+            ),
+            ("words-import-new", "New"),
+            ("words-import-additions", "New meanings for existing words"),
+            ("words-import-duplicates", "Already in library"),
+            ("words-import-errors", "Errors"),
+            ("words-import-error-line", "Line {0}: {1}"),
+            (
+                "words-import-enqueue",
+                "Queue imported meanings for generation",
+            ),
+            ("words-import-confirm", "Import"),
             ("words-delete-clozes", "Delete Clozes"),
             ("words-add-tag", "Add Tag"),
             ("words-add-tag-open", "Add Tag ▾"),
             ("words-queue", "Queue"),
             ("words-delete-meanings-batch", "Delete"),
             ("words-search-placeholder", "Search words or definitions..."),
+            ("query-error-unknown-tag", "Unknown tag \"{0}\""),
+            ("query-error-unknown-pos", "Unknown part of speech \"{0}\""),
+            ("query-error-unclosed-paren", "Unclosed parenthesis"),
+            (
+                "query-error-unmatched-paren",
+                "Unmatched closing parenthesis",
+            ),
+            ("query-error-empty-group", "Empty group"),
             ("words-sort", "Sort"),
             ("words-clear-filters", "Clear"),
             ("words-clozes-selected", "{} clozes selected"),
@@ -224,6 +314,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
                 "Cloze generated successfully",
             ),
             ("notification-generation-failed", "Generation failed: {}"),
+            ("notification-backup-done", "Backed up {0} words"),
+            ("notification-backup-failed", "Backup failed: {0}"),
+            (
+                "notification-restore-done",
+                "Restored {0} words, {1} meanings, {2} tags, {3} clozes, {4} queue items",
+            ),
+            ("notification-restore-failed", "Restore failed: {0}"),
+            (
+                "notification-restore-snapshot-failed",
+                "Restore aborted, snapshot failed: {0}",
+            ),
             // Dictionary lookup
             ("dictionary-suggestion-format", "[{}] {}"),
         ],
@@ -237,13 +338,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("nav-tags", "标签"),
             ("nav-practice", "练习"),
             ("nav-settings", "设置"),
+            ("nav-trash", "回收站"),
             ("sidebar-brand", "clozer"),
             ("sidebar-brand-collapsed", "C"),
+            ("sidebar-profile", "配置文件"),
+            ("profile-default", "默认词库"),
             ("sidebar-shortcut-words", "Ctrl+1"),
             ("sidebar-shortcut-queue", "Ctrl+2"),
             ("sidebar-shortcut-tags", "Ctrl+3"),
             ("sidebar-shortcut-practice", "Ctrl+4"),
             ("sidebar-shortcut-settings", "Ctrl+5"),
+            ("sidebar-shortcut-trash", "Ctrl+6"),
             ("settings-title", "设置"),
             ("settings-general", "通用"),
             ("settings-theme", "主题:"),
@@ -254,7 +359,46 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-active-model", "当前模型"),
             ("settings-data", "数据"),
             ("settings-selected", "已选择: "),
+            ("settings-candidates", "每次请求候选数:"),
+            ("settings-template", "模板:"),
+            ("settings-templates", "提示词模板"),
+            ("settings-template-default", "内置默认"),
+            ("settings-template-preview", "预览:"),
+            (
+                "settings-template-placeholders",
+                "在配置文件的 [ai] 部分编辑模板。占位符: {0}",
+            ),
             ("settings-directory", "目录: "),
+            ("settings-backup", "备份…"),
+            ("settings-restore-merge", "恢复（合并）…"),
+            ("settings-restore-replace", "恢复（替换）…"),
+            ("settings-integrity-check", "检查完整性"),
+            ("settings-integrity-repair-links", "修复链接"),
+            ("settings-integrity-repair-all", "修复并删除孤立数据"),
+            (
+                "settings-integrity-repair-all-confirm",
+                "修复时一并删除孤立的单词、释义和填空？",
+            ),
+            ("settings-snapshots", "快照"),
+            ("settings-snapshot-refresh", "刷新"),
+            ("settings-snapshot-now", "立即快照"),
+            ("settings-snapshot-empty", "暂无快照"),
+            ("settings-snapshot-restore", "恢复"),
+            (
+                "settings-snapshot-confirm",
+                "用此快照替换词库？当前词库会先保存快照。",
+            ),
+            ("settings-snapshot-reason-startup", "启动"),
+            ("settings-snapshot-reason-daily", "每日"),
+            ("settings-snapshot-reason-migration", "迁移前"),
+            ("settings-snapshot-reason-import", "导入前"),
+            ("settings-snapshot-reason-restore", "恢复前"),
+            ("settings-snapshot-reason-manual", "手动"),
+            ("settings-profiles", "配置文件"),
+            ("settings-profile-open", "打开"),
+            ("settings-profile-active", "当前"),
+            ("settings-profile-name", "新配置文件名称"),
+            ("settings-profile-add", "添加配置文件"),
             ("settings-none", "<无>"),
             ("settings-unknown-provider", "<未知>"),
             ("settings-add-provider", "添加提供商"),
@@ -267,6 +411,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-delete", "删除"),
             ("settings-save", "保存"),
             ("settings-cancel", "取消"),
+            ("notification-undo", "撤销"),
             ("settings-select", "选择"),
             ("settings-active", "已激活"),
             ("settings-type", "类型:"),
@@ -287,6 +432,21 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("queue-done", "已完成"),
             ("queue-failed", "失败: {}"),
             ("queue-status", "总计: {0} | 待处理: {1} | 已选择: {2}"),
+            ("queue-review", "待审核"),
+            ("queue-accept", "接受"),
+            ("queue-reject", "拒绝"),
+            ("queue-regenerate", "重新生成"),
+            ("trash-title", "回收站"),
+            ("trash-status", "{0} 项，{1} 天后永久删除"),
+            ("trash-restore", "恢复"),
+            ("trash-delete", "永久删除"),
+            ("trash-empty", "清空回收站"),
+            ("trash-empty-confirm", "永久删除回收站中的所有内容？"),
+            ("trash-none", "回收站为空"),
+            ("trash-words", "{0} 个单词"),
+            ("trash-meanings", "{0} 个释义"),
+            ("trash-tags", "{0} 个标签"),
+            ("trash-clozes", "{0} 个填空"),
             ("tags-new-tag", "新建标签"),
             ("tags-confirm-deletion", "确认删除"),
             ("tags-actions", "操作"),
@@ -323,6 +483,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("practice-description", "通过填空测试你的词汇量。"),
             ("practice-filter", "筛选: "),
             ("practice-clear-filter", "清除筛选"),
+            ("practice-include-subtags", "包含子标签"),
             ("practice-start-session", "开始练习"),
             ("practice-end-session", "结束练习"),
             ("practice-check-answer", "检查答案"),
@@ -333,7 +494,8 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("practice-back-to-setup", "返回设置"),
             ("practice-all-clozes", "所有填空"),
             ("practice-blank-placeholder", "空白 {}:"),
-            ("practice-clozes-available", "{} 个填空可用"),
+            ("practice-clozes-available", "{} 个填空待复习"),
+            ("practice-next-review", "{0} 天后复习"),
             ("practice-progress", "{} / {}"),
             ("practice-score", "得分: {}/{} ({:.0}%)"),
             (
@@ -347,12 +509,31 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("words-add-word", "+ 添加单词"),
             ("words-add-meaning", "+ 添加释义"),
             ("words-export", "导出"),
+            ("words-export-anki", "导出到 Anki"),
+            ("words-import", "导入…"),
+            ("words-import-title", "导入 {0}"),
+            (
+                "words-import-summary",
+                "新增 {0} 个，新释义 {1} 个，重复 {2} 个（跳过），错误 {3} 个",
+            ),
+            ("words-import-new", "新增"),
+            ("words-import-additions", "为已有单词新增释义"),
+            ("words-import-duplicates", "已存在"),
+            ("words-import-errors", "错误"),
+            ("words-import-error-line", "第 {0} 行: {1}"),
+            ("words-import-enqueue", "将导入的释义加入生成队列"),
+            ("words-import-confirm", "导入"),
             ("words-delete-clozes", "删除填空"),
             ("words-add-tag", "添加标签"),
             ("words-add-tag-open", "添加标签 ▾"),
             ("words-queue", "加入队列"),
             ("words-delete-meanings-batch", "删除"),
             ("words-search-placeholder", "搜索单词或释义..."),
+            ("query-error-unknown-tag", "未知标签“{0}”"),
+            ("query-error-unknown-pos", "未知词性“{0}”"),
+            ("query-error-unclosed-paren", "括号未闭合"),
+            ("query-error-unmatched-paren", "多余的右括号"),
+            ("query-error-empty-group", "空分组"),
             ("words-sort", "排序"),
             ("words-clear-filters", "清除"),
             ("words-clozes-selected", "已选择 {} 个填空"),
@@ -410,6 +591,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("notification-export-failed", "导出失败: {}"),
             ("notification-cloze-generated", "填空已成功生成"),
             ("notification-generation-failed", "生成失败: {}"),
+            ("notification-backup-done", "已备份 {0} 个单词"),
+            ("notification-backup-failed", "备份失败: {0}"),
+            (
+                "notification-restore-done",
+                "已恢复 {0} 个单词、{1} 个释义、{2} 个标签、{3} 个填空、{4} 个队列项",
+            ),
+            ("notification-restore-failed", "恢复失败: {0}"),
+            (
+                "notification-restore-snapshot-failed",
+                "快照失败，已中止恢复: {0}",
+            ),
             ("dictionary-suggestion-format", "[{}] {}"),
         ],
     ),
@@ -422,13 +614,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("nav-tags", "タグ"),
             ("nav-practice", "練習"),
             ("nav-settings", "設定"),
+            ("nav-trash", "ゴミ箱"),
             ("sidebar-brand", "clozer"),
             ("sidebar-brand-collapsed", "C"),
+            ("sidebar-profile", "プロファイル"),
+            ("profile-default", "既定のライブラリ"),
             ("sidebar-shortcut-words", "Ctrl+1"),
             ("sidebar-shortcut-queue", "Ctrl+2"),
             ("sidebar-shortcut-tags", "Ctrl+3"),
             ("sidebar-shortcut-practice", "Ctrl+4"),
             ("sidebar-shortcut-settings", "Ctrl+5"),
+            ("sidebar-shortcut-trash", "Ctrl+6"),
             ("settings-title", "設定"),
             ("settings-general", "一般"),
             ("settings-theme", "テーマ:"),
@@ -439,7 +635,49 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-active-model", "アクティブモデル"),
             ("settings-data", "データ"),
             ("settings-selected", "選択中: "),
+            ("settings-candidates", "リクエストごとの候補数:"),
+            ("settings-template", "テンプレート:"),
+            ("settings-templates", "プロンプトテンプレート"),
+            ("settings-template-default", "組み込みの既定"),
+            ("settings-template-preview", "プレビュー:"),
+            (
+                "settings-template-placeholders",
+                "テンプレートは設定ファイルの [ai] セクションで編集します。プレースホルダー: {0}",
+            ),
             ("settings-directory", "ディレクトリ: "),
+            ("settings-backup", "バックアップ…"),
+            ("settings-restore-merge", "復元（統合）…"),
+            ("settings-restore-replace", "復元（置換）…"),
+            ("settings-integrity-check", "整合性チェック"),
+            ("settings-integrity-repair-links", "リンクを修復"),
+            ("settings-integrity-repair-all", "修復して孤立データを削除"),
+            (
+                "settings-integrity-repair-all-confirm",
+                "修復と同時に孤立した単語・意味・クローズを削除しますか？",
+            ),
+            ("settings-snapshots", "スナップショット"),
+            ("settings-snapshot-refresh", "更新"),
+            ("settings-snapshot-now", "今すぐスナップショット"),
+            (
+                "settings-snapshot-empty",
+                "スナップショットはまだありません",
+            ),
+            ("settings-snapshot-restore", "復元"),
+            (
+                "settings-snapshot-confirm",
+                "このスナップショットでライブラリを置き換えますか？現在のライブラリは先にスナップショットされます。",
+            ),
+            ("settings-snapshot-reason-startup", "起動時"),
+            ("settings-snapshot-reason-daily", "毎日"),
+            ("settings-snapshot-reason-migration", "移行前"),
+            ("settings-snapshot-reason-import", "インポート前"),
+            ("settings-snapshot-reason-restore", "復元前"),
+            ("settings-snapshot-reason-manual", "手動"),
+            ("settings-profiles", "プロファイル"),
+            ("settings-profile-open", "開く"),
+            ("settings-profile-active", "使用中"),
+            ("settings-profile-name", "新しいプロファイル名"),
+            ("settings-profile-add", "プロファイルを追加"),
             ("settings-none", "<なし>"),
             ("settings-unknown-provider", "<不明>"),
             ("settings-add-provider", "プロバイダー追加"),
@@ -452,6 +690,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-delete", "削除"),
             ("settings-save", "保存"),
             ("settings-cancel", "キャンセル"),
+            ("notification-undo", "元に戻す"),
             ("settings-select", "選択"),
             ("settings-active", "アクティブ"),
             ("settings-type", "タイプ:"),
@@ -472,6 +711,24 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("queue-done", "完了"),
             ("queue-failed", "失敗: {}"),
             ("queue-status", "合計: {0} | 保留中: {1} | 選択中: {2}"),
+            ("queue-review", "レビュー待ち"),
+            ("queue-accept", "採用"),
+            ("queue-reject", "却下"),
+            ("queue-regenerate", "再生成"),
+            ("trash-title", "ゴミ箱"),
+            ("trash-status", "{0} 件、{1} 日後に完全削除"),
+            ("trash-restore", "復元"),
+            ("trash-delete", "完全に削除"),
+            ("trash-empty", "ゴミ箱を空にする"),
+            (
+                "trash-empty-confirm",
+                "ゴミ箱の中身をすべて完全に削除しますか？",
+            ),
+            ("trash-none", "ゴミ箱は空です"),
+            ("trash-words", "{0} 語"),
+            ("trash-meanings", "{0} 件の意味"),
+            ("trash-tags", "{0} 個のタグ"),
+            ("trash-clozes", "{0} 件のクローズ"),
             ("tags-new-tag", "新規タグ"),
             ("tags-confirm-deletion", "削除の確認"),
             ("tags-actions", "操作"),
@@ -511,6 +768,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ),
             ("practice-filter", "フィルター: "),
             ("practice-clear-filter", "フィルター解除"),
+            ("practice-include-subtags", "サブタグを含める"),
             ("practice-start-session", "セッション開始"),
             ("practice-end-session", "セッション終了"),
             ("practice-check-answer", "回答を確認"),
@@ -521,7 +779,8 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("practice-back-to-setup", "設定に戻る"),
             ("practice-all-clozes", "すべての穴埋め"),
             ("practice-blank-placeholder", "空白 {}:"),
-            ("practice-clozes-available", "{} 件の穴埋めが利用可能"),
+            ("practice-clozes-available", "{} 件の穴埋めが復習待ち"),
+            ("practice-next-review", "次の復習: {0} 日後"),
             ("practice-progress", "{} / {}"),
             ("practice-score", "スコア: {}/{} ({:.0}%)"),
             (
@@ -538,12 +797,34 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("words-add-word", "+ 単語追加"),
             ("words-add-meaning", "+ 意味追加"),
             ("words-export", "エクスポート"),
+            ("words-export-anki", "Anki にエクスポート"),
+            ("words-import", "インポート…"),
+            ("words-import-title", "{0} をインポート"),
+            (
+                "words-import-summary",
+                "新規 {0} 件、新しい意味 {1} 件、重複 {2} 件（スキップ）、エラー {3} 件",
+            ),
+            ("words-import-new", "新規"),
+            ("words-import-additions", "既存の単語への新しい意味"),
+            ("words-import-duplicates", "登録済み"),
+            ("words-import-errors", "エラー"),
+            ("words-import-error-line", "{0} 行目: {1}"),
+            (
+                "words-import-enqueue",
+                "インポートした意味を生成キューに追加",
+            ),
+            ("words-import-confirm", "インポート"),
             ("words-delete-clozes", "穴埋め削除"),
             ("words-add-tag", "タグ追加"),
             ("words-add-tag-open", "タグ追加 ▾"),
             ("words-queue", "キュー"),
             ("words-delete-meanings-batch", "削除"),
             ("words-search-placeholder", "単語や定義を検索..."),
+            ("query-error-unknown-tag", "不明なタグ「{0}」"),
+            ("query-error-unknown-pos", "不明な品詞「{0}」"),
+            ("query-error-unclosed-paren", "括弧が閉じられていません"),
+            ("query-error-unmatched-paren", "対応しない閉じ括弧"),
+            ("query-error-empty-group", "空のグループ"),
             ("words-sort", "並べ替え"),
             ("words-clear-filters", "クリア"),
             ("words-clozes-selected", "{} 件の穴埋めを選択"),
@@ -625,6 +906,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
                 "穴埋めが正常に生成されました",
             ),
             ("notification-generation-failed", "生成失敗: {}"),
+            ("notification-backup-done", "{0} 語をバックアップしました"),
+            ("notification-backup-failed", "バックアップ失敗: {0}"),
+            (
+                "notification-restore-done",
+                "{0} 語、{1} 件の意味、{2} 個のタグ、{3} 件のクローズ、{4} 件のキュー項目を復元しました",
+            ),
+            ("notification-restore-failed", "復元失敗: {0}"),
+            (
+                "notification-restore-snapshot-failed",
+                "スナップショット失敗のため復元を中止しました: {0}",
+            ),
             ("dictionary-suggestion-format", "[{}] {}"),
         ],
     ),
@@ -637,13 +929,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("nav-tags", "태그"),
             ("nav-practice", "연습"),
             ("nav-settings", "설정"),
+            ("nav-trash", "휴지통"),
             ("sidebar-brand", "clozer"),
             ("sidebar-brand-collapsed", "C"),
+            ("sidebar-profile", "프로필"),
+            ("profile-default", "기본 라이브러리"),
             ("sidebar-shortcut-words", "Ctrl+1"),
             ("sidebar-shortcut-queue", "Ctrl+2"),
             ("sidebar-shortcut-tags", "Ctrl+3"),
             ("sidebar-shortcut-practice", "Ctrl+4"),
             ("sidebar-shortcut-settings", "Ctrl+5"),
+            ("sidebar-shortcut-trash", "Ctrl+6"),
             ("settings-title", "설정"),
             ("settings-general", "일반"),
             ("settings-theme", "테마:"),
@@ -654,7 +950,46 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-active-model", "활성 모델"),
             ("settings-data", "데이터"),
             ("settings-selected", "선택됨: "),
+            ("settings-candidates", "요청당 후보 수:"),
+            ("settings-template", "템플릿:"),
+            ("settings-templates", "프롬프트 템플릿"),
+            ("settings-template-default", "기본 제공"),
+            ("settings-template-preview", "미리보기:"),
+            (
+                "settings-template-placeholders",
+                "템플릿은 설정 파일의 [ai] 섹션에서 편집합니다. 자리표시자: {0}",
+            ),
             ("settings-directory", "디렉토리: "),
+            ("settings-backup", "백업…"),
+            ("settings-restore-merge", "복원 (병합)…"),
+            ("settings-restore-replace", "복원 (교체)…"),
+            ("settings-integrity-check", "무결성 검사"),
+            ("settings-integrity-repair-links", "링크 복구"),
+            ("settings-integrity-repair-all", "복구 및 고아 데이터 삭제"),
+            (
+                "settings-integrity-repair-all-confirm",
+                "복구하면서 고아 단어, 의미, 클로즈를 삭제할까요?",
+            ),
+            ("settings-snapshots", "스냅샷"),
+            ("settings-snapshot-refresh", "새로 고침"),
+            ("settings-snapshot-now", "지금 스냅샷"),
+            ("settings-snapshot-empty", "아직 스냅샷이 없습니다"),
+            ("settings-snapshot-restore", "복원"),
+            (
+                "settings-snapshot-confirm",
+                "이 스냅샷으로 라이브러리를 교체할까요? 현재 라이브러리는 먼저 스냅샷으로 저장됩니다.",
+            ),
+            ("settings-snapshot-reason-startup", "시작 시"),
+            ("settings-snapshot-reason-daily", "매일"),
+            ("settings-snapshot-reason-migration", "마이그레이션 전"),
+            ("settings-snapshot-reason-import", "가져오기 전"),
+            ("settings-snapshot-reason-restore", "복원 전"),
+            ("settings-snapshot-reason-manual", "수동"),
+            ("settings-profiles", "프로필"),
+            ("settings-profile-open", "열기"),
+            ("settings-profile-active", "사용 중"),
+            ("settings-profile-name", "새 프로필 이름"),
+            ("settings-profile-add", "프로필 추가"),
             ("settings-none", "<없음>"),
             ("settings-unknown-provider", "<알 수 없음>"),
             ("settings-add-provider", "제공자 추가"),
@@ -667,6 +1002,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("settings-delete", "삭제"),
             ("settings-save", "저장"),
             ("settings-cancel", "취소"),
+            ("notification-undo", "실행 취소"),
             ("settings-select", "선택"),
             ("settings-active", "활성"),
             ("settings-type", "유형:"),
@@ -687,6 +1023,24 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("queue-done", "완료"),
             ("queue-failed", "실패: {}"),
             ("queue-status", "전체: {0} | 대기: {1} | 선택: {2}"),
+            ("queue-review", "검토 대기"),
+            ("queue-accept", "수락"),
+            ("queue-reject", "거절"),
+            ("queue-regenerate", "다시 생성"),
+            ("trash-title", "휴지통"),
+            ("trash-status", "{0}개 항목, {1}일 후 영구 삭제"),
+            ("trash-restore", "복원"),
+            ("trash-delete", "영구 삭제"),
+            ("trash-empty", "휴지통 비우기"),
+            (
+                "trash-empty-confirm",
+                "휴지통의 모든 항목을 영구 삭제할까요?",
+            ),
+            ("trash-none", "휴지통이 비어 있습니다"),
+            ("trash-words", "단어 {0}개"),
+            ("trash-meanings", "의미 {0}개"),
+            ("trash-tags", "태그 {0}개"),
+            ("trash-clozes", "클로즈 {0}개"),
             ("tags-new-tag", "새 태그"),
             ("tags-confirm-deletion", "삭제 확인"),
             ("tags-actions", "작업"),
@@ -726,6 +1080,7 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ),
             ("practice-filter", "필터: "),
             ("practice-clear-filter", "필터 해제"),
+            ("practice-include-subtags", "하위 태그 포함"),
             ("practice-start-session", "세션 시작"),
             ("practice-end-session", "세션 종료"),
             ("practice-check-answer", "답변 확인"),
@@ -736,7 +1091,8 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("practice-back-to-setup", "설정으로 돌아가기"),
             ("practice-all-clozes", "모든 빈칸"),
             ("practice-blank-placeholder", "빈칸 {}:"),
-            ("practice-clozes-available", "{}개의 빈칸 사용 가능"),
+            ("practice-clozes-available", "복습할 빈칸 {}개"),
+            ("practice-next-review", "{0}일 후 복습"),
             ("practice-progress", "{} / {}"),
             ("practice-score", "점수: {}/{} ({:.0}%)"),
             (
@@ -750,12 +1106,31 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("words-add-word", "+ 단어 추가"),
             ("words-add-meaning", "+ 의미 추가"),
             ("words-export", "내보내기"),
+            ("words-export-anki", "Anki로 내보내기"),
+            ("words-import", "가져오기…"),
+            ("words-import-title", "{0} 가져오기"),
+            (
+                "words-import-summary",
+                "새 항목 {0}개, 새 의미 {1}개, 중복 {2}개 (건너뜀), 오류 {3}개",
+            ),
+            ("words-import-new", "새 항목"),
+            ("words-import-additions", "기존 단어의 새 의미"),
+            ("words-import-duplicates", "이미 있음"),
+            ("words-import-errors", "오류"),
+            ("words-import-error-line", "{0}행: {1}"),
+            ("words-import-enqueue", "가져온 의미를 생성 대기열에 추가"),
+            ("words-import-confirm", "가져오기"),
             ("words-delete-clozes", "빈칸 삭제"),
             ("words-add-tag", "태그 추가"),
             ("words-add-tag-open", "태그 추가 ▾"),
             ("words-queue", "큐"),
             ("words-delete-meanings-batch", "삭제"),
             ("words-search-placeholder", "단어 또는 정의 검색..."),
+            ("query-error-unknown-tag", "알 수 없는 태그 \"{0}\""),
+            ("query-error-unknown-pos", "알 수 없는 품사 \"{0}\""),
+            ("query-error-unclosed-paren", "닫히지 않은 괄호"),
+            ("query-error-unmatched-paren", "짝이 없는 닫는 괄호"),
+            ("query-error-empty-group", "빈 그룹"),
             ("words-sort", "정렬"),
             ("words-clear-filters", "지우기"),
             ("words-clozes-selected", "{}개 빈칸 선택됨"),
@@ -825,6 +1200,17 @@ pub static TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[
             ("notification-export-failed", "내보내기 실패: {}"),
             ("notification-cloze-generated", "빈칸이 성공적으로 생성됨"),
             ("notification-generation-failed", "생성 실패: {}"),
+            ("notification-backup-done", "단어 {0}개를 백업했습니다"),
+            ("notification-backup-failed", "백업 실패: {0}"),
+            (
+                "notification-restore-done",
+                "단어 {0}개, 의미 {1}개, 태그 {2}개, 클로즈 {3}개, 대기열 항목 {4}개를 복원했습니다",
+            ),
+            ("notification-restore-failed", "복원 실패: {0}"),
+            (
+                "notification-restore-snapshot-failed",
+                "스냅샷 실패로 복원을 중단했습니다: {0}",
+            ),
             ("dictionary-suggestion-format", "[{}] {}"),
         ],
     ),
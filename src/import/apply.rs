@@ -0,0 +1,146 @@
+//! Creating previewed rows in the model.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::ImportPreview;
+use crate::models::{Meaning, Word, WordId};
+use crate::state::Model;
+
+/// Counts of entities created by an import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub words: usize,
+    pub meanings: usize,
+    pub tags: usize,
+    pub queued: usize,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} words, {} meanings, {} new tags",
+            self.words, self.meanings, self.tags
+        )
+    }
+}
+
+/// Creates the words, meanings and tags in `preview.creates` and adds the
+/// meanings in `preview.additions` to their existing words.
+///
+/// Rows sharing a word become meanings of one word. Rows without a definition
+/// create only the word. With `enqueue`, every new meaning is queued for
+/// cloze generation.
+pub fn apply(preview: &ImportPreview, model: &mut Model, enqueue: bool) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let tags_before = model.tag_registry.count();
+    let mut word_ids: HashMap<String, WordId> = HashMap::new();
+
+    for row in preview.creates.iter().chain(&preview.additions) {
+        let key = row.word.to_lowercase();
+        let word_id = match word_ids.get(&key) {
+            Some(id) => *id,
+            None => {
+                let id = match model.word_registry.find_by_content(&row.word) {
+                    Some(id) => id,
+                    None => {
+                        let word = match &row.language {
+                            Some(lang) => Word::builder()
+                                .content(row.word.clone())
+                                .language(lang.clone())
+                                .build(),
+                            None => Word::builder().content(row.word.clone()).build(),
+                        };
+                        let id = word.id;
+                        model.word_registry.add(word);
+                        summary.words += 1;
+                        id
+                    }
+                };
+                word_ids.insert(key, id);
+                id
+            }
+        };
+
+        if row.definition.is_empty() {
+            continue;
+        }
+
+        let meaning = Meaning::builder()
+            .word_id(word_id)
+            .definition(row.definition.clone())
+            .pos(row.pos)
+            .cefr_level(row.cefr)
+            .build();
+        let meaning_id = meaning.id;
+        model.meaning_registry.add(meaning);
+        model.word_registry.add_meaning(word_id, meaning_id);
+        summary.meanings += 1;
+
+        for path in &row.tags {
+            if let Some(tag_id) = model.tag_registry.get_or_create_path(path) {
+                model.meaning_registry.add_tag(meaning_id, tag_id);
+            }
+        }
+
+        if enqueue {
+            model.queue_registry.enqueue(meaning_id);
+            summary.queued += 1;
+        }
+    }
+
+    summary.tags = model.tag_registry.count() - tags_before;
+    tracing::info!(
+        words = summary.words,
+        meanings = summary.meanings,
+        tags = summary.tags,
+        queued = summary.queued,
+        "Imported word list"
+    );
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::import::preview;
+    use crate::persistence::Db;
+
+    #[test]
+    fn test_apply_groups_meanings_and_creates_tags() {
+        let mut model = Model::new(Db::in_memory().unwrap(), AppConfig::default());
+        let input = "word,definition,pos,tags\n\
+                     run,to move fast,verb,Verbs/Motion\n\
+                     run,a period of running,noun,Verbs/Motion;Nouns\n\
+                     walk,,,";
+        let result = preview(input, &model.word_registry, &model.meaning_registry);
+        let summary = apply(&result, &mut model, true);
+
+        assert_eq!(summary.words, 2);
+        assert_eq!(summary.meanings, 2);
+        assert_eq!(summary.tags, 3);
+        assert_eq!(summary.queued, 2);
+        let motion = model.tag_registry.find_by_path("verbs/motion").unwrap();
+        assert_eq!(model.meaning_registry.iter_by_tag(motion).count(), 2);
+    }
+
+    #[test]
+    fn test_apply_adds_meaning_to_existing_word() {
+        let mut model = Model::new(Db::in_memory().unwrap(), AppConfig::default());
+        let word_id = model.word_registry.create_word("run", None).unwrap();
+        let input = "Run,a period of running,noun\nrun,to move fast,verb";
+        let result = preview(input, &model.word_registry, &model.meaning_registry);
+        let summary = apply(&result, &mut model, false);
+
+        assert_eq!(summary.words, 0);
+        assert_eq!(summary.meanings, 2);
+        assert_eq!(model.word_registry.count(), 1);
+        assert_eq!(model.meaning_registry.count_by_word(word_id), 2);
+        assert_eq!(
+            model.word_registry.get(word_id).unwrap().meaning_ids.len(),
+            2
+        );
+    }
+}
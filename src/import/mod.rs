@@ -0,0 +1,12 @@
+//! Bulk vocabulary import from CSV/TSV word lists.
+//!
+//! Importing is two-step: [`preview`] parses the file and classifies each row
+//! as new, a new meaning of an existing word, duplicate or invalid without
+//! touching the model, then [`apply`] creates the previewed words, meanings
+//! and tags.
+
+mod apply;
+mod preview;
+
+pub use apply::{ImportSummary, apply};
+pub use preview::{COLUMNS, ImportIssue, ImportPreview, ImportRow, preview};
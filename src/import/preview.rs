@@ -0,0 +1,270 @@
+//! Parsing and classification of import rows.
+
+use std::collections::HashSet;
+
+use csv::{ReaderBuilder, Trim};
+use langtag::LangTagBuf;
+
+use crate::models::{CefrLevel, PartOfSpeech, WordId};
+use crate::query::normalize::fold;
+use crate::registry::{MeaningRegistry, WordRegistry};
+
+/// Recognised columns, in their default order when the file has no header.
+pub const COLUMNS: [&str; 6] = ["word", "definition", "pos", "cefr", "language", "tags"];
+
+/// A successfully parsed row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    /// 1-based line number in the source file
+    pub line: u64,
+    pub word: String,
+    pub definition: String,
+    pub pos: PartOfSpeech,
+    pub cefr: Option<CefrLevel>,
+    pub language: Option<LangTagBuf>,
+    /// `/`-separated tag paths
+    pub tags: Vec<String>,
+}
+
+/// A row that could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportIssue {
+    pub line: u64,
+    pub message: String,
+}
+
+/// What an import would do, before anything is written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportPreview {
+    /// Rows that will be created
+    pub creates: Vec<ImportRow>,
+    /// Rows adding a new meaning to a word already in the library
+    pub additions: Vec<ImportRow>,
+    /// Rows whose word, and meaning if any, already exist or appeared
+    /// earlier in the file; skipped on import
+    pub duplicates: Vec<ImportRow>,
+    /// Rows that failed to parse
+    pub errors: Vec<ImportIssue>,
+}
+
+impl ImportPreview {
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.additions.is_empty()
+    }
+}
+
+/// Parses `text` and classifies each row against the existing words and
+/// their meanings. A row repeating an earlier row's word and definition,
+/// ignoring case and accents, is a duplicate.
+///
+/// Tab-separated input is detected from the first line; otherwise commas are
+/// used. A first row starting with `word` is treated as a header and columns
+/// are matched by name; without one, columns follow [`COLUMNS`].
+pub fn preview(text: &str, words: &WordRegistry, meanings: &MeaningRegistry) -> ImportPreview {
+    let delimiter = if text.lines().next().is_some_and(|l| l.contains('\t')) {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(text.as_bytes());
+
+    let mut result = ImportPreview::default();
+    let mut columns: [Option<usize>; COLUMNS.len()] = std::array::from_fn(Some);
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+
+    for (index, record) in reader.records().enumerate() {
+        let line = index as u64 + 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                result.errors.push(ImportIssue {
+                    line: e.position().map(|p| p.line()).unwrap_or(line),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or(line);
+
+        if index == 0
+            && record
+                .get(0)
+                .is_some_and(|f| f.eq_ignore_ascii_case("word"))
+        {
+            columns = COLUMNS.map(|name| record.iter().position(|h| h.eq_ignore_ascii_case(name)));
+            continue;
+        }
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+
+        let field = |column: usize| -> &str {
+            columns[column]
+                .and_then(|i| record.get(i))
+                .unwrap_or_default()
+        };
+
+        match parse_row(
+            line,
+            field(0),
+            field(1),
+            field(2),
+            field(3),
+            field(4),
+            field(5),
+        ) {
+            Ok(row) if !seen.insert((fold(&row.word), fold(&row.definition))) => {
+                result.duplicates.push(row)
+            }
+            Ok(row) => match words.find_by_content(&row.word) {
+                None => result.creates.push(row),
+                Some(word_id) if is_new_meaning(&row, word_id, meanings) => {
+                    result.additions.push(row)
+                }
+                Some(_) => result.duplicates.push(row),
+            },
+            Err(message) => result.errors.push(ImportIssue { line, message }),
+        }
+    }
+
+    tracing::debug!(
+        creates = result.creates.len(),
+        additions = result.additions.len(),
+        duplicates = result.duplicates.len(),
+        errors = result.errors.len(),
+        "Built import preview"
+    );
+    result
+}
+
+/// Returns true if the row has a definition the word does not have yet,
+/// compared case-insensitively.
+fn is_new_meaning(row: &ImportRow, word_id: WordId, meanings: &MeaningRegistry) -> bool {
+    let definition = row.definition.to_lowercase();
+    !definition.is_empty()
+        && !meanings
+            .iter_by_word(word_id)
+            .any(|(_, m)| m.definition.trim().to_lowercase() == definition)
+}
+
+fn parse_row(
+    line: u64,
+    word: &str,
+    definition: &str,
+    pos: &str,
+    cefr: &str,
+    language: &str,
+    tags: &str,
+) -> Result<ImportRow, String> {
+    if word.is_empty() {
+        return Err("Missing word".to_string());
+    }
+    let pos = if pos.is_empty() {
+        PartOfSpeech::default()
+    } else {
+        PartOfSpeech::try_from_str(pos)
+            .ok_or_else(|| format!("Unknown part of speech \"{}\"", pos))?
+    };
+    let cefr = if cefr.is_empty() {
+        None
+    } else {
+        Some(
+            CefrLevel::try_from_str(cefr)
+                .ok_or_else(|| format!("Unknown CEFR level \"{}\"", cefr))?,
+        )
+    };
+    let language = if language.is_empty() {
+        None
+    } else {
+        Some(
+            language
+                .parse::<LangTagBuf>()
+                .map_err(|_| format!("Invalid language tag \"{}\"", language))?,
+        )
+    };
+    let tags = tags
+        .split(';')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Ok(ImportRow {
+        line,
+        word: word.to_string(),
+        definition: definition.to_string(),
+        pos,
+        cefr,
+        language,
+        tags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("run,to move fast,verb,A1,en,Sport", 1, 0; "positional csv")]
+    #[test_case("word\tpos\ndog\tnoun\ncat\tnoun", 2, 0; "tsv with header")]
+    #[test_case("run,to move fast,verbish", 0, 1; "unknown pos")]
+    #[test_case("run,,,Z9", 0, 1; "unknown cefr")]
+    #[test_case(",orphan definition", 0, 1; "missing word")]
+    #[test_case("\n\nrun\n", 1, 0; "blank lines skipped")]
+    fn test_preview_counts(input: &str, creates: usize, errors: usize) {
+        let result = preview(input, &WordRegistry::new(), &MeaningRegistry::new());
+        assert_eq!(result.creates.len(), creates);
+        assert_eq!(result.errors.len(), errors);
+    }
+
+    #[test]
+    fn test_preview_fields() {
+        let input = "word,tags,definition,cefr\n\"set, up\",Travel/Food; Exam,\"to arrange\",b2";
+        let result = preview(input, &WordRegistry::new(), &MeaningRegistry::new());
+        let row = &result.creates[0];
+        assert_eq!(row.line, 2);
+        assert_eq!(row.word, "set, up");
+        assert_eq!(row.definition, "to arrange");
+        assert_eq!(row.cefr, Some(CefrLevel::B2));
+        assert_eq!(row.tags, vec!["Travel/Food", "Exam"]);
+    }
+
+    #[test]
+    fn test_preview_duplicates() {
+        let mut words = WordRegistry::new();
+        words.create_word("Run", None);
+        let result = preview("run\nwalk", &words, &MeaningRegistry::new());
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.creates.len(), 1);
+    }
+
+    #[test_case("run,to move fast\nrun,to move fast", 1, 1; "repeated row")]
+    #[test_case("run,to move fast\nRUN,to move fást", 1, 1; "repeat ignores case and accents")]
+    #[test_case("run,to move fast\nrun,to flow", 2, 0; "different definitions")]
+    #[test_case("run\nrun", 1, 1; "repeated word without definition")]
+    fn test_preview_repeated_rows(input: &str, creates: usize, duplicates: usize) {
+        let result = preview(input, &WordRegistry::new(), &MeaningRegistry::new());
+        assert_eq!(result.creates.len(), creates);
+        assert_eq!(result.duplicates.len(), duplicates);
+    }
+
+    #[test_case("run,to move fast", 0, 1; "same definition")]
+    #[test_case("run,To Move Fast", 0, 1; "definition case ignored")]
+    #[test_case("run,a period of running", 1, 0; "new definition")]
+    #[test_case("run", 0, 1; "no definition")]
+    fn test_preview_existing_word(input: &str, additions: usize, duplicates: usize) {
+        let mut words = WordRegistry::new();
+        let mut meanings = MeaningRegistry::new();
+        let word_id = words.create_word("run", None).unwrap();
+        meanings.create_meaning(word_id, "to move fast", PartOfSpeech::Verb, None);
+        let result = preview(input, &words, &meanings);
+        assert_eq!(result.additions.len(), additions);
+        assert_eq!(result.duplicates.len(), duplicates);
+        assert!(result.creates.is_empty());
+    }
+}
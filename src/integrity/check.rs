@@ -0,0 +1,142 @@
+//! Read-only integrity pass.
+
+use std::collections::BTreeSet;
+
+use super::Issue;
+use crate::models::TagId;
+use crate::state::Model;
+
+/// Finds every inconsistency in the library, in a stable order.
+pub fn check(model: &Model) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (word_id, word) in model.word_registry.iter() {
+        for meaning_id in &word.meaning_ids {
+            let owned = model
+                .meaning_registry
+                .get(*meaning_id)
+                .is_some_and(|m| m.word_id == *word_id);
+            if !owned {
+                issues.push(Issue::DanglingMeaning {
+                    word_id: *word_id,
+                    meaning_id: *meaning_id,
+                });
+            }
+        }
+    }
+
+    for (meaning_id, meaning) in model.meaning_registry.iter() {
+        match model.word_registry.get(meaning.word_id) {
+            None => issues.push(Issue::OrphanMeaning {
+                meaning_id: *meaning_id,
+                word_id: meaning.word_id,
+            }),
+            Some(word) if !word.meaning_ids.contains(meaning_id) => {
+                issues.push(Issue::UnlistedMeaning {
+                    word_id: meaning.word_id,
+                    meaning_id: *meaning_id,
+                })
+            }
+            Some(_) => {}
+        }
+        for tag_id in &meaning.tag_ids {
+            if !model.tag_registry.exists(*tag_id) {
+                issues.push(Issue::MissingTag {
+                    meaning_id: *meaning_id,
+                    tag_id: *tag_id,
+                });
+            }
+        }
+    }
+
+    for (tag_id, tag) in model.tag_registry.iter() {
+        if let Some(parent_id) = tag.parent_id {
+            match model.tag_registry.get(parent_id) {
+                None => issues.push(Issue::MissingParent {
+                    tag_id: *tag_id,
+                    parent_id,
+                }),
+                Some(parent) if !parent.children_ids.contains(tag_id) => {
+                    issues.push(Issue::UnlistedChild {
+                        parent_id,
+                        tag_id: *tag_id,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for child_id in &tag.children_ids {
+            let owned = model
+                .tag_registry
+                .get(*child_id)
+                .is_some_and(|c| c.parent_id == Some(*tag_id));
+            if !owned {
+                issues.push(Issue::DanglingChild {
+                    tag_id: *tag_id,
+                    child_id: *child_id,
+                });
+            }
+        }
+    }
+    issues.extend(
+        tag_cycles(model)
+            .into_iter()
+            .map(|tag_id| Issue::TagCycle { tag_id }),
+    );
+
+    for (cloze_id, cloze) in model.cloze_registry.iter() {
+        if !model.meaning_registry.exists(cloze.meaning_id) {
+            issues.push(Issue::OrphanCloze {
+                cloze_id: *cloze_id,
+                meaning_id: cloze.meaning_id,
+            });
+        }
+    }
+    for item in model.queue_registry.get_items() {
+        if !model.meaning_registry.exists(item.meaning_id) {
+            issues.push(Issue::OrphanQueueItem {
+                item_id: item.id,
+                meaning_id: item.meaning_id,
+            });
+        }
+    }
+    for (cloze_id, _) in model.schedule_registry.iter() {
+        if !model.cloze_registry.exists(*cloze_id) {
+            issues.push(Issue::OrphanSchedule {
+                cloze_id: *cloze_id,
+            });
+        }
+    }
+    for (review_id, review) in model.review_registry.iter() {
+        if !model.cloze_registry.exists(review.cloze_id) {
+            issues.push(Issue::OrphanReview {
+                review_id: *review_id,
+                cloze_id: review.cloze_id,
+            });
+        }
+    }
+
+    issues.sort();
+    issues
+}
+
+/// Returns one tag per parent cycle: the smallest id on the loop, so that
+/// detaching it from its parent breaks the cycle.
+fn tag_cycles(model: &Model) -> BTreeSet<TagId> {
+    let mut cycles = BTreeSet::new();
+    for (start, _) in model.tag_registry.iter() {
+        let mut path = vec![*start];
+        let mut current = model.tag_registry.get(*start).and_then(|t| t.parent_id);
+        while let Some(id) = current {
+            if let Some(pos) = path.iter().position(|p| *p == id) {
+                if let Some(min) = path[pos..].iter().min() {
+                    cycles.insert(*min);
+                }
+                break;
+            }
+            path.push(id);
+            current = model.tag_registry.get(id).and_then(|t| t.parent_id);
+        }
+    }
+    cycles
+}
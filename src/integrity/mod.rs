@@ -0,0 +1,132 @@
+//! Library integrity checks and repair.
+//!
+//! `check` walks every registry and reports references that do not resolve
+//! or disagree with each other. `repair` fixes the reported issues, either
+//! only by correcting links or also by deleting orphaned entities.
+
+mod check;
+mod repair;
+
+use std::fmt;
+
+use crate::models::{ClozeId, MeaningId, ReviewId, TagId, WordId};
+
+pub use check::check;
+pub use repair::{RepairOptions, repair};
+
+/// A single inconsistency in the library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Issue {
+    /// Word lists a meaning that is missing or belongs to another word
+    DanglingMeaning {
+        word_id: WordId,
+        meaning_id: MeaningId,
+    },
+    /// Meaning is not listed by its word
+    UnlistedMeaning {
+        word_id: WordId,
+        meaning_id: MeaningId,
+    },
+    /// Meaning references a missing tag
+    MissingTag {
+        meaning_id: MeaningId,
+        tag_id: TagId,
+    },
+    /// Tag references a missing parent
+    MissingParent { tag_id: TagId, parent_id: TagId },
+    /// Tag lists a child that is missing or has another parent
+    DanglingChild { tag_id: TagId, child_id: TagId },
+    /// Tag is not listed by its parent
+    UnlistedChild { parent_id: TagId, tag_id: TagId },
+    /// Tag's parent chain loops back to itself
+    TagCycle { tag_id: TagId },
+    /// Meaning of a deleted word
+    OrphanMeaning {
+        meaning_id: MeaningId,
+        word_id: WordId,
+    },
+    /// Cloze of a deleted meaning
+    OrphanCloze {
+        cloze_id: ClozeId,
+        meaning_id: MeaningId,
+    },
+    /// Queue item for a deleted meaning
+    OrphanQueueItem {
+        item_id: WordId,
+        meaning_id: MeaningId,
+    },
+    /// Schedule of a deleted cloze
+    OrphanSchedule { cloze_id: ClozeId },
+    /// Review log of a deleted cloze
+    OrphanReview {
+        review_id: ReviewId,
+        cloze_id: ClozeId,
+    },
+}
+
+impl Issue {
+    /// Returns true if fixing this issue deletes data.
+    pub fn is_orphan(&self) -> bool {
+        matches!(
+            self,
+            Issue::OrphanMeaning { .. }
+                | Issue::OrphanCloze { .. }
+                | Issue::OrphanQueueItem { .. }
+                | Issue::OrphanSchedule { .. }
+                | Issue::OrphanReview { .. }
+        )
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::DanglingMeaning {
+                word_id,
+                meaning_id,
+            } => write!(f, "word {word_id} lists missing meaning {meaning_id}"),
+            Issue::UnlistedMeaning {
+                word_id,
+                meaning_id,
+            } => write!(f, "meaning {meaning_id} is not listed by word {word_id}"),
+            Issue::MissingTag { meaning_id, tag_id } => {
+                write!(f, "meaning {meaning_id} references missing tag {tag_id}")
+            }
+            Issue::MissingParent { tag_id, parent_id } => {
+                write!(f, "tag {tag_id} references missing parent {parent_id}")
+            }
+            Issue::DanglingChild { tag_id, child_id } => {
+                write!(f, "tag {tag_id} lists missing child {child_id}")
+            }
+            Issue::UnlistedChild { parent_id, tag_id } => {
+                write!(f, "tag {tag_id} is not listed by parent {parent_id}")
+            }
+            Issue::TagCycle { tag_id } => write!(f, "tag {tag_id} is its own ancestor"),
+            Issue::OrphanMeaning {
+                meaning_id,
+                word_id,
+            } => write!(f, "meaning {meaning_id} belongs to missing word {word_id}"),
+            Issue::OrphanCloze {
+                cloze_id,
+                meaning_id,
+            } => write!(
+                f,
+                "cloze {cloze_id} belongs to missing meaning {meaning_id}"
+            ),
+            Issue::OrphanQueueItem {
+                item_id,
+                meaning_id,
+            } => write!(
+                f,
+                "queue item {item_id} is for missing meaning {meaning_id}"
+            ),
+            Issue::OrphanSchedule { cloze_id } => {
+                write!(f, "schedule for missing cloze {cloze_id}")
+            }
+            Issue::OrphanReview {
+                review_id,
+                cloze_id,
+            } => write!(f, "review {review_id} is for missing cloze {cloze_id}"),
+        }
+    }
+}
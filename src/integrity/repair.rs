@@ -0,0 +1,218 @@
+//! Automatic repair of integrity issues.
+
+use super::{Issue, check};
+use crate::state::Model;
+
+/// Passes made before giving up; deleting an orphan can orphan its
+/// dependents (meaning → cloze → schedule), which the next pass picks up.
+const MAX_PASSES: usize = 4;
+
+/// Which issues `repair` may fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RepairOptions {
+    /// Correct links: drop dangling ids, relist unlisted children, detach
+    /// tags from missing parents and break parent cycles
+    pub fix_links: bool,
+    /// Delete meanings, clozes, queue items, schedules and reviews whose
+    /// owner no longer exists
+    pub delete_orphans: bool,
+}
+
+impl RepairOptions {
+    /// Fixes everything.
+    pub fn all() -> Self {
+        Self {
+            fix_links: true,
+            delete_orphans: true,
+        }
+    }
+
+    fn allows(&self, issue: &Issue) -> bool {
+        if issue.is_orphan() {
+            self.delete_orphans
+        } else {
+            self.fix_links
+        }
+    }
+}
+
+/// Fixes the issues allowed by `options` and returns them. Changes are left
+/// dirty for the next flush.
+pub fn repair(model: &mut Model, options: RepairOptions) -> Vec<Issue> {
+    let mut fixed = Vec::new();
+    for _ in 0..MAX_PASSES {
+        let issues: Vec<Issue> = check(model)
+            .into_iter()
+            .filter(|i| options.allows(i))
+            .collect();
+        if issues.is_empty() {
+            break;
+        }
+        for issue in issues {
+            apply(model, issue);
+            tracing::info!(%issue, "Repaired integrity issue");
+            fixed.push(issue);
+        }
+    }
+    fixed
+}
+
+fn apply(model: &mut Model, issue: Issue) {
+    match issue {
+        Issue::DanglingMeaning {
+            word_id,
+            meaning_id,
+        } => {
+            model.word_registry.remove_meaning(word_id, meaning_id);
+        }
+        Issue::UnlistedMeaning {
+            word_id,
+            meaning_id,
+        } => {
+            model.word_registry.add_meaning(word_id, meaning_id);
+        }
+        Issue::MissingTag { meaning_id, tag_id } => {
+            model.meaning_registry.remove_tag(meaning_id, tag_id);
+        }
+        Issue::MissingParent { tag_id, .. } => {
+            if let Some(tag) = model.tag_registry.get_mut(tag_id) {
+                tag.parent_id = None;
+            }
+        }
+        Issue::DanglingChild { tag_id, child_id } => {
+            if let Some(tag) = model.tag_registry.get_mut(tag_id) {
+                tag.children_ids.remove(&child_id);
+            }
+        }
+        Issue::UnlistedChild { parent_id, tag_id } => {
+            if let Some(parent) = model.tag_registry.get_mut(parent_id) {
+                parent.children_ids.insert(tag_id);
+            }
+        }
+        Issue::TagCycle { tag_id } => {
+            let parent_id = model.tag_registry.get(tag_id).and_then(|t| t.parent_id);
+            if let Some(parent) = parent_id.and_then(|id| model.tag_registry.get_mut(id)) {
+                parent.children_ids.remove(&tag_id);
+            }
+            if let Some(tag) = model.tag_registry.get_mut(tag_id) {
+                tag.parent_id = None;
+            }
+        }
+        Issue::OrphanMeaning { meaning_id, .. } => {
+            model.meaning_registry.delete(meaning_id);
+        }
+        Issue::OrphanCloze { cloze_id, .. } => {
+            model.cloze_registry.delete(cloze_id);
+        }
+        Issue::OrphanQueueItem { item_id, .. } => {
+            model.queue_registry.remove(item_id);
+        }
+        Issue::OrphanSchedule { cloze_id } => {
+            model.schedule_registry.delete(cloze_id);
+        }
+        Issue::OrphanReview { review_id, .. } => {
+            model.review_registry.delete(review_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::models::{Cloze, ClozeSegment, Meaning, PartOfSpeech, Tag, WordId};
+    use crate::persistence::Db;
+
+    fn model() -> Model {
+        Model::new(Db::in_memory().unwrap(), AppConfig::default())
+    }
+
+    #[test]
+    fn test_healthy_library_has_no_issues() {
+        let mut model = model();
+        let word_id = model.word_registry.create_word("hello", None).unwrap();
+        let meaning_id = model
+            .meaning_registry
+            .create_meaning(word_id, "a greeting", PartOfSpeech::Noun, None)
+            .unwrap();
+        model.word_registry.add_meaning(word_id, meaning_id);
+        let parent = model
+            .tag_registry
+            .get_or_create_path("Travel/Food")
+            .unwrap();
+        model.meaning_registry.add_tag(meaning_id, parent);
+        assert_eq!(check(&model), vec![]);
+    }
+
+    fn broken_library() -> Model {
+        let mut model = model();
+        // Meaning of a deleted word, with a cloze
+        let meaning = Meaning::builder()
+            .word_id(WordId::new())
+            .definition("lost".to_string())
+            .pos(PartOfSpeech::Noun)
+            .build();
+        let meaning_id = meaning.id;
+        model.meaning_registry.add(meaning);
+        model.cloze_registry.add(
+            Cloze::builder()
+                .meaning_id(meaning_id)
+                .segments(vec![ClozeSegment::Blank("lost".to_string())])
+                .build(),
+        );
+        // Two tags that are each other's parent
+        let mut a = Tag::builder().name("A".to_string()).build();
+        let mut b = Tag::builder().name("B".to_string()).build();
+        a.parent_id = Some(b.id);
+        b.parent_id = Some(a.id);
+        a.children_ids.insert(b.id);
+        b.children_ids.insert(a.id);
+        model.tag_registry.add(a);
+        model.tag_registry.add(b);
+        model
+    }
+
+    #[test]
+    fn test_check_reports_without_changing() {
+        let model = broken_library();
+        let issues = check(&model);
+        assert_eq!(
+            issues.iter().filter(|i| i.is_orphan()).count(),
+            1,
+            "{issues:?}"
+        );
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|i| matches!(i, Issue::TagCycle { .. }))
+                .count(),
+            1
+        );
+        assert_eq!(model.meaning_registry.count(), 1);
+    }
+
+    #[test]
+    fn test_repair_links_only_keeps_orphans() {
+        let mut model = broken_library();
+        let fixed = repair(
+            &mut model,
+            RepairOptions {
+                fix_links: true,
+                delete_orphans: false,
+            },
+        );
+        assert!(fixed.iter().all(|i| !i.is_orphan()));
+        assert!(check(&model).iter().all(|i| i.is_orphan()));
+        assert_eq!(model.cloze_registry.count(), 1);
+    }
+
+    #[test]
+    fn test_repair_all_cascades() {
+        let mut model = broken_library();
+        repair(&mut model, RepairOptions::all());
+        assert_eq!(check(&model), vec![]);
+        assert_eq!(model.meaning_registry.count(), 0);
+        assert_eq!(model.cloze_registry.count(), 0);
+        assert_eq!(model.tag_registry.count(), 2);
+    }
+}
@@ -7,14 +7,23 @@ pub use self::app::App;
 pub use self::message::Message;
 
 pub mod assets;
+pub mod backup;
+pub mod commands;
 pub mod config;
 pub mod dictionary;
+pub mod export;
 pub mod i18n;
+pub mod import;
+pub mod integrity;
 pub mod models;
 pub mod persistence;
 pub mod query;
 pub mod registry;
+pub mod scheduler;
 pub mod state;
 pub mod ui;
 
+#[cfg(test)]
+mod test_support;
+
 pub use self::config::{AppConfig, CliConfig, EnvConfig, LogLevel};
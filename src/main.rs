@@ -5,6 +5,8 @@ use clozer::config::{AppConfig, CliConfig, EnvConfig};
 fn main() {
     // Parse CLI args first to get log level for tracing init
     let cli = CliConfig::load(std::env::args_os());
+    let command = cli.command.clone();
+    let read_only = cli.read_only;
 
     // Load env config
     let env = EnvConfig::load(std::env::vars()).unwrap_or_default();
@@ -18,6 +20,8 @@ fn main() {
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::fmt::layer()
+                // Keep stdout clean for headless command output
+                .with_writer(std::io::stderr)
                 .with_target(true)
                 .with_thread_ids(true)
                 .with_file(true)
@@ -43,5 +47,13 @@ fn main() {
         "Clozer starting up"
     );
 
+    if let Some(command) = command {
+        if let Err(e) = clozer::commands::run(command, app_config, read_only) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     clozer::App::run(app_config);
 }
@@ -12,6 +12,7 @@ use crate::ui::practice::PracticeMessage;
 use crate::ui::queue::QueueMessage;
 use crate::ui::settings::SettingsMessage;
 use crate::ui::tags::TagsMessage;
+use crate::ui::trash::TrashMessage;
 use crate::ui::words::WordsMessage;
 
 /// Top-level application messages for the single-window application.
@@ -32,12 +33,18 @@ pub enum Message {
     // Practice panel
     Practice(PracticeMessage),
 
+    // Trash panel
+    Trash(TrashMessage),
+
     // Settings panel
     Settings(SettingsMessage),
 
     // Navigation
     Navigate(NavItem),
 
+    // Open another profile's library; None for the default library
+    SwitchProfile(Option<String>),
+
     // Global (not window-specific)
     QueueGenerationResult(QueueGenerationResult),
 
@@ -56,6 +63,21 @@ pub enum Message {
     // Tab key pressed (for search suggestion acceptance)
     TabPressed,
 
+    // Periodic autosave of dirty entities (from subscription)
+    AutosaveTick,
+
+    // Periodic check for a due daily snapshot (from subscription)
+    SnapshotTick,
+
+    // Periodic check for requests handed over by other processes (from subscription)
+    InboxTick,
+
+    // Revert the most recent library edit (Ctrl+Z or notification action)
+    Undo,
+
+    // Reapply the most recently undone edit (Ctrl+Shift+Z)
+    Redo,
+
     // Push a notification banner to the UI
     PushNotification(Notification),
 
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use fancy_regex::Regex;
 use once_cell::sync::Lazy;
 use std::fmt;
@@ -29,6 +30,10 @@ pub struct Cloze {
     pub id: ClozeId,
     pub meaning_id: MeaningId,
     pub segments: Vec<ClozeSegment>,
+    #[builder(default = Utc::now())]
+    pub created_at: DateTime<Utc>,
+    #[builder(default = *created_at)]
+    pub updated_at: DateTime<Utc>,
 }
 
 impl Cloze {
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use std::collections::BTreeSet;
 use strum::{Display, VariantArray};
 use typed_builder::TypedBuilder;
@@ -50,8 +51,9 @@ impl PartOfSpeech {
     }
 }
 
-/// CEFR (Common European Framework of Reference) language proficiency levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray)]
+/// CEFR (Common European Framework of Reference) language proficiency levels,
+/// ordered from beginner to proficient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, VariantArray)]
 pub enum CefrLevel {
     /// A1: Beginner
     A1,
@@ -67,6 +69,22 @@ pub enum CefrLevel {
     C2,
 }
 
+impl CefrLevel {
+    /// Attempt to parse a CEFR level such as `b2` or `B2`.
+    /// Returns `None` if the string isn't a known level.
+    pub fn try_from_str(s: &str) -> Option<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "A1" => Some(CefrLevel::A1),
+            "A2" => Some(CefrLevel::A2),
+            "B1" => Some(CefrLevel::B1),
+            "B2" => Some(CefrLevel::B2),
+            "C1" => Some(CefrLevel::C1),
+            "C2" => Some(CefrLevel::C2),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, TypedBuilder)]
 #[builder(mutators(
     fn with_tag(&mut self, tag_id: TagId) {
@@ -83,4 +101,8 @@ pub struct Meaning {
     pub cefr_level: Option<CefrLevel>,
     #[builder(default, via_mutators)]
     pub tag_ids: BTreeSet<TagId>,
+    #[builder(default = Utc::now())]
+    pub created_at: DateTime<Utc>,
+    #[builder(default = *created_at)]
+    pub updated_at: DateTime<Utc>,
 }
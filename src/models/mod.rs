@@ -2,7 +2,10 @@ pub mod cloze;
 pub mod meaning;
 pub mod model;
 pub mod provider;
+pub mod review;
+pub mod schedule;
 pub mod tag;
+pub mod trash;
 pub mod types;
 pub mod word;
 
@@ -10,6 +13,9 @@ pub use cloze::{Cloze, ClozeSegment};
 pub use meaning::{CefrLevel, Meaning, PartOfSpeech};
 pub use model::Model;
 pub use provider::{Provider, ProviderType};
+pub use review::{ReviewAnswer, ReviewLog};
+pub use schedule::CardSchedule;
 pub use tag::Tag;
-pub use types::{ClozeId, MeaningId, ModelId, ProviderId, TagId, WordId};
+pub use trash::TrashEntry;
+pub use types::{ClozeId, MeaningId, ModelId, ProviderId, ReviewId, TagId, TrashId, WordId};
 pub use word::Word;
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+
+use super::{ClozeId, ReviewId};
+
+/// The answer given for a single blank during review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewAnswer {
+    pub given: String,
+    pub correct: bool,
+}
+
+/// A single practice attempt at a cloze.
+#[derive(Debug, Clone, PartialEq, TypedBuilder)]
+pub struct ReviewLog {
+    #[builder(default = ReviewId::new())]
+    pub id: ReviewId,
+    pub cloze_id: ClozeId,
+    #[builder(default = Utc::now())]
+    pub reviewed_at: DateTime<Utc>,
+    /// Answers in blank order
+    #[builder(default)]
+    pub answers: Vec<ReviewAnswer>,
+    /// Time from showing the cloze to submitting the answers
+    #[builder(default)]
+    pub duration: Duration,
+}
+
+impl ReviewLog {
+    pub fn correct_count(&self) -> usize {
+        self.answers.iter().filter(|a| a.correct).count()
+    }
+
+    /// Returns true if every blank was answered correctly.
+    pub fn is_correct(&self) -> bool {
+        !self.answers.is_empty() && self.answers.iter().all(|a| a.correct)
+    }
+}
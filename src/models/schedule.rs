@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use typed_builder::TypedBuilder;
+
+use super::ClozeId;
+
+/// Starting ease factor for a card that has never been reviewed.
+pub const DEFAULT_EASE: f32 = 2.5;
+
+/// Spaced-repetition review state for a single cloze.
+#[derive(Debug, Clone, PartialEq, TypedBuilder)]
+pub struct CardSchedule {
+    pub cloze_id: ClozeId,
+    /// When the card is next due for review
+    #[builder(default = Utc::now())]
+    pub due: DateTime<Utc>,
+    /// Current review interval in days
+    #[builder(default)]
+    pub interval_days: u32,
+    /// Ease factor used to grow the interval
+    #[builder(default = DEFAULT_EASE)]
+    pub ease: f32,
+    /// Consecutive successful reviews
+    #[builder(default)]
+    pub repetitions: u32,
+    /// Number of times the card was forgotten
+    #[builder(default)]
+    pub lapses: u32,
+    #[builder(default)]
+    pub last_reviewed: Option<DateTime<Utc>>,
+}
+
+impl CardSchedule {
+    /// Returns true if the card should be shown at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+}
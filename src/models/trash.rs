@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use typed_builder::TypedBuilder;
+
+use super::{CardSchedule, Cloze, Meaning, MeaningId, ReviewLog, Tag, TagId, TrashId, Word};
+
+/// Entities removed by one delete, kept with their relationships so they
+/// can be restored together.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct TrashEntry {
+    #[builder(default = TrashId::new())]
+    pub id: TrashId,
+    #[builder(default = Utc::now())]
+    pub deleted_at: DateTime<Utc>,
+    #[builder(default)]
+    pub words: Vec<Word>,
+    #[builder(default)]
+    pub meanings: Vec<Meaning>,
+    /// Tags with ancestors before descendants
+    #[builder(default)]
+    pub tags: Vec<Tag>,
+    #[builder(default)]
+    pub clozes: Vec<Cloze>,
+    #[builder(default)]
+    pub schedules: Vec<CardSchedule>,
+    #[builder(default)]
+    pub reviews: Vec<ReviewLog>,
+    /// Meanings that carried a trashed tag, re-tagged on restore
+    #[builder(default)]
+    pub tagged: Vec<(TagId, MeaningId)>,
+}
+
+impl TrashEntry {
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+            && self.meanings.is_empty()
+            && self.tags.is_empty()
+            && self.clozes.is_empty()
+    }
+
+    /// Short description of what was deleted: the first word or tag name,
+    /// or else the first definition or cloze sentence.
+    pub fn title(&self) -> String {
+        if let Some(word) = self.words.first() {
+            word.content.clone()
+        } else if let Some(tag) = self.tags.first() {
+            tag.name.clone()
+        } else if let Some(meaning) = self.meanings.first() {
+            meaning.definition.clone()
+        } else if let Some(cloze) = self.clozes.first() {
+            cloze.render_answers()
+        } else {
+            String::new()
+        }
+    }
+}
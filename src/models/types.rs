@@ -55,3 +55,5 @@ define_id!(TagId);
 define_id!(ClozeId);
 define_id!(ProviderId);
 define_id!(ModelId);
+define_id!(ReviewId);
+define_id!(TrashId);
@@ -1,4 +1,5 @@
 use crate::models::MeaningId;
+use chrono::{DateTime, Utc};
 use langtag::LangTagBuf;
 use std::collections::BTreeSet;
 use typed_builder::TypedBuilder;
@@ -19,4 +20,8 @@ pub struct Word {
     pub meaning_ids: BTreeSet<MeaningId>,
     #[builder(default, setter(strip_option))]
     pub language: Option<LangTagBuf>,
+    #[builder(default = Utc::now())]
+    pub created_at: DateTime<Utc>,
+    #[builder(default = *created_at)]
+    pub updated_at: DateTime<Utc>,
 }
@@ -7,6 +7,25 @@ pub const WORDS_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new
 pub const MEANINGS_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("meanings");
 pub const CLOZES_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("clozes");
 pub const TAGS_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("tags");
+pub const SCHEDULES_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("schedules");
+pub const REVIEWS_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("reviews");
+pub const QUEUE_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("queue");
+pub const TRASH_TABLE: TableDefinition<[u8; 16], Vec<u8>> = TableDefinition::new("trash");
+
+/// Database metadata such as the schema version.
+pub const META_TABLE: TableDefinition<&str, u64> = TableDefinition::new("meta");
+
+/// Every entity table, created up front so reads never hit a missing table.
+pub const ALL_TABLES: [TableDefinition<[u8; 16], Vec<u8>>; 8] = [
+    WORDS_TABLE,
+    MEANINGS_TABLE,
+    CLOZES_TABLE,
+    TAGS_TABLE,
+    SCHEDULES_TABLE,
+    REVIEWS_TABLE,
+    QUEUE_TABLE,
+    TRASH_TABLE,
+];
 
 /// Serializes data to bytes using rmp-serde.
 pub fn serialize<T: serde::Serialize>(data: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
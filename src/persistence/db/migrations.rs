@@ -0,0 +1,181 @@
+//! Schema versioning and in-place migrations.
+//!
+//! The schema version lives in `META_TABLE`. Databases written before
+//! versioning have no entry and count as version 1. Migrations for all
+//! missing versions run in a single write transaction together with the
+//! version bump, so an interrupted upgrade leaves the old data untouched.
+
+use redb::{ReadableTable, ReadableTableMetadata, TableDefinition};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::{
+    ALL_TABLES, CLOZES_TABLE, Db, DbError, MEANINGS_TABLE, META_TABLE, WORDS_TABLE, WriteBatch,
+    deserialize, serialize,
+};
+use crate::persistence::dto::{ClozeDto, LEGACY_TIMESTAMP, MeaningDto, WordDto};
+
+/// Schema version written by this build.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Key of the schema version in `META_TABLE`.
+pub(super) const VERSION_KEY: &str = "schema_version";
+
+/// Upgrades a database from the version before `to`.
+struct Migration {
+    to: u64,
+    name: &'static str,
+    run: fn(&WriteBatch) -> Result<(), DbError>,
+}
+
+/// Every migration, ordered by target version.
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 2,
+    name: "timestamps on words, meanings and clozes",
+    run: add_timestamps,
+}];
+
+impl Db {
+    /// Returns the schema version of the stored data. A database without a
+    /// version entry is treated as current if it is empty, else as version 1.
+    pub fn schema_version(&self) -> Result<u64, DbError> {
+        if let Some(version) = self.stored_version()? {
+            return Ok(version);
+        }
+        let t = self.read()?;
+        for table in ALL_TABLES {
+            if !t.open_table(table)?.is_empty()? {
+                return Ok(1);
+            }
+        }
+        Ok(SCHEMA_VERSION)
+    }
+
+    fn stored_version(&self) -> Result<Option<u64>, DbError> {
+        let t = self.read()?;
+        let table = t.open_table(META_TABLE)?;
+        Ok(table.get(VERSION_KEY)?.map(|v| v.value()))
+    }
+
+    /// Runs every migration after `from` and records `SCHEMA_VERSION`.
+    pub(super) fn migrate(&self, from: u64) -> Result<(), DbError> {
+        if from > SCHEMA_VERSION {
+            return Err(DbError::UnsupportedSchema {
+                found: from,
+                supported: SCHEMA_VERSION,
+            });
+        }
+        if self.stored_version()? == Some(SCHEMA_VERSION) {
+            return Ok(());
+        }
+
+        let batch = self.batch()?;
+        for migration in MIGRATIONS.iter().filter(|m| m.to > from) {
+            tracing::info!(
+                version = migration.to,
+                name = migration.name,
+                "Running database migration"
+            );
+            (migration.run)(&batch)?;
+        }
+        batch
+            .tx
+            .open_table(META_TABLE)?
+            .insert(VERSION_KEY, SCHEMA_VERSION)?;
+        batch.commit()?;
+
+        if from < SCHEMA_VERSION {
+            tracing::info!(from, to = SCHEMA_VERSION, "Database migrated");
+        }
+        Ok(())
+    }
+}
+
+/// Decodes, edits and re-encodes every record in a table.
+fn rewrite<T: Serialize + DeserializeOwned>(
+    batch: &WriteBatch,
+    table: TableDefinition<[u8; 16], Vec<u8>>,
+    edit: impl Fn(&mut T),
+) -> Result<(), DbError> {
+    let mut table = batch.open_table(table)?;
+    let records: Vec<([u8; 16], Vec<u8>)> = table
+        .iter()?
+        .map(|r| r.map(|(k, v)| (k.value(), v.value())))
+        .collect::<Result<_, _>>()?;
+    for (key, bytes) in records {
+        let mut dto: T = deserialize(&bytes)?;
+        edit(&mut dto);
+        table.insert(&key, &serialize(&dto)?)?;
+    }
+    Ok(())
+}
+
+/// Version 2: `created_at`/`updated_at` on words, meanings and clozes.
+/// Records from version 1 get `LEGACY_TIMESTAMP`.
+fn add_timestamps(batch: &WriteBatch) -> Result<(), DbError> {
+    rewrite::<WordDto>(batch, WORDS_TABLE, |w| {
+        w.created_at.get_or_insert(LEGACY_TIMESTAMP);
+        w.updated_at.get_or_insert(LEGACY_TIMESTAMP);
+    })?;
+    rewrite::<MeaningDto>(batch, MEANINGS_TABLE, |m| {
+        m.created_at.get_or_insert(LEGACY_TIMESTAMP);
+        m.updated_at.get_or_insert(LEGACY_TIMESTAMP);
+    })?;
+    rewrite::<ClozeDto>(batch, CLOZES_TABLE, |c| {
+        c.created_at.get_or_insert(LEGACY_TIMESTAMP);
+        c.updated_at.get_or_insert(LEGACY_TIMESTAMP);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::db::{SnapshotReason, SnapshotStore, uuid_to_key};
+    use crate::test_support::data_dir;
+    use langtag::LangTagBuf;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_fresh_database_is_current() {
+        let db = Db::in_memory().unwrap();
+        assert_eq!(db.stored_version().unwrap(), Some(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn test_legacy_database_is_backed_up_and_migrated() {
+        let dir = data_dir("migrate");
+        let path = dir.join("data.redb");
+        let id = Uuid::new_v4();
+        {
+            // Version 1 layout: no meta table, words without timestamps
+            let db = redb::Database::create(&path).unwrap();
+            let t = db.begin_write().unwrap();
+            let legacy = (id, "hello", Vec::<Uuid>::new(), None::<LangTagBuf>);
+            t.open_table(WORDS_TABLE)
+                .unwrap()
+                .insert(&uuid_to_key(id), &serialize(&legacy).unwrap())
+                .unwrap();
+            t.commit().unwrap();
+        }
+
+        let db = Db::new(&path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+        let word: WordDto = db.load_entity(WORDS_TABLE, id).unwrap().unwrap();
+        assert_eq!(word.created_at, Some(LEGACY_TIMESTAMP));
+
+        let snapshots = SnapshotStore::new(&dir).list();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].reason, SnapshotReason::Migration);
+        drop(db);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let db = Db::in_memory().unwrap();
+        assert!(matches!(
+            db.migrate(SCHEMA_VERSION + 1),
+            Err(DbError::UnsupportedSchema { .. })
+        ));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides generic CRUD operations for all entity types.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use redb::{Database, ReadableDatabase, ReadableTable, ReadableTableMetadata};
 use serde::Serialize;
@@ -10,7 +10,11 @@ use serde::de::DeserializeOwned;
 use uuid::Uuid;
 
 mod core;
+mod migrations;
+mod snapshot;
 pub use core::*;
+pub use migrations::SCHEMA_VERSION;
+pub use snapshot::{Snapshot, SnapshotReason, SnapshotStore};
 
 /// Database errors.
 #[derive(Debug, thiserror::Error)]
@@ -38,6 +42,12 @@ pub enum DbError {
 
     #[error("Commit error: {0}")]
     Commit(#[from] redb::CommitError),
+
+    #[error("Database schema version {found} is newer than supported version {supported}")]
+    UnsupportedSchema { found: u64, supported: u64 },
+
+    #[error("Database {0:?} is in use by another Clozer instance")]
+    Locked(PathBuf),
 }
 
 /// Main database wrapper.
@@ -48,14 +58,70 @@ pub struct Db {
 
 impl Db {
     /// Opens or creates the database at the given path.
+    ///
+    /// Databases with an older schema are snapshotted into the `backups`
+    /// directory next to `path` and migrated in place before use.
     pub fn new(path: &Path) -> Result<Self, DbError> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let db = Database::create(path)?;
+        let db = Self::open(path)?;
+        let version = db.schema_version()?;
+        if version < SCHEMA_VERSION
+            && let Some(data_dir) = path.parent()
+        {
+            SnapshotStore::new(data_dir).create(&db, SnapshotReason::Migration)?;
+        }
+        db.migrate(version)?;
+
+        Ok(db)
+    }
+
+    /// Creates a database that lives only in memory.
+    pub fn in_memory() -> Result<Self, DbError> {
+        let db = Self {
+            db: Database::builder().create_with_backend(redb::backends::InMemoryBackend::new())?,
+        };
+        db.create_tables()?;
+        db.migrate(db.schema_version()?)?;
+
+        Ok(db)
+    }
+
+    /// Opens a private in-memory copy of the database at `path`, for reading
+    /// it while another process holds the file. Changes to the copy are
+    /// never written back.
+    pub fn open_copy(path: &Path) -> Result<Self, DbError> {
+        let copy = std::env::temp_dir().join(format!("clozer-copy-{}.redb", Uuid::new_v4()));
+        std::fs::copy(path, &copy)?;
+        let result = Self::in_memory().and_then(|db| db.restore_from(&copy).map(|()| db));
+        if let Err(e) = std::fs::remove_file(&copy) {
+            tracing::warn!(error = %e, path = ?copy, "Failed to remove database copy");
+        }
+        result
+    }
+
+    fn open(path: &Path) -> Result<Self, DbError> {
+        let db = Self {
+            db: Database::create(path).map_err(|e| match e {
+                redb::DatabaseError::DatabaseAlreadyOpen => DbError::Locked(path.to_path_buf()),
+                e => e.into(),
+            })?,
+        };
+        db.create_tables()?;
+        Ok(db)
+    }
 
-        Ok(Self { db })
+    /// Creates any missing entity and metadata tables.
+    fn create_tables(&self) -> Result<(), DbError> {
+        let t = self.write()?;
+        for table in ALL_TABLES {
+            t.open_table(table)?;
+        }
+        t.open_table(META_TABLE)?;
+        t.commit()?;
+        Ok(())
     }
 
     fn read(&self) -> Result<redb::ReadTransaction, redb::TransactionError> {
@@ -66,6 +132,12 @@ impl Db {
         self.db.begin_write()
     }
 
+    /// Starts a write batch. Writes made through it become visible together
+    /// on `WriteBatch::commit`; dropping it uncommitted discards them.
+    pub fn batch(&self) -> Result<WriteBatch, DbError> {
+        Ok(WriteBatch { tx: self.write()? })
+    }
+
     /// Saves an entity to the database (generic over table definition and DTO type).
     pub fn save_entity<T: Serialize>(
         &self,
@@ -156,3 +228,74 @@ impl Db {
         Ok(table.len()?)
     }
 }
+
+/// A single redb write transaction shared by many entity writes.
+pub struct WriteBatch {
+    tx: redb::WriteTransaction,
+}
+
+impl WriteBatch {
+    /// Opens an entity table for writing within this batch.
+    pub fn open_table(
+        &self,
+        table: redb::TableDefinition<[u8; 16], Vec<u8>>,
+    ) -> Result<redb::Table<'_, [u8; 16], Vec<u8>>, DbError> {
+        Ok(self.tx.open_table(table)?)
+    }
+
+    /// Commits every write in the batch atomically.
+    pub fn commit(self) -> Result<(), DbError> {
+        self.tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::data_dir;
+
+    #[test]
+    fn test_uncommitted_batch_is_discarded() {
+        let db = Db::in_memory().unwrap();
+        let id = Uuid::new_v4();
+        {
+            let batch = db.batch().unwrap();
+            let mut table = batch.open_table(WORDS_TABLE).unwrap();
+            table
+                .insert(&uuid_to_key(id), &serialize(&"hello").unwrap())
+                .unwrap();
+        }
+        assert_eq!(db.count_entities(WORDS_TABLE).unwrap(), 0);
+
+        let batch = db.batch().unwrap();
+        {
+            let mut table = batch.open_table(WORDS_TABLE).unwrap();
+            table
+                .insert(&uuid_to_key(id), &serialize(&"hello").unwrap())
+                .unwrap();
+        }
+        batch.commit().unwrap();
+        assert_eq!(db.count_entities(WORDS_TABLE).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_locked_database_can_be_read_as_copy() {
+        let dir = data_dir("lock");
+        let path = dir.join("data.redb");
+        let id = Uuid::new_v4();
+
+        let db = Db::new(&path).unwrap();
+        db.save_entity(WORDS_TABLE, id, &"hello", "word").unwrap();
+        assert!(matches!(Db::new(&path), Err(DbError::Locked(p)) if p == path));
+
+        let copy = Db::open_copy(&path).unwrap();
+        assert_eq!(copy.count_entities(WORDS_TABLE).unwrap(), 1);
+        copy.save_entity(WORDS_TABLE, Uuid::new_v4(), &"world", "word")
+            .unwrap();
+        assert_eq!(db.count_entities(WORDS_TABLE).unwrap(), 1);
+
+        drop(db);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
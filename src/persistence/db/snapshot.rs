@@ -0,0 +1,277 @@
+//! Rotating snapshots of the database file.
+//!
+//! A snapshot is a complete copy of every table, written to its own redb
+//! file in the `backups` directory next to the database. File names carry
+//! the creation time and reason, so listing needs no index:
+//! `20261017T093000123-startup.redb`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use redb::{
+    Database, ReadTransaction, ReadableDatabase, ReadableTable, TableError, WriteTransaction,
+};
+
+use super::migrations::VERSION_KEY;
+use super::{ALL_TABLES, Db, DbError, META_TABLE, SCHEMA_VERSION};
+use crate::config::constants::paths;
+
+const EXTENSION: &str = "redb";
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%S%3f";
+
+/// Why a snapshot was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum SnapshotReason {
+    Startup,
+    Daily,
+    /// Before a schema migration
+    Migration,
+    /// Before an import or archive restore
+    Import,
+    /// Before restoring another snapshot
+    Restore,
+    Manual,
+}
+
+/// A snapshot file in the backups directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub reason: SnapshotReason,
+    /// File size in bytes
+    pub size: u64,
+}
+
+impl Snapshot {
+    /// Parses a snapshot from its file name; None for unrelated files.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        if path.extension()? != EXTENSION {
+            return None;
+        }
+        let stem = path.file_stem()?.to_str()?;
+        let (time, reason) = stem.split_once('-')?;
+        let created_at = NaiveDateTime::parse_from_str(time, TIME_FORMAT)
+            .ok()?
+            .and_utc();
+        let reason = SnapshotReason::from_str(reason).ok()?;
+        let size = std::fs::metadata(&path).ok()?.len();
+        Some(Self {
+            path,
+            created_at,
+            reason,
+            size,
+        })
+    }
+}
+
+/// The backups directory of a data directory.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Store for the database in `data_dir`.
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(paths::BACKUPS),
+        }
+    }
+
+    /// Returns all snapshots, newest first.
+    pub fn list(&self) -> Vec<Snapshot> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut snapshots: Vec<Snapshot> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| Snapshot::from_path(e.path()))
+            .collect();
+        snapshots.sort_by(|a, b| (b.created_at, &b.path).cmp(&(a.created_at, &a.path)));
+        snapshots
+    }
+
+    /// Copies the current contents of `db` into a new snapshot.
+    pub fn create(&self, db: &Db, reason: SnapshotReason) -> Result<Snapshot, DbError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let name = format!("{}-{}", Utc::now().format(TIME_FORMAT), reason);
+        let path = self.dir.join(&name).with_extension(EXTENSION);
+        // Write under a temporary name so an interrupted copy is never listed
+        let partial = path.with_extension("partial");
+        {
+            let target = Database::create(&partial)?;
+            let tx = target.begin_write()?;
+            copy_tables(&db.read()?, &tx)?;
+            tx.commit()?;
+        }
+        std::fs::rename(&partial, &path)?;
+
+        let snapshot = Snapshot::from_path(path.clone())
+            .ok_or_else(|| std::io::Error::other(format!("unreadable snapshot {:?}", path)))?;
+        tracing::info!(path = ?snapshot.path, %reason, "Created database snapshot");
+        Ok(snapshot)
+    }
+
+    /// Deletes all but the `keep` newest snapshots of each reason and
+    /// returns how many were removed. Counting per reason stops frequent
+    /// startup snapshots from pushing out the daily and migration ones. At
+    /// least one snapshot of each reason is always kept.
+    pub fn prune(&self, keep: usize) -> usize {
+        let mut kept: HashMap<SnapshotReason, usize> = HashMap::new();
+        let mut removed = 0;
+        for snapshot in self.list() {
+            let count = kept.entry(snapshot.reason).or_default();
+            if *count < keep.max(1) {
+                *count += 1;
+                continue;
+            }
+            match std::fs::remove_file(&snapshot.path) {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    tracing::warn!(error = %e, path = ?snapshot.path, "Failed to remove snapshot")
+                }
+            }
+        }
+        if removed > 0 {
+            tracing::info!(removed, "Pruned old database snapshots");
+        }
+        removed
+    }
+
+    /// Returns true if the newest snapshot is older than `interval`.
+    pub fn is_due(&self, interval: TimeDelta) -> bool {
+        self.list()
+            .first()
+            .is_none_or(|s| Utc::now() - s.created_at >= interval)
+    }
+}
+
+impl Db {
+    /// Replaces every table with the contents of the snapshot at `path`,
+    /// migrating it if it predates the current schema.
+    pub fn restore_from(&self, path: &Path) -> Result<(), DbError> {
+        let source = Database::open(path)?;
+        let from = source.begin_read()?;
+        let version = from.open_table(META_TABLE)?.get(VERSION_KEY)?;
+        if let Some(found) = version.map(|v| v.value())
+            && found > SCHEMA_VERSION
+        {
+            return Err(DbError::UnsupportedSchema {
+                found,
+                supported: SCHEMA_VERSION,
+            });
+        }
+
+        let batch = self.batch()?;
+        for table in ALL_TABLES {
+            batch.tx.delete_table(table)?;
+        }
+        batch.tx.delete_table(META_TABLE)?;
+        copy_tables(&from, &batch.tx)?;
+        batch.commit()?;
+        tracing::info!(path = ?path, "Restored database snapshot");
+
+        self.migrate(self.schema_version()?)
+    }
+}
+
+/// Copies every entity table and the metadata table. Tables missing from
+/// older sources are left empty.
+fn copy_tables(from: &ReadTransaction, to: &WriteTransaction) -> Result<(), DbError> {
+    for table in ALL_TABLES {
+        let mut target = to.open_table(table)?;
+        let source = match from.open_table(table) {
+            Ok(source) => source,
+            Err(TableError::TableDoesNotExist(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in source.iter()? {
+            let (key, value) = entry?;
+            target.insert(&key.value(), &value.value())?;
+        }
+    }
+    let source = from.open_table(META_TABLE)?;
+    let mut target = to.open_table(META_TABLE)?;
+    for entry in source.iter()? {
+        let (key, value) = entry?;
+        target.insert(key.value(), value.value())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::WordDto;
+    use crate::persistence::db::WORDS_TABLE;
+    use crate::test_support::data_dir;
+    use uuid::Uuid;
+
+    fn word(text: &str) -> WordDto {
+        WordDto {
+            id: Uuid::new_v4(),
+            content: text.to_string(),
+            meaning_ids: Vec::new(),
+            language: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_restore_returns_to_snapshot() {
+        let dir = data_dir("snapshot-restore");
+        let db = Db::new(&dir.join("data.redb")).unwrap();
+        let store = SnapshotStore::new(&dir);
+        let kept = word("kept");
+        db.save_entity(WORDS_TABLE, kept.id, &kept, "word").unwrap();
+        let snapshot = store.create(&db, SnapshotReason::Manual).unwrap();
+        let added = word("added");
+        db.save_entity(WORDS_TABLE, added.id, &added, "word")
+            .unwrap();
+
+        db.restore_from(&snapshot.path).unwrap();
+        let words: Vec<(Uuid, WordDto)> = db.iter_entities(WORDS_TABLE).unwrap();
+        assert_eq!(words, vec![(kept.id, kept)]);
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+        drop(db);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_of_each_reason() {
+        let dir = data_dir("snapshot-prune");
+        let db = Db::new(&dir.join("data.redb")).unwrap();
+        let store = SnapshotStore::new(&dir);
+        for reason in [
+            SnapshotReason::Daily,
+            SnapshotReason::Startup,
+            SnapshotReason::Import,
+            SnapshotReason::Startup,
+            SnapshotReason::Startup,
+        ] {
+            store.create(&db, reason).unwrap();
+            // Keep creation times distinct at millisecond resolution
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert!(!store.is_due(TimeDelta::days(1)));
+
+        assert_eq!(store.prune(2), 1);
+        let reasons: Vec<_> = store.list().iter().map(|s| s.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                SnapshotReason::Startup,
+                SnapshotReason::Startup,
+                SnapshotReason::Import,
+                SnapshotReason::Daily,
+            ]
+        );
+        drop(db);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,8 @@
 //! Cloze DTO for serialization.
 
+use super::LEGACY_TIMESTAMP;
 use crate::models::{Cloze, ClozeId, ClozeSegment, MeaningId};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -41,6 +43,10 @@ pub struct ClozeDto {
     pub id: Uuid,
     pub segments: Vec<ClozeSegmentDto>,
     pub meaning_id: Uuid,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl From<&Cloze> for ClozeDto {
@@ -50,6 +56,8 @@ impl From<&Cloze> for ClozeDto {
             id: cloze.id.into(),
             segments: cloze.segments.iter().map(ClozeSegmentDto::from).collect(),
             meaning_id: cloze.meaning_id.into(),
+            created_at: Some(cloze.created_at),
+            updated_at: Some(cloze.updated_at),
         }
     }
 }
@@ -57,10 +65,13 @@ impl From<&Cloze> for ClozeDto {
 impl From<ClozeDto> for Cloze {
     fn from(dto: ClozeDto) -> Self {
         tracing::trace!(cloze_id = %dto.id, meaning_id = %dto.meaning_id, "ClozeDto -> Cloze");
+        let created_at = dto.created_at.unwrap_or(LEGACY_TIMESTAMP);
         Cloze {
             id: ClozeId(dto.id),
             meaning_id: MeaningId(dto.meaning_id),
             segments: dto.segments.into_iter().map(Into::into).collect(),
+            created_at,
+            updated_at: dto.updated_at.unwrap_or(created_at),
         }
     }
 }
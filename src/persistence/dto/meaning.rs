@@ -1,6 +1,8 @@
 //! Meaning DTO for serialization.
 
+use super::LEGACY_TIMESTAMP;
 use crate::models::{CefrLevel, Meaning, TagId, WordId};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -112,6 +114,10 @@ pub struct MeaningDto {
     pub word_id: Uuid,
     pub tag_ids: Vec<Uuid>,
     pub cloze_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl From<&Meaning> for MeaningDto {
@@ -124,12 +130,15 @@ impl From<&Meaning> for MeaningDto {
             word_id: meaning.word_id.into(),
             tag_ids: meaning.tag_ids.iter().map(|id| (*id).into()).collect(),
             cloze_ids: Vec::new(),
+            created_at: Some(meaning.created_at),
+            updated_at: Some(meaning.updated_at),
         }
     }
 }
 
 impl From<MeaningDto> for Meaning {
     fn from(dto: MeaningDto) -> Self {
+        let created_at = dto.created_at.unwrap_or(LEGACY_TIMESTAMP);
         Meaning {
             id: crate::models::MeaningId(dto.id),
             definition: dto.definition,
@@ -137,6 +146,8 @@ impl From<MeaningDto> for Meaning {
             cefr_level: dto.cefr_level.map(|l| l.into()),
             word_id: WordId(dto.word_id),
             tag_ids: dto.tag_ids.into_iter().map(TagId).collect(),
+            created_at,
+            updated_at: dto.updated_at.unwrap_or(created_at),
         }
     }
 }
@@ -5,11 +5,25 @@
 pub use self::{
     cloze::{ClozeDto, ClozeSegmentDto},
     meaning::{MeaningDto, PartOfSpeechDto},
+    queue::{QueueItemDto, QueueItemStatusDto},
+    review::{ReviewAnswerDto, ReviewLogDto},
+    schedule::CardScheduleDto,
     tag::TagDto,
+    trash::TrashEntryDto,
     word::WordDto,
 };
 
+use chrono::{DateTime, Utc};
+
+/// Timestamp given to records saved before timestamps were tracked, so they
+/// sort as oldest and never match recent date filters.
+pub const LEGACY_TIMESTAMP: DateTime<Utc> = DateTime::<Utc>::UNIX_EPOCH;
+
 pub mod cloze;
 pub mod meaning;
+pub mod queue;
+pub mod review;
+pub mod schedule;
 pub mod tag;
+pub mod trash;
 pub mod word;
@@ -0,0 +1,79 @@
+//! Queue item DTO for serialization.
+
+use crate::models::{Cloze, MeaningId, WordId};
+use crate::persistence::ClozeDto;
+use crate::registry::{QueueItem, QueueItemStatus};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Queue item status DTO for serialization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueItemStatusDto {
+    Pending,
+    Processing,
+    Review,
+    Completed,
+    Failed(String),
+}
+
+impl From<&QueueItemStatus> for QueueItemStatusDto {
+    fn from(status: &QueueItemStatus) -> Self {
+        match status {
+            QueueItemStatus::Pending => QueueItemStatusDto::Pending,
+            QueueItemStatus::Processing => QueueItemStatusDto::Processing,
+            QueueItemStatus::Review => QueueItemStatusDto::Review,
+            QueueItemStatus::Completed => QueueItemStatusDto::Completed,
+            QueueItemStatus::Failed(e) => QueueItemStatusDto::Failed(e.clone()),
+        }
+    }
+}
+
+impl From<QueueItemStatusDto> for QueueItemStatus {
+    fn from(dto: QueueItemStatusDto) -> Self {
+        match dto {
+            QueueItemStatusDto::Pending => QueueItemStatus::Pending,
+            QueueItemStatusDto::Processing => QueueItemStatus::Processing,
+            QueueItemStatusDto::Review => QueueItemStatus::Review,
+            QueueItemStatusDto::Completed => QueueItemStatus::Completed,
+            QueueItemStatusDto::Failed(e) => QueueItemStatus::Failed(e),
+        }
+    }
+}
+
+/// Queue item data (matches QueueItem structure).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueItemDto {
+    pub id: Uuid,
+    pub meaning_id: Uuid,
+    pub status: QueueItemStatusDto,
+    pub selected: bool,
+    #[serde(default)]
+    pub candidates: Vec<ClozeDto>,
+}
+
+impl From<&QueueItem> for QueueItemDto {
+    fn from(item: &QueueItem) -> Self {
+        tracing::trace!(item_id = %item.id, "QueueItem -> QueueItemDto");
+        QueueItemDto {
+            id: item.id.into(),
+            meaning_id: item.meaning_id.into(),
+            status: QueueItemStatusDto::from(&item.status),
+            selected: item.selected,
+            candidates: item.candidates.iter().map(ClozeDto::from).collect(),
+        }
+    }
+}
+
+impl From<QueueItemDto> for QueueItem {
+    fn from(dto: QueueItemDto) -> Self {
+        tracing::trace!(item_id = %dto.id, meaning_id = %dto.meaning_id, "QueueItemDto -> QueueItem");
+        QueueItem {
+            id: WordId(dto.id),
+            meaning_id: MeaningId(dto.meaning_id),
+            status: dto.status.into(),
+            selected: dto.selected,
+            candidates: dto.candidates.into_iter().map(Cloze::from).collect(),
+        }
+    }
+}
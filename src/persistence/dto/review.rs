@@ -0,0 +1,68 @@
+//! Review log DTO for serialization.
+
+use crate::models::{ClozeId, ReviewAnswer, ReviewId, ReviewLog};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Per-blank answer data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewAnswerDto {
+    pub given: String,
+    pub correct: bool,
+}
+
+impl From<&ReviewAnswer> for ReviewAnswerDto {
+    fn from(answer: &ReviewAnswer) -> Self {
+        ReviewAnswerDto {
+            given: answer.given.clone(),
+            correct: answer.correct,
+        }
+    }
+}
+
+impl From<ReviewAnswerDto> for ReviewAnswer {
+    fn from(dto: ReviewAnswerDto) -> Self {
+        ReviewAnswer {
+            given: dto.given,
+            correct: dto.correct,
+        }
+    }
+}
+
+/// Review log data (matches ReviewLog model structure).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewLogDto {
+    pub id: Uuid,
+    pub cloze_id: Uuid,
+    pub reviewed_at: DateTime<Utc>,
+    pub answers: Vec<ReviewAnswerDto>,
+    pub duration_ms: u64,
+}
+
+impl From<&ReviewLog> for ReviewLogDto {
+    fn from(log: &ReviewLog) -> Self {
+        tracing::trace!(review_id = %log.id, "ReviewLog -> ReviewLogDto");
+        ReviewLogDto {
+            id: log.id.into(),
+            cloze_id: log.cloze_id.into(),
+            reviewed_at: log.reviewed_at,
+            answers: log.answers.iter().map(ReviewAnswerDto::from).collect(),
+            duration_ms: log.duration.as_millis() as u64,
+        }
+    }
+}
+
+impl From<ReviewLogDto> for ReviewLog {
+    fn from(dto: ReviewLogDto) -> Self {
+        tracing::trace!(review_id = %dto.id, cloze_id = %dto.cloze_id, "ReviewLogDto -> ReviewLog");
+        ReviewLog {
+            id: ReviewId(dto.id),
+            cloze_id: ClozeId(dto.cloze_id),
+            reviewed_at: dto.reviewed_at,
+            answers: dto.answers.into_iter().map(Into::into).collect(),
+            duration: Duration::from_millis(dto.duration_ms),
+        }
+    }
+}
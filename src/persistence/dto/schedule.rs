@@ -0,0 +1,47 @@
+//! Card schedule DTO for serialization.
+
+use crate::models::{CardSchedule, ClozeId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Card schedule data (matches CardSchedule model structure).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardScheduleDto {
+    pub cloze_id: Uuid,
+    pub due: DateTime<Utc>,
+    pub interval_days: u32,
+    pub ease: f32,
+    pub repetitions: u32,
+    pub lapses: u32,
+    #[serde(default)]
+    pub last_reviewed: Option<DateTime<Utc>>,
+}
+
+impl From<&CardSchedule> for CardScheduleDto {
+    fn from(schedule: &CardSchedule) -> Self {
+        CardScheduleDto {
+            cloze_id: schedule.cloze_id.into(),
+            due: schedule.due,
+            interval_days: schedule.interval_days,
+            ease: schedule.ease,
+            repetitions: schedule.repetitions,
+            lapses: schedule.lapses,
+            last_reviewed: schedule.last_reviewed,
+        }
+    }
+}
+
+impl From<CardScheduleDto> for CardSchedule {
+    fn from(dto: CardScheduleDto) -> Self {
+        CardSchedule {
+            cloze_id: ClozeId(dto.cloze_id),
+            due: dto.due,
+            interval_days: dto.interval_days,
+            ease: dto.ease,
+            repetitions: dto.repetitions,
+            lapses: dto.lapses,
+            last_reviewed: dto.last_reviewed,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Trash entry DTO for serialization.
+
+use super::{CardScheduleDto, ClozeDto, MeaningDto, ReviewLogDto, TagDto, WordDto};
+use crate::models::{MeaningId, TagId, TrashEntry, TrashId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Trash entry data, embedding the DTOs of the trashed entities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntryDto {
+    pub id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+    pub words: Vec<WordDto>,
+    pub meanings: Vec<MeaningDto>,
+    pub tags: Vec<TagDto>,
+    pub clozes: Vec<ClozeDto>,
+    pub schedules: Vec<CardScheduleDto>,
+    pub reviews: Vec<ReviewLogDto>,
+    /// (tag, meaning) pairs
+    pub tagged: Vec<(Uuid, Uuid)>,
+}
+
+impl From<&TrashEntry> for TrashEntryDto {
+    fn from(entry: &TrashEntry) -> Self {
+        tracing::trace!(trash_id = %entry.id, "TrashEntry -> TrashEntryDto");
+        TrashEntryDto {
+            id: entry.id.into(),
+            deleted_at: entry.deleted_at,
+            words: entry.words.iter().map(WordDto::from).collect(),
+            meanings: entry.meanings.iter().map(MeaningDto::from).collect(),
+            tags: entry.tags.iter().map(TagDto::from).collect(),
+            clozes: entry.clozes.iter().map(ClozeDto::from).collect(),
+            schedules: entry.schedules.iter().map(CardScheduleDto::from).collect(),
+            reviews: entry.reviews.iter().map(ReviewLogDto::from).collect(),
+            tagged: entry
+                .tagged
+                .iter()
+                .map(|(tag_id, meaning_id)| ((*tag_id).into(), (*meaning_id).into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<TrashEntryDto> for TrashEntry {
+    fn from(dto: TrashEntryDto) -> Self {
+        tracing::trace!(trash_id = %dto.id, "TrashEntryDto -> TrashEntry");
+        TrashEntry {
+            id: TrashId(dto.id),
+            deleted_at: dto.deleted_at,
+            words: dto.words.into_iter().map(Into::into).collect(),
+            meanings: dto.meanings.into_iter().map(Into::into).collect(),
+            tags: dto.tags.into_iter().map(Into::into).collect(),
+            clozes: dto.clozes.into_iter().map(Into::into).collect(),
+            schedules: dto.schedules.into_iter().map(Into::into).collect(),
+            reviews: dto.reviews.into_iter().map(Into::into).collect(),
+            tagged: dto
+                .tagged
+                .into_iter()
+                .map(|(tag_id, meaning_id)| (TagId(tag_id), MeaningId(meaning_id)))
+                .collect(),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Word DTO for serialization.
 
+use super::LEGACY_TIMESTAMP;
 use crate::models::{MeaningId, Word, WordId};
+use chrono::{DateTime, Utc};
 use langtag::LangTagBuf;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -12,6 +14,10 @@ pub struct WordDto {
     pub content: String,
     pub meaning_ids: Vec<Uuid>,
     pub language: Option<LangTagBuf>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl From<&Word> for WordDto {
@@ -21,17 +27,22 @@ impl From<&Word> for WordDto {
             content: word.content.clone(),
             meaning_ids: word.meaning_ids.iter().map(|id| (*id).into()).collect(),
             language: word.language.clone(),
+            created_at: Some(word.created_at),
+            updated_at: Some(word.updated_at),
         }
     }
 }
 
 impl From<WordDto> for Word {
     fn from(dto: WordDto) -> Self {
+        let created_at = dto.created_at.unwrap_or(LEGACY_TIMESTAMP);
         Word {
             id: WordId(dto.id),
             content: dto.content,
             meaning_ids: dto.meaning_ids.into_iter().map(MeaningId).collect(),
             language: dto.language,
+            created_at,
+            updated_at: dto.updated_at.unwrap_or(created_at),
         }
     }
 }
@@ -8,5 +8,8 @@ pub mod dto;
 pub type Result<T> = std::result::Result<T, DbError>;
 
 pub use crate::models::ClozeSegment;
-pub use db::{Db, DbError};
-pub use dto::{ClozeDto, ClozeSegmentDto, MeaningDto, PartOfSpeechDto, TagDto, WordDto};
+pub use db::{Db, DbError, Snapshot, SnapshotReason, SnapshotStore, WriteBatch};
+pub use dto::{
+    CardScheduleDto, ClozeDto, ClozeSegmentDto, MeaningDto, PartOfSpeechDto, QueueItemDto,
+    QueueItemStatusDto, ReviewAnswerDto, ReviewLogDto, TagDto, TrashEntryDto, WordDto,
+};
@@ -1,11 +1,13 @@
-use crate::models::{PartOfSpeech, TagId};
+use crate::models::{CefrLevel, PartOfSpeech, TagId};
 use crate::query::parse::TagResolver;
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Utc};
 use strum::Display;
 
 /// Token for parsing phase (before tag resolution)
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
-    /// Tag name to be resolved (include)
+    /// Tag name or path to be resolved (include); a trailing `/*` also
+    /// matches the tags nested under it
     IncludeTagName(String),
     /// Tag name to be resolved (exclude)
     ExcludeTagName(String),
@@ -21,10 +23,36 @@ pub enum Token {
     IncludeStatus(StatusFilter),
     /// Status filter (exclude)
     ExcludeStatus(StatusFilter),
+    /// Creation date filter
+    Added(DateRange),
+    /// Modification date filter
+    Modified(DateRange),
+    /// Word content filter (`word:`)
+    WordText(String),
+    /// Word content filter tolerating typos (`word:term~`)
+    WordFuzzy(String),
+    /// Definition filter (`def:`)
+    DefinitionText(String),
+    /// Definition filter tolerating typos (`def:term~`)
+    DefinitionFuzzy(String),
+    /// CEFR level filter (`cefr:`)
+    Cefr(CefrRange),
+    /// Language filter (`lang:`)
+    Language(String),
+    /// Cloze count filter (`clozes:`)
+    ClozeCount(CountRange),
+    /// Meaning count filter (`meanings:`)
+    MeaningCount(CountRange),
     /// Text search
     Text(String),
-    /// OR operator
+    /// Text search tolerating typos (`term~`)
+    Fuzzy(String),
+    /// OR operator (`|` or `OR`)
     Or,
+    /// Explicit AND operator (`AND`), same as a space
+    And,
+    /// Negates the following term or group (`NOT` or `-(`)
+    Not,
     /// Left parenthesis
     LeftParen,
     /// Right parenthesis
@@ -36,6 +64,8 @@ pub enum Token {
 pub enum Condition {
     /// Text search (matches word content or definition)
     Text(String),
+    /// Text search that also accepts words a few edits away
+    Fuzzy(String),
     /// Has specific tag
     HasTag(TagId),
     /// Does not have specific tag
@@ -52,18 +82,53 @@ pub enum Condition {
     HasStatus(StatusFilter),
     /// Does not have specific status
     NotHasStatus(StatusFilter),
+    /// Word was created within range
+    Added(DateRange),
+    /// Word, one of its meanings or one of their clozes was changed within range
+    Modified(DateRange),
+    /// Word content contains text
+    WordText(String),
+    /// Word content is within a few edits of text
+    WordFuzzy(String),
+    /// One of the word's definitions contains text
+    DefinitionText(String),
+    /// One of the word's definitions has a word within a few edits of text
+    DefinitionFuzzy(String),
+    /// One of the word's meanings has a CEFR level within range
+    Cefr(CefrRange),
+    /// Word language is the tag or one of its subtags (`de` matches `de-AT`)
+    Language(String),
+    /// Number of clozes across the word's meanings is within range
+    ClozeCount(CountRange),
+    /// Number of the word's meanings is within range
+    MeaningCount(CountRange),
     /// All conditions must match (AND)
     All(Vec<Condition>),
     /// Any condition must match (OR)
     Any(Vec<Condition>),
+    /// Condition must not match
+    Not(Box<Condition>),
 }
 
 impl Condition {
     /// Resolves all TagName conditions to TagId conditions
     pub fn resolve_tags(self, resolver: &mut TagResolver) -> Option<Condition> {
         match self {
-            Condition::HasTagName(name) => resolver.resolve(&name).map(Condition::HasTag),
-            Condition::NotHasTagName(name) => resolver.resolve(&name).map(Condition::NotHasTag),
+            Condition::HasTagName(name) => {
+                let ids = resolver.resolve_filter(&name)?;
+                Some(match ids[..] {
+                    [id] => Condition::HasTag(id),
+                    // A subtree matches if any of its tags does
+                    _ => Condition::Any(ids.into_iter().map(Condition::HasTag).collect()),
+                })
+            }
+            Condition::NotHasTagName(name) => {
+                let ids = resolver.resolve_filter(&name)?;
+                Some(match ids[..] {
+                    [id] => Condition::NotHasTag(id),
+                    _ => Condition::All(ids.into_iter().map(Condition::NotHasTag).collect()),
+                })
+            }
             Condition::All(conditions) => {
                 let resolved: Vec<_> = conditions
                     .into_iter()
@@ -86,30 +151,45 @@ impl Condition {
                     Some(Condition::Any(resolved))
                 }
             }
+            Condition::Not(condition) => condition
+                .resolve_tags(resolver)
+                .map(|c| Condition::Not(Box::new(c))),
             other => Some(other),
         }
     }
 
     /// Returns true if this condition has any text search components
+    /// (negated text does not count)
     pub fn has_text_search(&self) -> bool {
         match self {
-            Condition::Text(_) => true,
+            Condition::Text(_)
+            | Condition::Fuzzy(_)
+            | Condition::WordText(_)
+            | Condition::WordFuzzy(_)
+            | Condition::DefinitionText(_)
+            | Condition::DefinitionFuzzy(_) => true,
             Condition::All(conds) => conds.iter().any(|c| c.has_text_search()),
             Condition::Any(conds) => conds.iter().any(|c| c.has_text_search()),
             _ => false,
         }
     }
 
-    /// Extracts all text queries from this condition
-    pub fn text_queries(&self) -> Vec<&str> {
+    /// Extracts all text queries from this condition, each paired with
+    /// whether it is fuzzy
+    pub fn text_queries(&self) -> Vec<(&str, bool)> {
         let mut queries = Vec::new();
         self.collect_text_queries(&mut queries);
         queries
     }
 
-    fn collect_text_queries<'a>(&'a self, queries: &mut Vec<&'a str>) {
+    fn collect_text_queries<'a>(&'a self, queries: &mut Vec<(&'a str, bool)>) {
         match self {
-            Condition::Text(s) => queries.push(s.as_str()),
+            Condition::Text(s) | Condition::WordText(s) | Condition::DefinitionText(s) => {
+                queries.push((s.as_str(), false))
+            }
+            Condition::Fuzzy(s) | Condition::WordFuzzy(s) | Condition::DefinitionFuzzy(s) => {
+                queries.push((s.as_str(), true))
+            }
             Condition::All(conds) | Condition::Any(conds) => {
                 for cond in conds {
                     cond.collect_text_queries(queries);
@@ -140,6 +220,168 @@ impl StatusFilter {
     }
 }
 
+/// Time range for `added:` and `modified:` filters (start inclusive, end
+/// exclusive, either side open).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start.is_none_or(|start| time >= start) && self.end.is_none_or(|end| time < end)
+    }
+
+    /// Parses a range relative to `now`, with days in `now`'s timezone:
+    /// `today`, `yesterday`, `week` (since Monday), `month`, `7d`/`2w`
+    /// (last N days or weeks), `2026-10-01` (that day) and inclusive day
+    /// ranges `2026-10-01..2026-10-17`, `2026-10-01..`, `..2026-10-17`.
+    pub fn parse<Tz: TimeZone>(s: &str, now: DateTime<Tz>) -> Option<Self> {
+        let tz = now.timezone();
+        let today = now.date_naive();
+        let day_start = |date: NaiveDate| {
+            tz.from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc))
+        };
+        let days = |date: NaiveDate, first: NaiveDate| -> Option<Self> {
+            Some(Self {
+                start: Some(day_start(first)?),
+                end: Some(day_start(date.checked_add_days(Days::new(1))?)?),
+            })
+        };
+        let since = |start: DateTime<Utc>| Self {
+            start: Some(start),
+            end: None,
+        };
+
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "today" => return days(today, today),
+            "yesterday" => {
+                let yesterday = today.pred_opt()?;
+                return days(yesterday, yesterday);
+            }
+            "week" => {
+                let monday = today
+                    .checked_sub_days(Days::new(today.weekday().num_days_from_monday().into()))?;
+                return Some(since(day_start(monday)?));
+            }
+            "month" => return Some(since(day_start(today.with_day(1)?)?)),
+            _ => {}
+        }
+
+        if let Some((first, last)) = s.split_once("..") {
+            let parse_day = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok();
+            let start = match first {
+                "" => None,
+                d => Some(day_start(parse_day(d)?)?),
+            };
+            let end = match last {
+                "" => None,
+                d => Some(day_start(parse_day(d)?.checked_add_days(Days::new(1))?)?),
+            };
+            return (start.is_some() || end.is_some()).then_some(Self { start, end });
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
+            return days(date, date);
+        }
+
+        let unit_days = match s.chars().last()? {
+            'd' => 1,
+            'w' => 7,
+            _ => return None,
+        };
+        // Unsigned, so `-5d` cannot reach into the future
+        let count: i64 = s[..s.len() - 1].parse::<u64>().ok()?.try_into().ok()?;
+        let span = chrono::Duration::try_days(count.checked_mul(unit_days)?)?;
+        let start = now.with_timezone(&Utc).checked_sub_signed(span)?;
+        Some(since(start))
+    }
+}
+
+/// Inclusive count range for `clozes:` and `meanings:` filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountRange {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl CountRange {
+    pub fn contains(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+
+    /// Parses an exact count `2`, comparisons `>2`, `>=2`, `<2`, `<=2` and
+    /// inclusive ranges `1..3`, `2..`, `..3`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let count = |n: &str| n.parse::<usize>().ok();
+        let range = |min, max| Some(Self { min, max });
+
+        if let Some(n) = s.strip_prefix(">=") {
+            return range(count(n)?, None);
+        }
+        if let Some(n) = s.strip_prefix("<=") {
+            return range(0, Some(count(n)?));
+        }
+        if let Some(n) = s.strip_prefix('>') {
+            return range(count(n)?.checked_add(1)?, None);
+        }
+        if let Some(n) = s.strip_prefix('<') {
+            return range(0, Some(count(n)?.checked_sub(1)?));
+        }
+        if let Some((first, last)) = s.split_once("..") {
+            if first.is_empty() && last.is_empty() {
+                return None;
+            }
+            let min = if first.is_empty() { 0 } else { count(first)? };
+            let max = if last.is_empty() {
+                None
+            } else {
+                Some(count(last)?)
+            };
+            return (max.is_none_or(|max| max >= min)).then_some(Self { min, max });
+        }
+        let n = count(s)?;
+        range(n, Some(n))
+    }
+}
+
+/// Inclusive CEFR level range for `cefr:` filters, either side open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CefrRange {
+    pub min: Option<CefrLevel>,
+    pub max: Option<CefrLevel>,
+}
+
+impl CefrRange {
+    pub fn contains(&self, level: CefrLevel) -> bool {
+        self.min.is_none_or(|min| level >= min) && self.max.is_none_or(|max| level <= max)
+    }
+
+    /// Parses a level `B2` or an inclusive range `B1..C1`, `B1..`, `..B1`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let level = |l: &str| match l {
+            "" => Some(None),
+            l => CefrLevel::try_from_str(l).map(Some),
+        };
+        let Some((first, last)) = s.split_once("..") else {
+            let level = CefrLevel::try_from_str(s)?;
+            return Some(Self {
+                min: Some(level),
+                max: Some(level),
+            });
+        };
+        let (min, max) = (level(first)?, level(last)?);
+        if min.is_none() && max.is_none() || min.zip(max).is_some_and(|(min, max)| min > max) {
+            return None;
+        }
+        Some(Self { min, max })
+    }
+}
+
 /// Query structure
 #[derive(Debug, Clone)]
 pub struct Query {
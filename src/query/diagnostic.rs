@@ -0,0 +1,201 @@
+use crate::query::ast::Token;
+use crate::query::parse::{TagResolver, parse_pos};
+use std::fmt;
+use std::ops::Range;
+
+/// A problem found in a query string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// Character range of the offending part of the query
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// `#name`, `tag:path` or their exclusions naming a tag that does not exist
+    UnknownTag(String),
+    /// A part of speech in `:pos` that is not recognized
+    UnknownPos(String),
+    /// `(` without a matching `)`
+    UnclosedParen,
+    /// `)` without a matching `(`
+    UnmatchedParen,
+    /// `()` or a group holding only operators
+    EmptyGroup,
+}
+
+impl Diagnostic {
+    /// Translation key for the message
+    pub fn key(&self) -> &'static str {
+        match self.kind {
+            DiagnosticKind::UnknownTag(_) => "query-error-unknown-tag",
+            DiagnosticKind::UnknownPos(_) => "query-error-unknown-pos",
+            DiagnosticKind::UnclosedParen => "query-error-unclosed-paren",
+            DiagnosticKind::UnmatchedParen => "query-error-unmatched-paren",
+            DiagnosticKind::EmptyGroup => "query-error-empty-group",
+        }
+    }
+
+    /// Arguments for the translated message
+    pub fn args(&self) -> Vec<&str> {
+        match &self.kind {
+            DiagnosticKind::UnknownTag(name) | DiagnosticKind::UnknownPos(name) => vec![name],
+            _ => vec![],
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            DiagnosticKind::UnknownTag(name) => write!(f, "Unknown tag \"{}\"", name),
+            DiagnosticKind::UnknownPos(pos) => write!(f, "Unknown part of speech \"{}\"", pos),
+            DiagnosticKind::UnclosedParen => write!(f, "Unclosed parenthesis"),
+            DiagnosticKind::UnmatchedParen => write!(f, "Unmatched closing parenthesis"),
+            DiagnosticKind::EmptyGroup => write!(f, "Empty group"),
+        }
+    }
+}
+
+/// Checks tokenized input for problems, ordered by position
+pub(crate) fn diagnose(
+    input: &str,
+    tokens: &[(Token, Range<usize>)],
+    resolver: &mut TagResolver,
+) -> Vec<Diagnostic> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut diagnostics = Vec::new();
+    // Open parens with whether their group has an operand yet
+    let mut groups: Vec<(usize, bool)> = Vec::new();
+
+    for (token, span) in tokens {
+        let is_operand = !matches!(
+            token,
+            Token::LeftParen | Token::RightParen | Token::Or | Token::And | Token::Not
+        );
+        if is_operand && let Some(group) = groups.last_mut() {
+            group.1 = true;
+        }
+
+        match token {
+            Token::LeftParen => groups.push((span.start, false)),
+            Token::RightParen => match groups.pop() {
+                Some((start, has_operand)) => {
+                    if !has_operand {
+                        diagnostics.push(Diagnostic {
+                            kind: DiagnosticKind::EmptyGroup,
+                            span: start..span.end,
+                        });
+                    }
+                    // The group itself is an operand of the enclosing one
+                    if let Some(outer) = groups.last_mut() {
+                        outer.1 = true;
+                    }
+                }
+                None => diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::UnmatchedParen,
+                    span: span.clone(),
+                }),
+            },
+            Token::IncludeTagName(name) | Token::ExcludeTagName(name)
+                if resolver.resolve_filter(name).is_none() =>
+            {
+                diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::UnknownTag(name.clone()),
+                    span: span.clone(),
+                });
+            }
+            Token::IncludePos(_) | Token::ExcludePos(_) | Token::Text(_) => {
+                diagnostics.extend(unknown_pos(&chars, span.clone()));
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics.extend(groups.into_iter().map(|(start, _)| Diagnostic {
+        kind: DiagnosticKind::UnclosedParen,
+        span: start..start + 1,
+    }));
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+/// Reports the unrecognized entries of a `:pos` or `-:pos` filter
+fn unknown_pos(chars: &[char], span: Range<usize>) -> Vec<Diagnostic> {
+    let raw = &chars[span.clone()];
+    let prefix = match raw {
+        [':', ..] => 1,
+        ['-', ':', ..] => 2,
+        _ => return Vec::new(),
+    };
+
+    let mut diagnostics = Vec::new();
+    let mut start = span.start + prefix;
+    for part in raw[prefix..].split(|c| *c == ',') {
+        let name: String = part.iter().collect();
+        if !name.trim().is_empty() && parse_pos(name.trim()).is_none() {
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::UnknownPos(name),
+                span: start..start + part.len(),
+            });
+        }
+        start += part.len() + 1;
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Tag;
+    use crate::query::parse::tokenize_spanned;
+    use crate::registry::TagRegistry;
+    use test_case::test_case;
+
+    fn check(input: &str) -> Vec<Diagnostic> {
+        let mut registry = TagRegistry::new();
+        registry.add(Tag::builder().name("travel".to_string()).build());
+        let mut resolver = TagResolver::new(&registry);
+        diagnose(input, &tokenize_spanned(input), &mut resolver)
+    }
+
+    #[test_case("hello #travel :noun,verb (a | b)"; "valid query")]
+    #[test_case("\":foo\""; "quoted colon text")]
+    #[test_case("((a) b)"; "nested groups")]
+    fn test_valid_query_has_no_diagnostics(input: &str) {
+        assert!(check(input).is_empty());
+    }
+
+    #[test_case("#food", DiagnosticKind::UnknownTag("food".into()), 0..5; "unknown tag")]
+    #[test_case("a -#food", DiagnosticKind::UnknownTag("food".into()), 2..8; "unknown excluded tag")]
+    #[test_case(":noun,vrb", DiagnosticKind::UnknownPos("vrb".into()), 6..9; "unknown pos")]
+    #[test_case("-:xyz", DiagnosticKind::UnknownPos("xyz".into()), 2..5; "unknown excluded pos")]
+    #[test_case("(a b", DiagnosticKind::UnclosedParen, 0..1; "unclosed paren")]
+    #[test_case("a b)", DiagnosticKind::UnmatchedParen, 3..4; "unmatched paren")]
+    #[test_case("a ()", DiagnosticKind::EmptyGroup, 2..4; "empty group")]
+    #[test_case("( | )", DiagnosticKind::EmptyGroup, 0..5; "group of operators")]
+    fn test_single_diagnostic(input: &str, kind: DiagnosticKind, span: Range<usize>) {
+        assert_eq!(check(input), vec![Diagnostic { kind, span }]);
+    }
+
+    #[test]
+    fn test_spans_count_characters() {
+        let diagnostics = check("café #boîte");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span, 5..11);
+    }
+
+    #[test]
+    fn test_diagnostics_are_ordered() {
+        let diagnostics = check("(#nope ) )");
+        let kinds: Vec<_> = diagnostics.into_iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::UnknownTag("nope".into()),
+                DiagnosticKind::UnmatchedParen,
+            ]
+        );
+    }
+}
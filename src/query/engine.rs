@@ -1,7 +1,10 @@
-use crate::models::types::WordId;
+use crate::models::types::{MeaningId, WordId};
 use crate::query::ast::{Condition, Query, SortType, StatusFilter};
+use crate::query::normalize::{fold, fuzzy_distance};
 use crate::registry::{ClozeRegistry, MeaningRegistry, QueueRegistry, WordRegistry};
-use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 
 /// Query engine for executing search queries
 pub struct QueryEngine<'a> {
@@ -9,6 +12,15 @@ pub struct QueryEngine<'a> {
     meaning_registry: &'a MeaningRegistry,
     cloze_registry: &'a ClozeRegistry,
     queue_registry: &'a QueueRegistry,
+    /// Folded text, built on first use so each word and definition is
+    /// folded once per engine
+    folded: OnceCell<FoldedText>,
+}
+
+/// Folded word contents and definitions
+struct FoldedText {
+    words: HashMap<WordId, String>,
+    definitions: HashMap<MeaningId, String>,
 }
 
 impl<'a> QueryEngine<'a> {
@@ -23,9 +35,36 @@ impl<'a> QueryEngine<'a> {
             meaning_registry,
             cloze_registry,
             queue_registry,
+            folded: OnceCell::new(),
         }
     }
 
+    fn folded(&self) -> &FoldedText {
+        self.folded.get_or_init(|| FoldedText {
+            words: self
+                .word_registry
+                .iter()
+                .map(|(id, w)| (*id, fold(&w.content)))
+                .collect(),
+            definitions: self
+                .meaning_registry
+                .iter()
+                .map(|(id, m)| (*id, fold(&m.definition)))
+                .collect(),
+        })
+    }
+
+    fn folded_word(&self, id: WordId) -> &str {
+        self.folded().words.get(&id).map_or("", String::as_str)
+    }
+
+    fn folded_definition(&self, id: MeaningId) -> &str {
+        self.folded()
+            .definitions
+            .get(&id)
+            .map_or("", String::as_str)
+    }
+
     /// Execute a query and return matching word IDs with scores
     pub fn execute(&self, query: &Query) -> Vec<(WordId, i32)> {
         // Apply filter conditions to get candidates
@@ -41,7 +80,8 @@ impl<'a> QueryEngine<'a> {
     /// Apply a condition to filter words
     fn apply_condition(&self, condition: &Condition) -> HashSet<WordId> {
         match condition {
-            Condition::Text(query) => self.search_text(query),
+            Condition::Text(query) => self.search_text(query, false),
+            Condition::Fuzzy(query) => self.search_text(query, true),
             Condition::HasTag(tag_id) => self
                 .meaning_registry
                 .iter_by_tag(*tag_id)
@@ -78,6 +118,62 @@ impl<'a> QueryEngine<'a> {
                 let status_words = self.filter_by_status(*status);
                 all_words.difference(&status_words).copied().collect()
             }
+            Condition::Added(range) => self
+                .word_registry
+                .iter()
+                .filter(|(_, w)| range.contains(w.created_at))
+                .map(|(id, _)| *id)
+                .collect(),
+            Condition::Modified(range) => self
+                .word_registry
+                .iter()
+                .filter(|(id, _)| self.last_modified(**id).is_some_and(|t| range.contains(t)))
+                .map(|(id, _)| *id)
+                .collect(),
+            Condition::WordText(text) => self.search_words(text, false),
+            Condition::WordFuzzy(text) => self.search_words(text, true),
+            Condition::DefinitionText(text) => self.search_definitions(text, false),
+            Condition::DefinitionFuzzy(text) => self.search_definitions(text, true),
+            Condition::Cefr(range) => self
+                .meaning_registry
+                .iter()
+                .filter(|(_, m)| m.cefr_level.is_some_and(|level| range.contains(level)))
+                .map(|(_, m)| m.word_id)
+                .collect(),
+            Condition::Language(tag) => self
+                .word_registry
+                .iter()
+                .filter(|(_, w)| {
+                    w.language
+                        .as_ref()
+                        .is_some_and(|language| language_matches(language.as_str(), tag))
+                })
+                .map(|(id, _)| *id)
+                .collect(),
+            Condition::ClozeCount(range) => self
+                .word_registry
+                .iter()
+                .filter(|(_, w)| {
+                    let clozes = w
+                        .meaning_ids
+                        .iter()
+                        .map(|mid| self.cloze_registry.count_by_meaning(*mid))
+                        .sum();
+                    range.contains(clozes)
+                })
+                .map(|(id, _)| *id)
+                .collect(),
+            Condition::MeaningCount(range) => self
+                .word_registry
+                .iter()
+                .filter(|(_, w)| range.contains(w.meaning_ids.len()))
+                .map(|(id, _)| *id)
+                .collect(),
+            Condition::Not(condition) => {
+                let all_words = self.all_word_ids();
+                let matched = self.apply_condition(condition);
+                all_words.difference(&matched).copied().collect()
+            }
             Condition::All(conditions) => {
                 // Intersection (AND)
                 if conditions.is_empty() {
@@ -120,21 +216,43 @@ impl<'a> QueryEngine<'a> {
         self.word_registry.iter().map(|(id, _)| *id).collect()
     }
 
-    /// Search for words matching text query
-    fn search_text(&self, query: &str) -> HashSet<WordId> {
-        let query_lower = query.to_lowercase();
+    /// Latest `updated_at` of a word, its meanings and their clozes
+    fn last_modified(&self, word_id: WordId) -> Option<DateTime<Utc>> {
+        let word = self.word_registry.get(word_id)?;
+        let meanings = self
+            .meaning_registry
+            .iter_by_word(word_id)
+            .flat_map(|(id, m)| {
+                std::iter::once(m.updated_at).chain(
+                    self.cloze_registry
+                        .iter_by_meaning_id(*id)
+                        .map(|(_, c)| c.updated_at),
+                )
+            });
+        std::iter::once(word.updated_at).chain(meanings).max()
+    }
+
+    /// Creation time of a word, for date sorts
+    fn created_at(&self, word_id: WordId) -> Option<DateTime<Utc>> {
+        self.word_registry.get(word_id).map(|w| w.created_at)
+    }
+
+    /// Search for words matching text query, ignoring case, accents and
+    /// width; a fuzzy query also accepts words a few typos away
+    fn search_text(&self, query: &str, fuzzy: bool) -> HashSet<WordId> {
+        let query = fold(query);
         let mut results = HashSet::new();
 
-        for (word_id, word) in self.word_registry.iter() {
+        for (word_id, _) in self.word_registry.iter() {
             // Check word content
-            if word.content.to_lowercase().contains(&query_lower) {
+            if text_matches(self.folded_word(*word_id), &query, fuzzy) {
                 results.insert(*word_id);
                 continue;
             }
 
             // Check definitions
-            for (_, meaning) in self.meaning_registry.iter_by_word(*word_id) {
-                if meaning.definition.to_lowercase().contains(&query_lower) {
+            for (meaning_id, _) in self.meaning_registry.iter_by_word(*word_id) {
+                if text_matches(self.folded_definition(*meaning_id), &query, fuzzy) {
                     results.insert(*word_id);
                     break;
                 }
@@ -144,6 +262,26 @@ impl<'a> QueryEngine<'a> {
         results
     }
 
+    /// Words whose content matches `query` (`word:`)
+    fn search_words(&self, query: &str, fuzzy: bool) -> HashSet<WordId> {
+        let query = fold(query);
+        self.word_registry
+            .iter()
+            .filter(|(id, _)| text_matches(self.folded_word(**id), &query, fuzzy))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Words with a definition matching `query` (`def:`)
+    fn search_definitions(&self, query: &str, fuzzy: bool) -> HashSet<WordId> {
+        let query = fold(query);
+        self.meaning_registry
+            .iter()
+            .filter(|(id, _)| text_matches(self.folded_definition(**id), &query, fuzzy))
+            .map(|(_, m)| m.word_id)
+            .collect()
+    }
+
     /// Filter words by status
     fn filter_by_status(&self, status: StatusFilter) -> HashSet<WordId> {
         let mut result = HashSet::new();
@@ -205,7 +343,12 @@ impl<'a> QueryEngine<'a> {
         candidates: HashSet<WordId>,
         condition: &Condition,
     ) -> Vec<(WordId, i32)> {
-        let text_queries = condition.text_queries();
+        // Fold each query once rather than once per candidate
+        let text_queries: Vec<(String, bool)> = condition
+            .text_queries()
+            .into_iter()
+            .map(|(query, fuzzy)| (fold(query), fuzzy))
+            .collect();
 
         if text_queries.is_empty() {
             return candidates.into_iter().map(|id| (id, 0)).collect();
@@ -220,36 +363,40 @@ impl<'a> QueryEngine<'a> {
             .collect()
     }
 
-    /// Calculate score for a single word
-    fn calculate_word_score(&self, word_id: WordId, queries: &[&str]) -> i32 {
-        let word = match self.word_registry.get(word_id) {
-            Some(w) => w,
-            None => return 0,
-        };
+    /// Calculate score for a single word from already folded queries
+    fn calculate_word_score(&self, word_id: WordId, queries: &[(String, bool)]) -> i32 {
+        if !self.word_registry.exists(word_id) {
+            return 0;
+        }
 
-        let word_lower = word.content.to_lowercase();
+        let word_folded = self.folded_word(word_id);
         let mut max_score = 0;
 
-        for query in queries {
-            let query_lower = query.to_lowercase();
-
+        for (query_folded, fuzzy) in queries {
             // Exact match
-            if word_lower == query_lower {
+            if word_folded == query_folded {
                 max_score = max_score.max(100);
             }
             // Starts with
-            else if word_lower.starts_with(&query_lower) {
+            else if word_folded.starts_with(query_folded.as_str()) {
                 max_score = max_score.max(50);
             }
             // Contains
-            else if word_lower.contains(&query_lower) {
+            else if word_folded.contains(query_folded.as_str()) {
                 max_score = max_score.max(20);
             }
+            // Close misspelling, less per typo
+            else if *fuzzy && let Some(distance) = fuzzy_distance(word_folded, query_folded) {
+                max_score = max_score.max(20 - 5 * distance as i32);
+            }
 
             // Check definition matches
-            for (_, meaning) in self.meaning_registry.iter_by_word(word_id) {
-                if meaning.definition.to_lowercase().contains(&query_lower) {
+            for (meaning_id, _) in self.meaning_registry.iter_by_word(word_id) {
+                let definition = self.folded_definition(*meaning_id);
+                if definition.contains(query_folded.as_str()) {
                     max_score = max_score.max(10);
+                } else if *fuzzy && let Some(distance) = fuzzy_distance(definition, query_folded) {
+                    max_score = max_score.max(10 - 3 * distance as i32);
                 }
             }
         }
@@ -264,25 +411,13 @@ impl<'a> QueryEngine<'a> {
                 results.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
             }
             SortType::Newest => {
-                results.sort_by_key(|b| std::cmp::Reverse(b.0));
+                results.sort_by_key(|r| std::cmp::Reverse((self.created_at(r.0), r.0)));
             }
             SortType::Oldest => {
-                results.sort_by_key(|a| a.0);
+                results.sort_by_key(|r| (self.created_at(r.0), r.0));
             }
             SortType::AZ => {
-                results.sort_by(|a, b| {
-                    let word_a = self
-                        .word_registry
-                        .get(a.0)
-                        .map(|w| w.content.to_lowercase())
-                        .unwrap_or_default();
-                    let word_b = self
-                        .word_registry
-                        .get(b.0)
-                        .map(|w| w.content.to_lowercase())
-                        .unwrap_or_default();
-                    word_a.cmp(&word_b)
-                });
+                results.sort_by(|a, b| self.folded_word(a.0).cmp(self.folded_word(b.0)));
             }
             SortType::Length => {
                 results.sort_by(|a, b| {
@@ -305,6 +440,27 @@ impl<'a> QueryEngine<'a> {
     }
 }
 
+/// Returns true if folded `text` contains folded `query`, or for a fuzzy
+/// query has a word within its typo budget
+fn text_matches(text: &str, query: &str, fuzzy: bool) -> bool {
+    if fuzzy {
+        fuzzy_distance(text, query).is_some()
+    } else {
+        text.contains(query)
+    }
+}
+
+/// Returns true if `language` is the tag `query` or one of its subtags,
+/// ignoring case: `de` matches `de` and `de-AT` but not `den`.
+fn language_matches(language: &str, query: &str) -> bool {
+    let language = language.to_lowercase();
+    let query = query.to_lowercase();
+    language == query
+        || language
+            .strip_prefix(&query)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
 /// Legacy search function for backward compatibility
 ///
 /// This function is deprecated. Use QueryEngine instead.
@@ -323,49 +479,16 @@ pub fn search(
         queue_registry,
     );
 
-    let query = Query::empty();
-    let mut results = engine.execute(&query);
-
-    // Apply sort
-    match sort {
-        SortType::BestMatch => {
-            results.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
-        }
-        SortType::Newest => {
-            results.sort_by_key(|b| std::cmp::Reverse(b.0));
-        }
-        SortType::Oldest => {
-            results.sort_by_key(|a| a.0);
-        }
-        SortType::AZ => {
-            results.sort_by(|a, b| {
-                let word_a = word_registry
-                    .get(a.0)
-                    .map(|w| w.content.to_lowercase())
-                    .unwrap_or_default();
-                let word_b = word_registry
-                    .get(b.0)
-                    .map(|w| w.content.to_lowercase())
-                    .unwrap_or_default();
-                word_a.cmp(&word_b)
-            });
-        }
-        SortType::Length => {
-            results.sort_by(|a, b| {
-                let len_a = word_registry.get(a.0).map(|w| w.content.len()).unwrap_or(0);
-                let len_b = word_registry.get(b.0).map(|w| w.content.len()).unwrap_or(0);
-                len_a.cmp(&len_b)
-            });
-        }
-    }
-
-    results
+    engine.execute(&Query::new(Condition::All(vec![]), sort))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Meaning, Word};
+    use crate::models::{CefrLevel, Cloze, ClozeSegment, Meaning, PartOfSpeech, TagId, Word};
+    use crate::query::DateRange;
+    use crate::query::parse::{TagResolver, parse_query, parse_query_raw};
+    use chrono::TimeZone;
     use test_case::test_case;
 
     fn setup_test_data() -> (WordRegistry, MeaningRegistry, ClozeRegistry, QueueRegistry) {
@@ -424,6 +547,8 @@ mod tests {
     #[test_case(Condition::Text("foo".to_string()), 1; "text search foo")]
     #[test_case(Condition::All(vec![Condition::Text("hello".to_string()), Condition::Text("world".to_string())]), 0; "AND condition no match")]
     #[test_case(Condition::Any(vec![Condition::Text("hello".to_string()), Condition::Text("world".to_string())]), 2; "OR condition")]
+    #[test_case(Condition::Not(Box::new(Condition::Text("hello".to_string()))), 2; "NOT condition")]
+    #[test_case(Condition::Not(Box::new(Condition::Any(vec![Condition::Text("hello".to_string()), Condition::Text("world".to_string())]))), 1; "NOT group")]
     fn test_query_execution(condition: Condition, expected_count: usize) {
         let (word_registry, meaning_registry, cloze_registry, queue_registry) = setup_test_data();
         let engine = make_engine(
@@ -436,4 +561,247 @@ mod tests {
         let results = engine.execute(&query);
         assert_eq!(results.len(), expected_count);
     }
+
+    fn dated_word(registry: &mut WordRegistry, content: &str, day: u32) -> WordId {
+        let word = Word::builder()
+            .content(content.to_string())
+            .created_at(Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap())
+            .build();
+        let id = word.id;
+        registry.add(word);
+        id
+    }
+
+    #[test_case(SortType::Newest, &["c", "b", "a"]; "newest")]
+    #[test_case(SortType::Oldest, &["a", "b", "c"]; "oldest")]
+    fn test_sort_by_creation(sort: SortType, expected: &[&str]) {
+        let mut word_registry = WordRegistry::new();
+        for (content, day) in [("b", 5), ("c", 9), ("a", 1)] {
+            dated_word(&mut word_registry, content, day);
+        }
+        let (meaning_registry, cloze_registry, queue_registry) = (
+            MeaningRegistry::new(),
+            ClozeRegistry::new(),
+            QueueRegistry::new(),
+        );
+        let engine = make_engine(
+            &word_registry,
+            &meaning_registry,
+            &cloze_registry,
+            &queue_registry,
+        );
+        let results = engine.execute(&Query::new(Condition::All(vec![]), sort));
+        let contents: Vec<_> = results
+            .iter()
+            .map(|(id, _)| word_registry.get(*id).unwrap().content.as_str())
+            .collect();
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn test_date_filters() {
+        let mut word_registry = WordRegistry::new();
+        let mut meaning_registry = MeaningRegistry::new();
+        dated_word(&mut word_registry, "old", 1);
+        let new = dated_word(&mut word_registry, "new", 15);
+        let edited = dated_word(&mut word_registry, "edited", 2);
+        let meaning = Meaning::builder()
+            .word_id(edited)
+            .definition("changed later".to_string())
+            .pos(crate::models::PartOfSpeech::Noun)
+            .created_at(Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap())
+            .build();
+        meaning_registry.add(meaning);
+        let (cloze_registry, queue_registry) = (ClozeRegistry::new(), QueueRegistry::new());
+        let engine = make_engine(
+            &word_registry,
+            &meaning_registry,
+            &cloze_registry,
+            &queue_registry,
+        );
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let week = DateRange::parse("week", now).unwrap();
+
+        let ids = |condition| -> HashSet<WordId> {
+            engine
+                .execute(&Query::new(condition, SortType::BestMatch))
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(ids(Condition::Added(week)), HashSet::from([new]));
+        assert_eq!(ids(Condition::Modified(week)), HashSet::from([new, edited]));
+    }
+
+    #[test]
+    fn test_field_filters() {
+        let mut word_registry = WordRegistry::new();
+        let mut meaning_registry = MeaningRegistry::new();
+        let mut cloze_registry = ClozeRegistry::new();
+        let mut add = |content: &str, language: &str, pos, cefr, clozes| {
+            let word_id = WordId::new();
+            let meaning = Meaning::builder()
+                .word_id(word_id)
+                .definition(format!("{} meaning", content))
+                .pos(pos)
+                .cefr_level(Some(cefr))
+                .build();
+            for _ in 0..clozes {
+                cloze_registry.add(
+                    Cloze::builder()
+                        .meaning_id(meaning.id)
+                        .segments(vec![ClozeSegment::Blank(content.to_string())])
+                        .build(),
+                );
+            }
+            word_registry.add(
+                Word::builder()
+                    .id(word_id)
+                    .content(content.to_string())
+                    .language(language.parse().unwrap())
+                    .build(),
+            );
+            word_registry.add_meaning(word_id, meaning.id);
+            meaning_registry.add(meaning);
+            word_id
+        };
+        let haus = add("haus", "de", PartOfSpeech::Noun, CefrLevel::B2, 0);
+        let hund = add("hund", "de-AT", PartOfSpeech::Noun, CefrLevel::B2, 2);
+        let laufen = add("laufen", "de", PartOfSpeech::Verb, CefrLevel::B2, 0);
+        add("house", "en", PartOfSpeech::Noun, CefrLevel::B2, 0);
+        let baum = add("baum", "den", PartOfSpeech::Noun, CefrLevel::A2, 0);
+        let queue_registry = QueueRegistry::new();
+        let engine = make_engine(
+            &word_registry,
+            &meaning_registry,
+            &cloze_registry,
+            &queue_registry,
+        );
+
+        let ids = |query: &str| -> HashSet<WordId> {
+            let (_, condition) = parse_query_raw(query);
+            engine
+                .execute(&Query::new(condition, SortType::BestMatch))
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(ids("cefr:B2 :noun lang:de clozes:0"), HashSet::from([haus]));
+        assert_eq!(ids("lang:de"), HashSet::from([haus, hund, laufen]));
+        assert_eq!(ids("clozes:>=2"), HashSet::from([hund]));
+        assert_eq!(ids("cefr:..B1"), HashSet::from([baum]));
+        assert_eq!(ids("def:\"laufen meaning\""), HashSet::from([laufen]));
+        assert_eq!(ids("word:au"), HashSet::from([haus, laufen, baum]));
+        assert_eq!(ids("meanings:0").len(), 0);
+    }
+
+    #[test]
+    fn test_tag_path_filters() {
+        let mut tag_registry = crate::registry::TagRegistry::new();
+        let travel = tag_registry.get_or_create_path("Travel").unwrap();
+        let food = tag_registry.get_or_create_path("Travel/Food").unwrap();
+        let work_food = tag_registry.get_or_create_path("Work/Food").unwrap();
+
+        let mut word_registry = WordRegistry::new();
+        let mut meaning_registry = MeaningRegistry::new();
+        let mut add = |content: &str, tag_id: TagId| {
+            let word = Word::builder().content(content.to_string()).build();
+            let id = word.id;
+            word_registry.add(word);
+            let mut meaning = Meaning::builder()
+                .word_id(id)
+                .definition(String::new())
+                .pos(PartOfSpeech::Noun)
+                .build();
+            meaning.tag_ids.insert(tag_id);
+            meaning_registry.add(meaning);
+            id
+        };
+        let trip = add("trip", travel);
+        let bread = add("bread", food);
+        let lunch = add("lunch", work_food);
+
+        let cloze_registry = ClozeRegistry::new();
+        let queue_registry = QueueRegistry::new();
+        let engine = make_engine(
+            &word_registry,
+            &meaning_registry,
+            &cloze_registry,
+            &queue_registry,
+        );
+        let ids = |query: &str| -> HashSet<WordId> {
+            let mut resolver = TagResolver::new(&tag_registry);
+            engine
+                .execute(&parse_query(query, &mut resolver))
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(ids("tag:Travel"), HashSet::from([trip]));
+        assert_eq!(ids("tag:travel/food"), HashSet::from([bread]));
+        assert_eq!(ids("#Work/Food"), HashSet::from([lunch]));
+        assert_eq!(ids("tag:Travel/*"), HashSet::from([trip, bread]));
+        assert_eq!(ids("-tag:Travel/*"), HashSet::from([lunch]));
+    }
+
+    #[test]
+    fn test_text_search_folds_and_fuzzy_matches() {
+        let mut word_registry = WordRegistry::new();
+        let mut meaning_registry = MeaningRegistry::new();
+        let mut add = |content: &str, definition: &str| {
+            let word = Word::builder().content(content.to_string()).build();
+            let id = word.id;
+            word_registry.add(word);
+            meaning_registry.add(
+                Meaning::builder()
+                    .word_id(id)
+                    .definition(definition.to_string())
+                    .pos(PartOfSpeech::Noun)
+                    .build(),
+            );
+            id
+        };
+        let cafe = add("café", "a small restaurant");
+        let guide = add("ガイド", "guide");
+        let necessary = add("necessary", "needed");
+        let restaurant = add("restaurant", "a place to eat");
+        let mind = add("mind", "to take care of; look after someone");
+
+        let cloze_registry = ClozeRegistry::new();
+        let queue_registry = QueueRegistry::new();
+        let engine = make_engine(
+            &word_registry,
+            &meaning_registry,
+            &cloze_registry,
+            &queue_registry,
+        );
+        let results = |query: &str| -> Vec<(WordId, i32)> {
+            let (_, condition) = parse_query_raw(query);
+            engine.execute(&Query::new(condition, SortType::BestMatch))
+        };
+        let ids = |query: &str| -> HashSet<WordId> {
+            results(query).into_iter().map(|(id, _)| id).collect()
+        };
+        assert_eq!(ids("CAFE"), HashSet::from([cafe]));
+        assert_eq!(ids("cafe\u{301}"), HashSet::from([cafe]));
+        assert_eq!(ids("ｃａｆｅ"), HashSet::from([cafe]));
+        assert_eq!(ids("ｶﾞｲﾄﾞ"), HashSet::from([guide]));
+        assert_eq!(ids("word:Cafe"), HashSet::from([cafe]));
+        assert!(ids("necesary").is_empty());
+        assert_eq!(ids("necesary~"), HashSet::from([necessary]));
+        assert_eq!(ids("-necesary~").len(), 4);
+        assert!(ids("word:necesary").is_empty());
+        assert_eq!(ids("word:necesary~"), HashSet::from([necessary]));
+        assert_eq!(ids("def:\"a plase to eat\"~"), HashSet::from([restaurant]));
+        assert_eq!(ids("def:neded~"), HashSet::from([necessary]));
+        assert!(ids("def:neded").is_empty());
+        // A phrase is compared against runs of as many words
+        assert_eq!(results("def:\"look aftr\"~"), vec![(mind, 7)]);
+
+        // A typo in the word itself outranks a definition containing it
+        let ranked = results("restaurnt~");
+        assert_eq!(ranked[0], (restaurant, 15));
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[1], (cafe, 7));
+    }
 }
@@ -1,7 +1,12 @@
 pub mod ast;
+pub mod diagnostic;
 pub mod engine;
+pub mod normalize;
 pub mod parse;
 
-pub use ast::{Condition, Query, QueryAST, SortType, StatusFilter, Token};
+pub use ast::{
+    CefrRange, Condition, CountRange, DateRange, Query, QueryAST, SortType, StatusFilter, Token,
+};
+pub use diagnostic::{Diagnostic, DiagnosticKind};
 pub use engine::{QueryEngine, search};
-pub use parse::{TagResolver, parse_pos, parse_query};
+pub use parse::{TagResolver, parse_pos, parse_query, parse_query_with_diagnostics};
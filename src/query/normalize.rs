@@ -0,0 +1,131 @@
+//! Text folding and fuzzy comparison for search.
+//!
+//! Folded text is compatibility-normalized (full-width `ｃａｆｅ` and
+//! half-width `ｶﾌｪ` become `cafe` and `カフェ`), stripped of Latin
+//! diacritics and lowercased. Japanese voicing marks are kept, so `ガ`
+//! still differs from `カ`.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Folds text for comparison: `Café`, `CAFE` and `cafe\u{301}` all fold
+/// to `cafe`.
+pub fn fold(text: &str) -> String {
+    text.nfkd()
+        .filter(|c| !is_diacritic(*c))
+        .nfc()
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Returns the rest of `text` after its longest start that folds to the
+/// same string as `prefix`, or `None` if no start does:
+/// `strip_folded_prefix("Café au lait", "CAFE")` is `Some(" au lait")`.
+pub fn strip_folded_prefix<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let prefix = fold(prefix);
+    let ends = std::iter::once(0).chain(text.char_indices().map(|(i, c)| i + c.len_utf8()));
+    let mut rest = None;
+    for end in ends {
+        let folded = fold(&text[..end]);
+        if folded == prefix {
+            rest = Some(&text[end..]);
+        } else if !prefix.starts_with(&folded) {
+            break;
+        }
+    }
+    rest
+}
+
+/// Combining marks used as accents on Latin, Greek and Cyrillic letters
+fn is_diacritic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Number of typos a fuzzy query of `len` characters tolerates. Short
+/// queries must match exactly, or nearly every word would be a hit.
+fn typo_budget(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Edit distance between a folded query and the closest word of folded
+/// `text`, or `None` if every word needs more edits than the query's
+/// length allows. A query of several words is compared against each run of
+/// as many consecutive words. A text containing the query is a distance
+/// of 0.
+pub fn fuzzy_distance(text: &str, query: &str) -> Option<usize> {
+    if text.contains(query) {
+        return Some(0);
+    }
+    let budget = typo_budget(query.chars().count());
+    if budget == 0 {
+        return None;
+    }
+    let query_words = words(query);
+    let query = query_words.join(" ");
+    words(text)
+        .windows(query_words.len().max(1))
+        .map(|window| strsim::levenshtein(&window.join(" "), &query))
+        .filter(|distance| *distance <= budget)
+        .min()
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("Café", "cafe"; "precomposed accent")]
+    #[test_case("cafe\u{301}", "cafe"; "combining accent")]
+    #[test_case("NAÏVE Façade", "naive facade"; "case and cedilla")]
+    #[test_case("ｃａｆｅ", "cafe"; "full-width latin")]
+    #[test_case("ｶﾌｪ", "カフェ"; "half-width katakana")]
+    #[test_case("ガイド", "ガイド"; "voicing mark kept")]
+    #[test_case("か\u{3099}", "が"; "decomposed kana recombined")]
+    fn test_fold(input: &str, expected: &str) {
+        assert_eq!(fold(input), fold(expected));
+        assert_eq!(fold(input), expected.nfc().collect::<String>());
+    }
+
+    #[test_case("Café au lait", "CAFE", Some(" au lait"); "accent and case")]
+    #[test_case("cafe\u{301}s", "cafe", Some("s"); "combining mark kept with prefix")]
+    #[test_case("İstanbul", "i̇s", Some("tanbul"); "lowercase changes length")]
+    #[test_case("カフェ", "ｶﾌ", Some("ェ"); "half-width prefix")]
+    #[test_case("café", "", Some("café"); "empty prefix")]
+    #[test_case("café", "cab", None; "no match")]
+    fn test_strip_folded_prefix(text: &str, prefix: &str, expected: Option<&str>) {
+        assert_eq!(strip_folded_prefix(text, prefix), expected);
+    }
+
+    #[test_case("necessary", "necesary", Some(1); "one typo")]
+    #[test_case("a necessary evil", "neccesary", Some(2); "two typos in long word")]
+    #[test_case("cafe", "cafe", Some(0); "exact")]
+    #[test_case("cafeteria", "cafe", Some(0); "contained")]
+    #[test_case("cat", "car", None; "short query must be exact")]
+    #[test_case("house", "mouse", Some(1); "substitution")]
+    #[test_case("house", "hose", Some(1); "deletion")]
+    #[test_case("garden", "gardens", Some(1); "insertion")]
+    #[test_case("elephant", "telephone", None; "too far")]
+    #[test_case("to look after", "to look aftr", Some(1); "phrase")]
+    #[test_case("to take care of; look after someone", "look aftr", Some(1); "phrase within text")]
+    #[test_case("to look after", "look  aftr", Some(1); "extra space in phrase")]
+    #[test_case("to look into something", "look aftr", None; "phrase too far")]
+    fn test_fuzzy_distance(text: &str, query: &str, expected: Option<usize>) {
+        assert_eq!(fuzzy_distance(text, query), expected);
+    }
+}
@@ -1,24 +1,39 @@
 use crate::models::{PartOfSpeech, TagId};
-use crate::query::ast::{Condition, Query, SortType, StatusFilter, Token};
+use crate::query::ast::{
+    CefrRange, Condition, CountRange, DateRange, Query, SortType, StatusFilter, Token,
+};
+use crate::query::diagnostic::{Diagnostic, diagnose};
+use chrono::Local;
 use std::collections::HashMap;
 use std::iter::Peekable;
+use std::ops::Range;
 use std::slice::Iter;
 
 /// Parses a query string into a Query with resolved tag names
 pub fn parse_query(input: &str, resolver: &mut TagResolver) -> Query {
+    parse_query_with_diagnostics(input, resolver).0
+}
+
+/// Parses a query string like `parse_query`, also reporting problems such
+/// as unknown tags or unbalanced parentheses with their character spans
+pub fn parse_query_with_diagnostics(
+    input: &str,
+    resolver: &mut TagResolver,
+) -> (Query, Vec<Diagnostic>) {
     if input.trim().is_empty() {
-        return Query::empty();
+        return (Query::empty(), Vec::new());
     }
 
-    let tokens = tokenize(input);
-    let condition = build_ast(tokens);
+    let spanned = tokenize_spanned(input);
+    let diagnostics = diagnose(input, &spanned, resolver);
+    let condition = build_ast(spanned.into_iter().map(|(token, _)| token).collect());
 
     // Resolve tag names to TagIds
     let filter = condition
         .resolve_tags(resolver)
         .unwrap_or_else(|| Condition::All(vec![]));
 
-    Query::new(filter, SortType::BestMatch)
+    (Query::new(filter, SortType::BestMatch), diagnostics)
 }
 
 /// Parses a query string into a Query without resolving tags (for testing)
@@ -29,49 +44,55 @@ pub fn parse_query_raw(input: &str) -> (Vec<Token>, Condition) {
 }
 
 /// First phase: Tokenize input string
+///
+/// Double quotes keep spaces, `|` and parentheses inside a token, so
+/// `def:"to move fast"` is a single filter and `"to look after"` a single
+/// phrase. A `-` directly before `(` negates the group.
 pub fn tokenize(input: &str) -> Vec<Token> {
+    tokenize_spanned(input)
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect()
+}
+
+/// Tokenizes like `tokenize`, pairing each token with the character range
+/// it came from
+pub fn tokenize_spanned(input: &str) -> Vec<(Token, Range<usize>)> {
     let mut tokens = Vec::new();
-    let chars = input.chars();
     let mut current_token = String::new();
+    let mut start = 0;
+    let mut in_quotes = false;
 
-    for ch in chars {
+    for (i, ch) in input.chars().enumerate() {
+        if current_token.is_empty() {
+            start = i;
+        }
+        if in_quotes || ch == '"' {
+            in_quotes = in_quotes != (ch == '"');
+            current_token.push(ch);
+            continue;
+        }
         match ch {
             '|' => {
                 // Flush current token before OR
-                if !current_token.is_empty() {
-                    if let Some(token) = parse_single_token(&current_token) {
-                        tokens.push(token);
-                    }
-                    current_token.clear();
-                }
-                tokens.push(Token::Or);
+                flush_token(&mut tokens, &mut current_token, start..i);
+                tokens.push((Token::Or, i..i + 1));
             }
             '(' => {
-                if !current_token.is_empty() {
-                    if let Some(token) = parse_single_token(&current_token) {
-                        tokens.push(token);
-                    }
+                if current_token == "-" {
                     current_token.clear();
+                    tokens.push((Token::Not, start..i));
                 }
-                tokens.push(Token::LeftParen);
+                flush_token(&mut tokens, &mut current_token, start..i);
+                tokens.push((Token::LeftParen, i..i + 1));
             }
             ')' => {
-                if !current_token.is_empty() {
-                    if let Some(token) = parse_single_token(&current_token) {
-                        tokens.push(token);
-                    }
-                    current_token.clear();
-                }
-                tokens.push(Token::RightParen);
+                flush_token(&mut tokens, &mut current_token, start..i);
+                tokens.push((Token::RightParen, i..i + 1));
             }
             ' ' | '\t' | '\n' => {
                 // Whitespace separates tokens
-                if !current_token.is_empty() {
-                    if let Some(token) = parse_single_token(&current_token) {
-                        tokens.push(token);
-                    }
-                    current_token.clear();
-                }
+                flush_token(&mut tokens, &mut current_token, start..i);
             }
             _ => {
                 current_token.push(ch);
@@ -80,25 +101,55 @@ pub fn tokenize(input: &str) -> Vec<Token> {
     }
 
     // Don't forget the last token
-    if !current_token.is_empty()
-        && let Some(token) = parse_single_token(&current_token)
-    {
-        tokens.push(token);
-    }
+    let end = input.chars().count();
+    flush_token(&mut tokens, &mut current_token, start..end);
 
     tokens
 }
 
+/// Pushes the tokens for a finished token string and clears it. Filters
+/// without an exclusion form of their own, such as `-added:week`, are
+/// negated with a preceding `Not`.
+fn flush_token(
+    tokens: &mut Vec<(Token, Range<usize>)>,
+    current_token: &mut String,
+    span: Range<usize>,
+) {
+    if current_token.is_empty() {
+        return;
+    }
+    match parse_single_token(current_token) {
+        Some(Token::Text(text)) if text.starts_with('-') => match parse_single_token(&text[1..]) {
+            Some(token) if !matches!(token, Token::Text(_)) => {
+                tokens.push((Token::Not, span.start..span.start + 1));
+                tokens.push((token, span));
+            }
+            _ => tokens.push((Token::Text(text), span)),
+        },
+        Some(token) => tokens.push((token, span)),
+        None => {}
+    }
+    current_token.clear();
+}
+
 /// Parses a single token string into a Token
 fn parse_single_token(s: &str) -> Option<Token> {
     let s = s.trim();
-    if s.is_empty() {
-        return None;
+    match s {
+        "" => return None,
+        "AND" => return Some(Token::And),
+        "OR" => return Some(Token::Or),
+        "NOT" => return Some(Token::Not),
+        _ => {}
     }
 
     if s.starts_with('-') {
         // Exclusion tokens
-        if let Some(tag_name) = s.strip_prefix("-#") {
+        if let Some(tag_name) = s
+            .strip_prefix("-#")
+            .or(s.strip_prefix("-tag:"))
+            .map(unquote)
+        {
             if !tag_name.is_empty() {
                 return Some(Token::ExcludeTagName(tag_name.to_string()));
             }
@@ -114,8 +165,7 @@ fn parse_single_token(s: &str) -> Option<Token> {
         }
         // Text starting with - but not a special token
         return Some(Token::Text(s.to_string()));
-    } else if s.starts_with('#') {
-        let tag_name = s.strip_prefix('#').unwrap_or(s);
+    } else if let Some(tag_name) = s.strip_prefix('#').or(s.strip_prefix("tag:")).map(unquote) {
         if !tag_name.is_empty() {
             return Some(Token::IncludeTagName(tag_name.to_string()));
         }
@@ -128,29 +178,106 @@ fn parse_single_token(s: &str) -> Option<Token> {
         && let Some(status) = StatusFilter::parse(status_str)
     {
         return Some(Token::IncludeStatus(status));
+    } else if let Some(range_str) = s.strip_prefix("added:")
+        && let Some(range) = DateRange::parse(range_str, Local::now())
+    {
+        return Some(Token::Added(range));
+    } else if let Some(range_str) = s.strip_prefix("modified:")
+        && let Some(range) = DateRange::parse(range_str, Local::now())
+    {
+        return Some(Token::Modified(range));
+    } else if let Some((text, fuzzy)) = s.strip_prefix("word:").map(split_fuzzy)
+        && !text.is_empty()
+    {
+        let text = text.to_string();
+        return Some(if fuzzy {
+            Token::WordFuzzy(text)
+        } else {
+            Token::WordText(text)
+        });
+    } else if let Some((text, fuzzy)) = s.strip_prefix("def:").map(split_fuzzy)
+        && !text.is_empty()
+    {
+        let text = text.to_string();
+        return Some(if fuzzy {
+            Token::DefinitionFuzzy(text)
+        } else {
+            Token::DefinitionText(text)
+        });
+    } else if let Some(range_str) = s.strip_prefix("cefr:")
+        && let Some(range) = CefrRange::parse(range_str)
+    {
+        return Some(Token::Cefr(range));
+    } else if let Some(tag) = s.strip_prefix("lang:").map(unquote)
+        && !tag.is_empty()
+    {
+        return Some(Token::Language(tag.to_string()));
+    } else if let Some(range_str) = s.strip_prefix("clozes:")
+        && let Some(range) = CountRange::parse(range_str)
+    {
+        return Some(Token::ClozeCount(range));
+    } else if let Some(range_str) = s.strip_prefix("meanings:")
+        && let Some(range) = CountRange::parse(range_str)
+    {
+        return Some(Token::MeaningCount(range));
+    }
+
+    // A trailing `~` makes the term fuzzy
+    if let Some(term) = s.strip_suffix('~').map(unquote)
+        && !term.is_empty()
+    {
+        return Some(Token::Fuzzy(term.to_string()));
     }
 
     // Default: text search
-    Some(Token::Text(s.to_string()))
+    Some(Token::Text(unquote(s).to_string()))
+}
+
+/// Unquotes a value and reports whether a trailing `~` made it fuzzy.
+fn split_fuzzy(s: &str) -> (&str, bool) {
+    match s.strip_suffix('~') {
+        Some(rest) => (unquote(rest), true),
+        None => (unquote(s), false),
+    }
+}
+
+/// Strips the double quotes around a value; an unterminated quote only
+/// loses its opening mark.
+fn unquote(s: &str) -> &str {
+    match s.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"').unwrap_or(rest),
+        None => s,
+    }
 }
 
 /// Second phase: Build AST from tokens using recursive descent parser
 ///
 /// Grammar:
-///   expression := and_expr ("|" and_expr)*
-///   and_expr   := primary+
-///   primary    := tag | pos | status | text | "(" expression ")"
+///   expression := and_expr (("|" | "OR") and_expr)*
+///   and_expr   := unary ("AND"? unary)*
+///   unary      := ("NOT" | "-(") unary | primary
+///   primary    := tag | pos | status | date | field | text | "(" expression ")"
 pub fn build_ast(tokens: Vec<Token>) -> Condition {
     if tokens.is_empty() {
         return Condition::All(vec![]);
     }
 
     let mut iter = tokens.iter().peekable();
-    parse_expression(&mut iter)
+    let mut conditions = vec![parse_expression(&mut iter)];
+    // A stray `)` ends the expression early; the rest still applies
+    while iter.next().is_some() {
+        conditions.push(parse_expression(&mut iter));
+    }
+
+    if conditions.len() == 1 {
+        conditions.into_iter().next().unwrap()
+    } else {
+        Condition::All(conditions)
+    }
 }
 
 /// Parse expression (handles OR operator)
-/// expression := and_expr ("|" and_expr)*
+/// expression := and_expr (("|" | "OR") and_expr)*
 fn parse_expression(tokens: &mut Peekable<Iter<Token>>) -> Condition {
     let left = parse_and(tokens);
 
@@ -175,7 +302,7 @@ fn parse_expression(tokens: &mut Peekable<Iter<Token>>) -> Condition {
 }
 
 /// Parse AND expression (handles implicit AND via space separation)
-/// and_expr := primary+
+/// and_expr := unary ("AND"? unary)*
 fn parse_and(tokens: &mut Peekable<Iter<Token>>) -> Condition {
     let mut conditions = vec![];
 
@@ -183,9 +310,14 @@ fn parse_and(tokens: &mut Peekable<Iter<Token>>) -> Condition {
         match token {
             // Stop at OR or right paren
             Token::Or | Token::RightParen => break,
+            // Explicit AND is the same as a space
+            Token::And => {
+                tokens.next();
+            }
             _ => {
-                let cond = parse_primary(tokens);
-                conditions.push(cond);
+                if let Some(cond) = parse_unary(tokens) {
+                    conditions.push(cond);
+                }
             }
         }
     }
@@ -199,8 +331,20 @@ fn parse_and(tokens: &mut Peekable<Iter<Token>>) -> Condition {
     }
 }
 
+/// Parse unary expression; a `NOT` with nothing left to negate is dropped
+/// unary := ("NOT" | "-(") unary | primary
+fn parse_unary(tokens: &mut Peekable<Iter<Token>>) -> Option<Condition> {
+    if tokens.next_if_eq(&&Token::Not).is_none() {
+        return Some(parse_primary(tokens));
+    }
+    match tokens.peek() {
+        None | Some(Token::Or | Token::And | Token::RightParen) => None,
+        Some(_) => parse_unary(tokens).map(|cond| Condition::Not(Box::new(cond))),
+    }
+}
+
 /// Parse primary expression
-/// primary := tag | pos | status | text | "(" expression ")"
+/// primary := tag | pos | status | date | field | text | "(" expression ")"
 fn parse_primary(tokens: &mut Peekable<Iter<Token>>) -> Condition {
     match tokens.next() {
         Some(Token::LeftParen) => {
@@ -233,7 +377,18 @@ fn parse_primary(tokens: &mut Peekable<Iter<Token>>) -> Condition {
         }
         Some(Token::IncludeStatus(status)) => Condition::HasStatus(*status),
         Some(Token::ExcludeStatus(status)) => Condition::NotHasStatus(*status),
+        Some(Token::Added(range)) => Condition::Added(*range),
+        Some(Token::Modified(range)) => Condition::Modified(*range),
+        Some(Token::WordText(text)) => Condition::WordText(text.clone()),
+        Some(Token::WordFuzzy(text)) => Condition::WordFuzzy(text.clone()),
+        Some(Token::DefinitionText(text)) => Condition::DefinitionText(text.clone()),
+        Some(Token::DefinitionFuzzy(text)) => Condition::DefinitionFuzzy(text.clone()),
+        Some(Token::Cefr(range)) => Condition::Cefr(*range),
+        Some(Token::Language(tag)) => Condition::Language(tag.clone()),
+        Some(Token::ClozeCount(range)) => Condition::ClozeCount(*range),
+        Some(Token::MeaningCount(range)) => Condition::MeaningCount(*range),
         Some(Token::Text(text)) => Condition::Text(text.clone()),
+        Some(Token::Fuzzy(text)) => Condition::Fuzzy(text.clone()),
         _ => Condition::All(vec![]), // Empty condition matches all
     }
 }
@@ -274,6 +429,7 @@ impl<'a> TagResolver<'a> {
         }
     }
 
+    /// Resolves a tag name, or a `/`-separated path such as `Travel/Food`
     pub fn resolve(&mut self, name: &str) -> Option<TagId> {
         let name_lower = name.to_lowercase();
 
@@ -283,21 +439,38 @@ impl<'a> TagResolver<'a> {
         }
 
         // Look up in registry
-        let found = self
-            .tag_registry
-            .iter()
-            .find(|(_, t)| t.name.to_lowercase() == name_lower)
-            .map(|(id, _)| *id);
+        let found = if name_lower.contains('/') {
+            self.tag_registry.find_by_path(&name_lower)
+        } else {
+            self.tag_registry
+                .iter()
+                .find(|(_, t)| t.name.to_lowercase() == name_lower)
+                .map(|(id, _)| *id)
+        };
 
         // Cache the result
         self.cache.insert(name_lower, found);
         found
     }
+
+    /// Resolves the tags a tag filter matches: the named tag alone, or with
+    /// every tag nested under it when the name ends in `/*`
+    pub fn resolve_filter(&mut self, name: &str) -> Option<Vec<TagId>> {
+        match name.strip_suffix("/*") {
+            Some(path) => {
+                let id = self.resolve(path)?;
+                Some(self.tag_registry.with_descendants(id))
+            }
+            None => self.resolve(name).map(|id| vec![id]),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::CefrLevel;
+    use chrono::{DateTime, TimeZone, Utc};
     use test_case::test_case;
 
     #[test_case("", 0; "empty")]
@@ -307,6 +480,13 @@ mod tests {
     #[test_case(":noun,verb", 1; "POS filter")]
     #[test_case("hello | world", 3; "OR operator")]
     #[test_case("(hello world)", 4; "grouping")]
+    #[test_case("def:\"to move fast\" word:run", 2; "quoted value")]
+    #[test_case("\"a | (b)\"", 1; "quoted operators")]
+    #[test_case("\"to look after\"", 1; "quoted phrase")]
+    #[test_case("-(hello world)", 5; "negated group")]
+    #[test_case("hello AND world OR foo", 5; "keywords")]
+    #[test_case("NOT hello", 2; "NOT keyword")]
+    #[test_case("-added:week", 2; "negated filter")]
     fn test_tokenize(input: &str, expected_count: usize) {
         let tokens = tokenize(input);
         assert_eq!(tokens.len(), expected_count);
@@ -315,8 +495,15 @@ mod tests {
     #[test_case("hello world", "hello"; "first text token")]
     #[test_case("#important", "important"; "tag name")]
     #[test_case("-#ignored", "ignored"; "exclude tag name")]
+    #[test_case("tag:Travel/Food", "Travel/Food"; "tag path")]
+    #[test_case("-tag:\"Exam Prep\"", "Exam Prep"; "exclude quoted tag path")]
     #[test_case("hello | world", "hello"; "text before OR")]
     #[test_case("(hello world)", "hello"; "text after left paren")]
+    #[test_case("\"to look after\" care", "to look after"; "quoted phrase")]
+    #[test_case("\"is:done\"", "is:done"; "quoted filter is text")]
+    #[test_case("-world", "-world"; "dash text")]
+    #[test_case("colour~", "colour"; "fuzzy term")]
+    #[test_case("\"to look aftr\"~", "to look aftr"; "fuzzy phrase")]
     fn test_tokenize_first_text(input: &str, expected: &str) {
         let tokens = tokenize(input);
         assert!(!tokens.is_empty());
@@ -324,11 +511,15 @@ mod tests {
             Token::Text(s) => assert_eq!(s, expected),
             Token::IncludeTagName(name) => assert_eq!(name, expected),
             Token::ExcludeTagName(name) => assert_eq!(name, expected),
+            Token::Fuzzy(s) => assert_eq!(s, expected),
             _ => {}
         }
     }
 
     #[test_case("hello", "hello"; "simple text")]
+    #[test_case("\"to look after\"", "to look after"; "quoted phrase")]
+    #[test_case("and", "and"; "lowercase keyword is text")]
+    #[test_case("hello NOT", "hello"; "dangling NOT")]
     fn test_parse_simple(input: &str, expected_text: &str) {
         let (_, cond) = parse_query_raw(input);
         assert!(matches!(cond, Condition::Text(s) if s == expected_text));
@@ -338,6 +529,10 @@ mod tests {
     #[test_case("hello :noun | world", 2; "OR with AND")]
     #[test_case("(#tag1 | #tag2) :noun", 2; "grouped OR")]
     #[test_case("hello -world | foo (#tag1 | #tag2)", 2; "complex query")]
+    #[test_case("hello AND world", 2; "explicit AND")]
+    #[test_case("hello OR world OR foo", 3; "OR keyword")]
+    #[test_case("hello | world OR foo", 3; "mixed OR")]
+    #[test_case("#tag1 -(hello | world)", 2; "AND with negated group")]
     fn test_parse_condition_structure(input: &str, expected_branch_count: usize) {
         let (_, cond) = parse_query_raw(input);
         match cond {
@@ -358,4 +553,156 @@ mod tests {
             _ => panic!("expected status condition"),
         }
     }
+
+    #[test_case("-(hello world)"; "negated group")]
+    #[test_case("NOT hello"; "NOT keyword")]
+    #[test_case("NOT (hello | world)"; "NOT group")]
+    #[test_case("-added:week"; "negated date filter")]
+    #[test_case("-lang:de"; "negated field filter")]
+    fn test_parse_not(input: &str) {
+        let (_, cond) = parse_query_raw(input);
+        assert!(matches!(cond, Condition::Not(_)), "{:?}", cond);
+    }
+
+    #[test]
+    fn test_parse_negated_group_contents() {
+        let (_, cond) = parse_query_raw("#travel -(:noun | is:done) NOT NOT hello");
+        let Condition::All(conds) = cond else {
+            panic!("expected AND");
+        };
+        assert_eq!(conds.len(), 3);
+        let Condition::Not(group) = &conds[1] else {
+            panic!("expected negated group");
+        };
+        assert!(matches!(&**group, Condition::Any(branches) if branches.len() == 2));
+        let Condition::Not(inner) = &conds[2] else {
+            panic!("expected NOT");
+        };
+        assert!(
+            matches!(&**inner, Condition::Not(text) if matches!(&**text, Condition::Text(s) if s == "hello"))
+        );
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test_case("today", Some("2026-10-17T00:00:00Z"), Some("2026-10-18T00:00:00Z"); "today")]
+    #[test_case("yesterday", Some("2026-10-16T00:00:00Z"), Some("2026-10-17T00:00:00Z"); "yesterday")]
+    #[test_case("week", Some("2026-10-12T00:00:00Z"), None; "since monday")]
+    #[test_case("month", Some("2026-10-01T00:00:00Z"), None; "since first of month")]
+    #[test_case("7d", Some("2026-10-10T12:00:00Z"), None; "last days")]
+    #[test_case("2w", Some("2026-10-03T12:00:00Z"), None; "last weeks")]
+    #[test_case("2026-10-01", Some("2026-10-01T00:00:00Z"), Some("2026-10-02T00:00:00Z"); "single day")]
+    #[test_case("2026-10-01..2026-10-03", Some("2026-10-01T00:00:00Z"), Some("2026-10-04T00:00:00Z"); "inclusive range")]
+    #[test_case("..2026-10-03", None, Some("2026-10-04T00:00:00Z"); "open start")]
+    fn test_parse_date_range(input: &str, start: Option<&str>, end: Option<&str>) {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let range = DateRange::parse(input, now).unwrap();
+        assert_eq!(range.start, start.map(at));
+        assert_eq!(range.end, end.map(at));
+    }
+
+    #[test_case("soon"; "unknown word")]
+    #[test_case("2026-13-01"; "invalid date")]
+    #[test_case(".."; "empty range")]
+    #[test_case("xd"; "missing count")]
+    #[test_case("-5d"; "negative count")]
+    #[test_case("100000000d"; "days out of range")]
+    #[test_case("2000000000000000000w"; "weeks overflow")]
+    #[test_case("99999999999999999999d"; "count overflow")]
+    fn test_parse_date_range_invalid(input: &str) {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        assert_eq!(DateRange::parse(input, now), None);
+    }
+
+    #[test_case("2", 2, Some(2); "exact")]
+    #[test_case(">=2", 2, None; "at least")]
+    #[test_case(">2", 3, None; "more than")]
+    #[test_case("<=2", 0, Some(2); "at most")]
+    #[test_case("<2", 0, Some(1); "less than")]
+    #[test_case("1..3", 1, Some(3); "inclusive range")]
+    #[test_case("..3", 0, Some(3); "open start")]
+    fn test_parse_count_range(input: &str, min: usize, max: Option<usize>) {
+        assert_eq!(CountRange::parse(input), Some(CountRange { min, max }));
+    }
+
+    #[test_case("many"; "not a number")]
+    #[test_case("<0"; "below zero")]
+    #[test_case("3..1"; "reversed")]
+    #[test_case(".."; "empty range")]
+    fn test_parse_count_range_invalid(input: &str) {
+        assert_eq!(CountRange::parse(input), None);
+    }
+
+    #[test_case("b2", Some(CefrLevel::B2), Some(CefrLevel::B2); "single level")]
+    #[test_case("B1..C1", Some(CefrLevel::B1), Some(CefrLevel::C1); "inclusive range")]
+    #[test_case("B1..", Some(CefrLevel::B1), None; "open end")]
+    #[test_case("..A2", None, Some(CefrLevel::A2); "open start")]
+    fn test_parse_cefr_range(input: &str, min: Option<CefrLevel>, max: Option<CefrLevel>) {
+        assert_eq!(CefrRange::parse(input), Some(CefrRange { min, max }));
+    }
+
+    #[test]
+    fn test_parse_fuzzy_field_filters() {
+        let (_, cond) = parse_query_raw("word:necesary~ def:\"a plase\"~ word:\"a~b\"");
+        let Condition::All(conds) = cond else {
+            panic!("expected AND");
+        };
+        assert!(matches!(&conds[0], Condition::WordFuzzy(s) if s == "necesary"));
+        assert!(matches!(&conds[1], Condition::DefinitionFuzzy(s) if s == "a plase"));
+        assert!(matches!(&conds[2], Condition::WordText(s) if s == "a~b"));
+    }
+
+    #[test]
+    fn test_parse_field_filters() {
+        let (_, cond) = parse_query_raw(
+            "word:run def:\"to move fast\" cefr:B1..C1 lang:de clozes:>=2 meanings:0",
+        );
+        let Condition::All(conds) = cond else {
+            panic!("expected AND");
+        };
+        assert!(matches!(&conds[0], Condition::WordText(s) if s == "run"));
+        assert!(matches!(&conds[1], Condition::DefinitionText(s) if s == "to move fast"));
+        assert!(matches!(conds[2], Condition::Cefr(r) if r.max == Some(CefrLevel::C1)));
+        assert!(matches!(&conds[3], Condition::Language(s) if s == "de"));
+        assert!(matches!(conds[4], Condition::ClozeCount(r) if r.min == 2));
+        assert!(matches!(conds[5], Condition::MeaningCount(r) if r.max == Some(0)));
+
+        let (_, cond) = parse_query_raw("cefr:Z9");
+        assert!(matches!(cond, Condition::Text(s) if s == "cefr:Z9"));
+    }
+
+    #[test]
+    fn test_parse_date_filters() {
+        let (_, cond) = parse_query_raw("added:2026-10-01.. modified:today added:soon");
+        let Condition::All(conds) = cond else {
+            panic!("expected AND");
+        };
+        assert!(matches!(conds[0], Condition::Added(r) if r.end.is_none()));
+        assert!(matches!(conds[1], Condition::Modified(_)));
+        assert!(matches!(&conds[2], Condition::Text(s) if s == "added:soon"));
+    }
+
+    #[test_case("a  #b", vec![0..1, 3..5]; "whitespace")]
+    #[test_case("-(x)|y", vec![0..1, 1..2, 2..3, 3..4, 4..5, 5..6]; "operators")]
+    #[test_case("-added:week", vec![0..1, 0..11]; "negated filter")]
+    #[test_case("déf:\"x y\"", vec![0..9]; "quoted characters")]
+    fn test_tokenize_spans(input: &str, expected: Vec<Range<usize>>) {
+        let spans: Vec<_> = tokenize_spanned(input)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect();
+        assert_eq!(spans, expected);
+    }
+
+    #[test]
+    fn test_parse_continues_after_stray_paren() {
+        let (_, cond) = parse_query_raw("a) b");
+        let Condition::All(conds) = cond else {
+            panic!("expected AND");
+        };
+        assert!(matches!(&conds[0], Condition::Text(s) if s == "a"));
+        assert!(matches!(&conds[1], Condition::Text(s) if s == "b"));
+    }
 }
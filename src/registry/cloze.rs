@@ -1,13 +1,16 @@
 use crate::models::{Cloze, ClozeId, MeaningId};
 use crate::persistence::db::CLOZES_TABLE;
-use crate::persistence::{ClozeDto, DbError};
-use crate::registry::dirty::{DirtyTracker, flush_registry};
+use crate::persistence::{ClozeDto, DbError, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use chrono::Utc;
 use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Clone, Default)]
 pub struct ClozeRegistry {
     pub(crate) clozes: BTreeMap<ClozeId, Cloze>,
     pub(crate) dirty: DirtyTracker<ClozeId>,
+    pub(crate) journal: Journal<ClozeId, Cloze>,
     pub(crate) by_meaning: BTreeMap<MeaningId, BTreeSet<ClozeId>>,
 }
 
@@ -16,16 +19,14 @@ impl ClozeRegistry {
         Self {
             clozes: BTreeMap::new(),
             dirty: DirtyTracker::new(),
+            journal: Journal::new(),
             by_meaning: BTreeMap::new(),
         }
     }
 
     pub fn add(&mut self, cloze: Cloze) {
-        let Cloze {
-            id,
-            meaning_id,
-            segments: _,
-        } = cloze.clone();
+        let Cloze { id, meaning_id, .. } = cloze;
+        self.journal.touch(id, &self.clozes);
         self.clozes.insert(id, cloze);
         self.dirty.mark(id);
         self.by_meaning.entry(meaning_id).or_default().insert(id);
@@ -39,8 +40,14 @@ impl ClozeRegistry {
         self.clozes.iter()
     }
 
+    /// Returns a cloze for editing, marking it dirty and bumping
+    /// `updated_at`. The meaning must not be changed.
     pub fn get_mut(&mut self, id: ClozeId) -> Option<&mut Cloze> {
-        self.clozes.get_mut(&id)
+        self.journal.touch(id, &self.clozes);
+        let cloze = self.clozes.get_mut(&id)?;
+        cloze.updated_at = Utc::now();
+        self.dirty.mark(id);
+        Some(cloze)
     }
 
     pub fn iter_by_meaning_id(
@@ -56,6 +63,7 @@ impl ClozeRegistry {
     }
 
     pub fn delete(&mut self, id: ClozeId) -> bool {
+        self.journal.touch(id, &self.clozes);
         if let Some(cloze) = self.clozes.remove(&id) {
             self.dirty.mark(id);
             if let Some(ids) = self.by_meaning.get_mut(&cloze.meaning_id) {
@@ -73,12 +81,21 @@ impl ClozeRegistry {
     pub fn delete_by_meaning(&mut self, meaning_id: MeaningId) {
         if let Some(cloze_ids) = self.by_meaning.remove(&meaning_id) {
             for cloze_id in cloze_ids {
+                self.journal.touch(cloze_id, &self.clozes);
                 self.dirty.mark(cloze_id);
                 self.clozes.remove(&cloze_id);
             }
         }
     }
 
+    /// Puts a cloze back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: ClozeId, cloze: Option<Cloze>) {
+        self.delete(id);
+        if let Some(cloze) = cloze {
+            self.add(cloze);
+        }
+    }
+
     pub fn count(&self) -> usize {
         self.clozes.len()
     }
@@ -102,6 +119,10 @@ impl ClozeRegistry {
             Ok(items) => {
                 for (id, mut dto) in items {
                     dto.id = id;
+                    // Persist backfilled timestamps for legacy records
+                    if dto.created_at.is_none() || dto.updated_at.is_none() {
+                        self.dirty.mark(ClozeId(id));
+                    }
                     let cloze = Cloze::from(dto);
                     self.clozes.insert(cloze.id, cloze.clone());
                     self.by_meaning
@@ -118,12 +139,12 @@ impl ClozeRegistry {
         tracing::debug!(count = loaded, "Loaded clozes from database");
     }
 
-    /// Flush all dirty entities to the database
-    pub fn flush_dirty(&mut self, db: &crate::persistence::Db) -> Result<(), DbError> {
-        flush_registry(
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
             &self.clozes,
-            &mut self.dirty,
-            db,
+            &self.dirty,
+            batch,
             CLOZES_TABLE,
             |c| ClozeDto::from(c),
             "cloze",
@@ -134,4 +155,9 @@ impl ClozeRegistry {
     pub fn has_dirty(&self) -> bool {
         self.dirty.has_dirty()
     }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
 }
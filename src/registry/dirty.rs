@@ -1,4 +1,4 @@
-use crate::persistence::db::{Db, DbError};
+use crate::persistence::db::{DbError, WriteBatch, serialize, uuid_to_key};
 use redb::TableDefinition;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
@@ -20,8 +20,8 @@ impl<K: Ord + Copy> DirtyTracker<K> {
         self.ids.insert(id);
     }
 
-    pub fn clean(&mut self, id: &K) -> bool {
-        self.ids.remove(id)
+    pub fn clear(&mut self) {
+        self.ids.clear();
     }
 
     pub fn has_dirty(&self) -> bool {
@@ -31,16 +31,15 @@ impl<K: Ord + Copy> DirtyTracker<K> {
     pub fn len(&self) -> usize {
         self.ids.len()
     }
-
-    pub fn snapshot(&self) -> Vec<K> {
-        self.ids.iter().copied().collect()
-    }
 }
 
-pub(crate) fn flush_registry<K, V, D>(
+/// Writes every dirty entity of a registry into `batch`: present entities
+/// are saved, missing ones deleted. Dirty flags are left set; the caller
+/// clears them once the batch has committed.
+pub(crate) fn write_registry<K, V, D>(
     entities: &BTreeMap<K, V>,
-    dirty: &mut DirtyTracker<K>,
-    db: &Db,
+    dirty: &DirtyTracker<K>,
+    batch: &WriteBatch,
     table: TableDefinition<[u8; 16], Vec<u8>>,
     to_dto: impl Fn(&V) -> D,
     label: &str,
@@ -49,54 +48,24 @@ where
     K: Ord + Copy + fmt::Display + Into<Uuid>,
     D: serde::Serialize,
 {
-    let dirty_count = dirty.len();
-    if dirty_count == 0 {
+    if !dirty.has_dirty() {
         return Ok(());
     }
-    tracing::info!(
-        count = dirty_count,
-        label,
-        "Flushing {} dirty entities",
-        label
-    );
+    tracing::debug!(count = dirty.len(), label, "Writing dirty {}", label);
 
-    let mut errors = 0;
-    for id in dirty.snapshot() {
-        if let Some(entity) = entities.get(&id) {
-            let dto = to_dto(entity);
-            match db.save_entity(table, id, &dto, label) {
-                Ok(_) => {
-                    tracing::debug!(entity_id = %id, "Saved {}", label);
-                    dirty.clean(&id);
-                }
-                Err(e) => {
-                    errors += 1;
-                    tracing::error!(entity_id = %id, error = %e, "Failed to save {}", label);
-                }
+    let mut table = batch.open_table(table)?;
+    for id in &dirty.ids {
+        let key = uuid_to_key((*id).into());
+        match entities.get(id) {
+            Some(entity) => {
+                table.insert(&key, &serialize(&to_dto(entity))?)?;
+                tracing::trace!(entity_id = %id, "Saved {}", label);
             }
-        } else {
-            match db.delete_entity(table, id, label) {
-                Ok(_) => {
-                    tracing::debug!(entity_id = %id, "Deleted {}", label);
-                    dirty.clean(&id);
-                }
-                Err(e) => {
-                    errors += 1;
-                    tracing::error!(entity_id = %id, error = %e, "Failed to delete {}", label);
-                }
+            None => {
+                table.remove(&key)?;
+                tracing::trace!(entity_id = %id, "Deleted {}", label);
             }
         }
     }
-    if errors > 0 {
-        tracing::warn!(errors, label, "Some {} failed to persist", label);
-    } else {
-        tracing::info!(
-            count = dirty_count,
-            label,
-            "Flushed {} {} successfully",
-            dirty_count,
-            label
-        );
-    }
     Ok(())
 }
@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+
+/// Before and after state of one entity across an undoable edit. `None`
+/// means the entity did not exist.
+#[derive(Debug, Clone)]
+pub struct Change<V> {
+    pub before: Option<V>,
+    pub after: Option<V>,
+}
+
+/// Records the state of entities before their first change while an
+/// undoable edit is in progress. Mutators call `touch` before changing an
+/// entity; outside of a recording it does nothing.
+#[derive(Debug, Clone)]
+pub(crate) struct Journal<K: Ord + Copy, V: Clone> {
+    before: Option<BTreeMap<K, Option<V>>>,
+}
+
+impl<K: Ord + Copy, V: Clone> Default for Journal<K, V> {
+    fn default() -> Self {
+        Self { before: None }
+    }
+}
+
+impl<K: Ord + Copy, V: Clone> Journal<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts recording, discarding any unfinished recording.
+    pub fn begin(&mut self) {
+        self.before = Some(BTreeMap::new());
+    }
+
+    /// Remembers the current state of `id` unless it was already touched.
+    pub fn touch(&mut self, id: K, entities: &BTreeMap<K, V>) {
+        if let Some(before) = &mut self.before {
+            before
+                .entry(id)
+                .or_insert_with(|| entities.get(&id).cloned());
+        }
+    }
+
+    /// Stops recording and pairs every touched entity with its current state.
+    pub fn finish(&mut self, entities: &BTreeMap<K, V>) -> BTreeMap<K, Change<V>> {
+        self.before
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, before)| {
+                let after = entities.get(&id).cloned();
+                (id, Change { before, after })
+            })
+            .collect()
+    }
+}
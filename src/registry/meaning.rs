@@ -1,7 +1,9 @@
 use crate::models::{CefrLevel, Meaning, MeaningId, PartOfSpeech, TagId, WordId};
 use crate::persistence::db::MEANINGS_TABLE;
-use crate::persistence::{DbError, MeaningDto};
-use crate::registry::dirty::{DirtyTracker, flush_registry};
+use crate::persistence::{DbError, MeaningDto, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use chrono::Utc;
 use either::Either;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -9,6 +11,7 @@ use std::collections::{BTreeMap, BTreeSet};
 pub struct MeaningRegistry {
     pub(crate) meanings: BTreeMap<MeaningId, Meaning>,
     pub(crate) dirty: DirtyTracker<MeaningId>,
+    pub(crate) journal: Journal<MeaningId, Meaning>,
     pub(crate) by_word: BTreeMap<WordId, BTreeSet<MeaningId>>,
     pub(crate) by_tag: BTreeMap<TagId, BTreeSet<MeaningId>>,
 }
@@ -18,6 +21,7 @@ impl MeaningRegistry {
         Self {
             meanings: BTreeMap::new(),
             dirty: DirtyTracker::new(),
+            journal: Journal::new(),
             by_word: BTreeMap::new(),
             by_tag: BTreeMap::new(),
         }
@@ -27,6 +31,7 @@ impl MeaningRegistry {
     pub fn add(&mut self, meaning: Meaning) {
         let meaning_id = meaning.id;
         let word_id = meaning.word_id;
+        self.journal.touch(meaning_id, &self.meanings);
 
         self.meanings.insert(meaning_id, meaning);
         self.dirty.mark(meaning_id);
@@ -44,11 +49,19 @@ impl MeaningRegistry {
         self.meanings.get(&id)
     }
 
+    /// Returns a meaning for editing, marking it dirty and bumping
+    /// `updated_at`. Tags must be changed through `add_tag`/`remove_tag` to
+    /// keep the tag index in sync.
     pub fn get_mut(&mut self, id: MeaningId) -> Option<&mut Meaning> {
-        self.meanings.get_mut(&id)
+        self.journal.touch(id, &self.meanings);
+        let meaning = self.meanings.get_mut(&id)?;
+        meaning.updated_at = Utc::now();
+        self.dirty.mark(id);
+        Some(meaning)
     }
 
     pub fn delete(&mut self, id: MeaningId) -> bool {
+        self.journal.touch(id, &self.meanings);
         if let Some(meaning) = self.meanings.remove(&id) {
             self.dirty.mark(id);
 
@@ -76,6 +89,7 @@ impl MeaningRegistry {
     pub fn delete_by_word(&mut self, word_id: WordId) {
         if let Some(meaning_ids) = self.by_word.remove(&word_id) {
             for meaning_id in meaning_ids {
+                self.journal.touch(meaning_id, &self.meanings);
                 self.dirty.mark(meaning_id);
                 if let Some(meaning) = self.meanings.remove(&meaning_id) {
                     for tag_id in meaning.tag_ids {
@@ -91,6 +105,14 @@ impl MeaningRegistry {
         }
     }
 
+    /// Puts a meaning back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: MeaningId, meaning: Option<Meaning>) {
+        self.delete(id);
+        if let Some(meaning) = meaning {
+            self.add(meaning);
+        }
+    }
+
     // Iterators
     pub fn iter(&self) -> impl Iterator<Item = (&MeaningId, &Meaning)> {
         self.meanings.iter()
@@ -161,8 +183,10 @@ impl MeaningRegistry {
 
     // Tag management
     pub fn add_tag(&mut self, meaning_id: MeaningId, tag_id: TagId) -> bool {
+        self.journal.touch(meaning_id, &self.meanings);
         if let Some(meaning) = self.meanings.get_mut(&meaning_id) {
             meaning.tag_ids.insert(tag_id);
+            meaning.updated_at = Utc::now();
             self.by_tag.entry(tag_id).or_default().insert(meaning_id);
             self.dirty.mark(meaning_id);
             true
@@ -172,9 +196,13 @@ impl MeaningRegistry {
     }
 
     pub fn remove_tag(&mut self, meaning_id: MeaningId, tag_id: TagId) -> bool {
+        self.journal.touch(meaning_id, &self.meanings);
         let mut removed = false;
         if let Some(meaning) = self.meanings.get_mut(&meaning_id) {
             removed = meaning.tag_ids.remove(&tag_id);
+            if removed {
+                meaning.updated_at = Utc::now();
+            }
         }
         if removed {
             self.dirty.mark(meaning_id);
@@ -196,6 +224,10 @@ impl MeaningRegistry {
             Ok(items) => {
                 for (id, mut dto) in items {
                     dto.id = id;
+                    // Persist backfilled timestamps for legacy records
+                    if dto.created_at.is_none() || dto.updated_at.is_none() {
+                        self.dirty.mark(MeaningId(id));
+                    }
                     let meaning = Meaning::from(dto);
                     self.meanings.insert(meaning.id, meaning.clone());
                     self.by_word
@@ -215,12 +247,12 @@ impl MeaningRegistry {
         tracing::debug!(count = loaded, "Loaded meanings from database");
     }
 
-    /// Flush all dirty entities to the database
-    pub fn flush_dirty(&mut self, db: &crate::persistence::Db) -> Result<(), DbError> {
-        flush_registry(
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
             &self.meanings,
-            &mut self.dirty,
-            db,
+            &self.dirty,
+            batch,
             MEANINGS_TABLE,
             |m| MeaningDto::from(m),
             "meaning",
@@ -231,4 +263,9 @@ impl MeaningRegistry {
     pub fn has_dirty(&self) -> bool {
         self.dirty.has_dirty()
     }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
 }
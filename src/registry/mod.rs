@@ -1,16 +1,24 @@
 pub mod cloze;
 pub(crate) mod dirty;
+pub(crate) mod journal;
 pub mod meaning;
 pub mod model;
 pub mod provider;
 pub mod queue;
+pub mod review;
+pub mod schedule;
 pub mod tag;
+pub mod trash;
 pub mod word;
 
 pub use self::cloze::ClozeRegistry;
+pub use self::journal::Change;
 pub use self::meaning::MeaningRegistry;
 pub use self::model::ModelRegistry;
 pub use self::provider::ProviderRegistry;
 pub use self::queue::{QueueItem, QueueItemStatus, QueueRegistry};
+pub use self::review::ReviewRegistry;
+pub use self::schedule::ScheduleRegistry;
 pub use self::tag::TagRegistry;
+pub use self::trash::TrashRegistry;
 pub use self::word::WordRegistry;
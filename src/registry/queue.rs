@@ -1,11 +1,16 @@
-// use crate::persistence::DbError;
-use crate::models::{MeaningId, WordId};
-use std::collections::{BTreeMap, BTreeSet};
+use crate::models::{Cloze, ClozeId, MeaningId, WordId};
+use crate::persistence::db::QUEUE_TABLE;
+use crate::persistence::{DbError, QueueItemDto, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum QueueItemStatus {
     Pending,
     Processing,
+    /// Candidates are waiting to be accepted or rejected
+    Review,
     Completed,
     Failed(String),
 }
@@ -16,6 +21,8 @@ pub struct QueueItem {
     pub meaning_id: MeaningId,
     pub status: QueueItemStatus,
     pub selected: bool,
+    /// Generated clozes not yet added to the cloze registry
+    pub candidates: Vec<Cloze>,
 }
 
 impl QueueItem {
@@ -25,28 +32,39 @@ impl QueueItem {
             meaning_id,
             status: QueueItemStatus::Pending,
             selected: true,
+            candidates: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct QueueRegistry {
-    items: BTreeMap<WordId, QueueItem>,
-    dirty_ids: BTreeSet<WordId>,
+    pub(crate) items: BTreeMap<WordId, QueueItem>,
+    pub(crate) dirty: DirtyTracker<WordId>,
+    pub(crate) journal: Journal<WordId, QueueItem>,
 }
 
 impl QueueRegistry {
     pub fn new() -> Self {
         Self {
             items: BTreeMap::new(),
-            dirty_ids: BTreeSet::new(),
+            dirty: DirtyTracker::new(),
+            journal: Journal::new(),
         }
     }
 
     pub fn enqueue(&mut self, meaning_id: MeaningId) {
         let item = QueueItem::new(meaning_id);
+        self.journal.touch(item.id, &self.items);
         self.items.insert(item.id, item.clone());
-        self.dirty_ids.insert(item.id);
+        self.dirty.mark(item.id);
+    }
+
+    /// Inserts an item as-is, replacing any item with the same id.
+    pub fn insert(&mut self, item: QueueItem) {
+        self.journal.touch(item.id, &self.items);
+        self.dirty.mark(item.id);
+        self.items.insert(item.id, item);
     }
 
     pub fn get_item(&self, id: WordId) -> Option<&QueueItem> {
@@ -84,59 +102,136 @@ impl QueueRegistry {
     }
 
     pub fn remove(&mut self, id: WordId) {
+        self.journal.touch(id, &self.items);
         if self.items.remove(&id).is_some() {
-            self.dirty_ids.insert(id);
+            self.dirty.mark(id);
+        }
+    }
+
+    /// Puts an item back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: WordId, item: Option<QueueItem>) {
+        self.remove(id);
+        if let Some(item) = item {
+            self.insert(item);
         }
     }
 
     pub fn select(&mut self, id: WordId) {
+        self.journal.touch(id, &self.items);
         if let Some(item) = self.items.get_mut(&id) {
-            item.selected = true
+            item.selected = true;
+            self.dirty.mark(id);
         }
     }
 
     pub fn deselect(&mut self, id: WordId) {
+        self.journal.touch(id, &self.items);
         if let Some(item) = self.items.get_mut(&id) {
-            item.selected = false
+            item.selected = false;
+            self.dirty.mark(id);
         }
     }
 
     pub fn select_all(&mut self) {
-        for (_, item) in self.items.iter_mut() {
-            if item.status == QueueItemStatus::Pending {
-                item.selected = true;
-            }
+        for id in self.pending_ids() {
+            self.select(id);
         }
     }
 
     pub fn deselect_all(&mut self) {
-        for (_, item) in self.items.iter_mut() {
-            if item.status == QueueItemStatus::Pending {
-                item.selected = false;
-            }
+        for id in self.pending_ids() {
+            self.deselect(id);
         }
     }
 
+    fn pending_ids(&self) -> Vec<WordId> {
+        self.items
+            .values()
+            .filter(|item| item.status == QueueItemStatus::Pending)
+            .map(|item| item.id)
+            .collect()
+    }
+
     pub fn set_processing(&mut self, id: WordId) {
+        self.journal.touch(id, &self.items);
         if let Some(item) = self.items.get_mut(&id) {
             item.status = QueueItemStatus::Processing;
-            self.dirty_ids.insert(id);
+            self.dirty.mark(id);
         }
     }
 
     pub fn set_completed(&mut self, id: WordId) {
+        self.journal.touch(id, &self.items);
         if let Some(item) = self.items.get_mut(&id) {
             item.status = QueueItemStatus::Completed;
             item.selected = false;
-            self.dirty_ids.insert(id);
+            self.dirty.mark(id);
+        }
+    }
+
+    /// Stores generated candidates for review. An item without candidates
+    /// is completed.
+    pub fn set_candidates(&mut self, id: WordId, candidates: Vec<Cloze>) {
+        self.journal.touch(id, &self.items);
+        if let Some(item) = self.items.get_mut(&id) {
+            item.status = if candidates.is_empty() {
+                QueueItemStatus::Completed
+            } else {
+                QueueItemStatus::Review
+            };
+            item.candidates = candidates;
+            item.selected = false;
+            self.dirty.mark(id);
+        }
+    }
+
+    /// Removes a candidate, returning it. The item completes once its last
+    /// candidate has been taken.
+    pub fn take_candidate(&mut self, id: WordId, cloze_id: ClozeId) -> Option<Cloze> {
+        let index = self
+            .items
+            .get(&id)?
+            .candidates
+            .iter()
+            .position(|c| c.id == cloze_id)?;
+        self.journal.touch(id, &self.items);
+        let item = self.items.get_mut(&id)?;
+        let cloze = item.candidates.remove(index);
+        if item.candidates.is_empty() {
+            item.status = QueueItemStatus::Completed;
         }
+        self.dirty.mark(id);
+        Some(cloze)
+    }
+
+    /// Replaces a candidate in place. Returns false if it was already taken.
+    pub fn replace_candidate(&mut self, id: WordId, cloze_id: ClozeId, cloze: Cloze) -> bool {
+        if !self
+            .items
+            .get(&id)
+            .is_some_and(|item| item.candidates.iter().any(|c| c.id == cloze_id))
+        {
+            return false;
+        }
+        self.journal.touch(id, &self.items);
+        let Some(slot) = self
+            .items
+            .get_mut(&id)
+            .and_then(|item| item.candidates.iter_mut().find(|c| c.id == cloze_id))
+        else {
+            return false;
+        };
+        *slot = cloze;
+        self.dirty.mark(id);
+        true
     }
 
     pub fn set_failed(&mut self, id: WordId, error: String) {
+        self.journal.touch(id, &self.items);
         if let Some(item) = self.items.get_mut(&id) {
             item.status = QueueItemStatus::Failed(error);
             item.selected = false;
-            self.dirty_ids.insert(id);
+            self.dirty.mark(id);
         }
     }
 
@@ -149,10 +244,145 @@ impl QueueRegistry {
             .collect();
 
         for id in &completed_ids {
-            self.dirty_ids.insert(*id);
+            self.journal.touch(*id, &self.items);
+            self.dirty.mark(*id);
         }
 
         self.items
             .retain(|_, item| item.status != QueueItemStatus::Completed);
     }
+
+    // Persistence
+    /// Load all queue items from database.
+    ///
+    /// Items interrupted while `Processing` are reset to `Pending` so the
+    /// batch can resume.
+    pub fn load_all(&mut self, db: &crate::persistence::Db) {
+        let count = self.items.len();
+        let mut resumed = 0;
+        match db.iter_entities::<QueueItemDto>(QUEUE_TABLE) {
+            Ok(items) => {
+                for (id, mut dto) in items {
+                    dto.id = id;
+                    let mut item = QueueItem::from(dto);
+                    if item.status == QueueItemStatus::Processing {
+                        item.status = QueueItemStatus::Pending;
+                        self.dirty.mark(item.id);
+                        resumed += 1;
+                    }
+                    self.items.insert(item.id, item);
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, source = "queue_registry", "Failed to load queue items from database");
+            }
+        }
+        let loaded = self.items.len() - count;
+        tracing::debug!(count = loaded, resumed, "Loaded queue items from database");
+    }
+
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
+            &self.items,
+            &self.dirty,
+            batch,
+            QUEUE_TABLE,
+            |i| QueueItemDto::from(i),
+            "queue item",
+        )
+    }
+
+    /// Check if there are any dirty entities
+    pub fn has_dirty(&self) -> bool {
+        self.dirty.has_dirty()
+    }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Db;
+    use test_case::test_case;
+
+    fn flush(queue: &QueueRegistry, db: &Db) {
+        let batch = db.batch().unwrap();
+        queue.write_dirty(&batch).unwrap();
+        batch.commit().unwrap();
+    }
+
+    #[test_case(QueueItemStatus::Pending, QueueItemStatus::Pending; "pending")]
+    #[test_case(QueueItemStatus::Processing, QueueItemStatus::Pending; "processing resumes")]
+    #[test_case(QueueItemStatus::Review, QueueItemStatus::Review; "review")]
+    #[test_case(QueueItemStatus::Completed, QueueItemStatus::Completed; "completed")]
+    #[test_case(QueueItemStatus::Failed("timeout".to_string()), QueueItemStatus::Failed("timeout".to_string()); "failed")]
+    fn test_status_round_trip(status: QueueItemStatus, expected: QueueItemStatus) {
+        let db = Db::in_memory().unwrap();
+        let mut queue = QueueRegistry::new();
+        let mut item = QueueItem::new(MeaningId::new());
+        item.status = status;
+        let id = item.id;
+        queue.insert(item);
+        flush(&queue, &db);
+
+        let mut loaded = QueueRegistry::new();
+        loaded.load_all(&db);
+        assert_eq!(loaded.get_item(id).unwrap().status, expected);
+    }
+
+    fn candidate(meaning_id: MeaningId, answer: &str) -> Cloze {
+        Cloze::builder()
+            .meaning_id(meaning_id)
+            .segments(vec![crate::models::ClozeSegment::Blank(answer.to_string())])
+            .build()
+    }
+
+    #[test]
+    fn test_candidates_complete_item_when_taken() {
+        let mut queue = QueueRegistry::new();
+        let item = QueueItem::new(MeaningId::new());
+        let (id, meaning_id) = (item.id, item.meaning_id);
+        queue.insert(item);
+
+        let (a, b) = (candidate(meaning_id, "a"), candidate(meaning_id, "b"));
+        let (a_id, b_id) = (a.id, b.id);
+        queue.set_candidates(id, vec![a, b]);
+        assert_eq!(queue.get_item(id).unwrap().status, QueueItemStatus::Review);
+
+        let replacement = candidate(meaning_id, "c");
+        let c_id = replacement.id;
+        assert!(queue.replace_candidate(id, b_id, replacement));
+        assert!(queue.take_candidate(id, b_id).is_none());
+
+        assert_eq!(queue.take_candidate(id, a_id).unwrap().id, a_id);
+        assert_eq!(queue.get_item(id).unwrap().status, QueueItemStatus::Review);
+        assert_eq!(queue.take_candidate(id, c_id).unwrap().id, c_id);
+        assert_eq!(
+            queue.get_item(id).unwrap().status,
+            QueueItemStatus::Completed
+        );
+    }
+
+    #[test]
+    fn test_candidates_round_trip() {
+        let db = Db::in_memory().unwrap();
+        let mut queue = QueueRegistry::new();
+        let item = QueueItem::new(MeaningId::new());
+        let (id, meaning_id) = (item.id, item.meaning_id);
+        queue.insert(item);
+        queue.set_candidates(id, vec![candidate(meaning_id, "a")]);
+        flush(&queue, &db);
+
+        let mut loaded = QueueRegistry::new();
+        loaded.load_all(&db);
+        let item = loaded.get_item(id).unwrap();
+        assert_eq!(item.status, QueueItemStatus::Review);
+        assert_eq!(item.candidates.len(), 1);
+        assert_eq!(item.candidates[0].render_answers(), "a");
+    }
 }
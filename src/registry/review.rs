@@ -0,0 +1,158 @@
+use crate::models::{ClozeId, ReviewId, ReviewLog};
+use crate::persistence::db::REVIEWS_TABLE;
+use crate::persistence::{DbError, ReviewLogDto, WriteBatch};
+use crate::registry::ClozeRegistry;
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Practice review history, indexed by cloze.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewRegistry {
+    pub(crate) reviews: BTreeMap<ReviewId, ReviewLog>,
+    pub(crate) dirty: DirtyTracker<ReviewId>,
+    pub(crate) journal: Journal<ReviewId, ReviewLog>,
+    pub(crate) by_cloze: BTreeMap<ClozeId, BTreeSet<ReviewId>>,
+}
+
+impl ReviewRegistry {
+    pub fn new() -> Self {
+        Self {
+            reviews: BTreeMap::new(),
+            dirty: DirtyTracker::new(),
+            journal: Journal::new(),
+            by_cloze: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, log: ReviewLog) {
+        let (id, cloze_id) = (log.id, log.cloze_id);
+        self.journal.touch(id, &self.reviews);
+        self.reviews.insert(id, log);
+        self.dirty.mark(id);
+        self.by_cloze.entry(cloze_id).or_default().insert(id);
+    }
+
+    pub fn get(&self, id: ReviewId) -> Option<&ReviewLog> {
+        self.reviews.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ReviewId, &ReviewLog)> {
+        self.reviews.iter()
+    }
+
+    /// Returns the reviews of a cloze, oldest first.
+    pub fn history(&self, cloze_id: ClozeId) -> Vec<&ReviewLog> {
+        let mut logs: Vec<&ReviewLog> = self
+            .by_cloze
+            .get(&cloze_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.reviews.get(id))
+            .collect();
+        logs.sort_by_key(|log| log.reviewed_at);
+        logs
+    }
+
+    pub fn delete(&mut self, id: ReviewId) -> bool {
+        self.journal.touch(id, &self.reviews);
+        if let Some(log) = self.reviews.remove(&id) {
+            self.dirty.mark(id);
+            if let Some(ids) = self.by_cloze.get_mut(&log.cloze_id) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.by_cloze.remove(&log.cloze_id);
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn delete_by_cloze(&mut self, cloze_id: ClozeId) {
+        if let Some(review_ids) = self.by_cloze.remove(&cloze_id) {
+            for review_id in review_ids {
+                self.journal.touch(review_id, &self.reviews);
+                self.dirty.mark(review_id);
+                self.reviews.remove(&review_id);
+            }
+        }
+    }
+
+    /// Removes the history of clozes that no longer exist.
+    pub fn prune_orphans(&mut self, cloze_registry: &ClozeRegistry) -> usize {
+        let orphans: Vec<ClozeId> = self
+            .by_cloze
+            .keys()
+            .filter(|id| !cloze_registry.exists(**id))
+            .copied()
+            .collect();
+        for cloze_id in &orphans {
+            self.delete_by_cloze(*cloze_id);
+        }
+        orphans.len()
+    }
+
+    /// Puts a review back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: ReviewId, log: Option<ReviewLog>) {
+        self.delete(id);
+        if let Some(log) = log {
+            self.add(log);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.reviews.len()
+    }
+
+    pub fn count_by_cloze(&self, cloze_id: ClozeId) -> usize {
+        self.by_cloze.get(&cloze_id).map(|s| s.len()).unwrap_or(0)
+    }
+
+    // Persistence
+    /// Load all reviews from database
+    pub fn load_all(&mut self, db: &crate::persistence::Db) {
+        let count = self.reviews.len();
+        match db.iter_entities::<ReviewLogDto>(REVIEWS_TABLE) {
+            Ok(items) => {
+                for (id, mut dto) in items {
+                    dto.id = id;
+                    let log = ReviewLog::from(dto);
+                    self.by_cloze
+                        .entry(log.cloze_id)
+                        .or_default()
+                        .insert(log.id);
+                    self.reviews.insert(log.id, log);
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, source = "review_registry", "Failed to load reviews from database");
+            }
+        }
+        let loaded = self.reviews.len() - count;
+        tracing::debug!(count = loaded, "Loaded reviews from database");
+    }
+
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
+            &self.reviews,
+            &self.dirty,
+            batch,
+            REVIEWS_TABLE,
+            |r| ReviewLogDto::from(r),
+            "review",
+        )
+    }
+
+    /// Check if there are any dirty entities
+    pub fn has_dirty(&self) -> bool {
+        self.dirty.has_dirty()
+    }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
@@ -0,0 +1,137 @@
+use crate::models::{CardSchedule, ClozeId};
+use crate::persistence::db::SCHEDULES_TABLE;
+use crate::persistence::{CardScheduleDto, DbError, WriteBatch};
+use crate::registry::ClozeRegistry;
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Review schedules keyed by cloze. Clozes without an entry have never
+/// been reviewed and are treated as new (always due).
+#[derive(Debug, Default, Clone)]
+pub struct ScheduleRegistry {
+    pub(crate) schedules: BTreeMap<ClozeId, CardSchedule>,
+    pub(crate) dirty: DirtyTracker<ClozeId>,
+    pub(crate) journal: Journal<ClozeId, CardSchedule>,
+}
+
+impl ScheduleRegistry {
+    pub fn new() -> Self {
+        Self {
+            schedules: BTreeMap::new(),
+            dirty: DirtyTracker::new(),
+            journal: Journal::new(),
+        }
+    }
+
+    /// Inserts or replaces the schedule for a cloze.
+    pub fn upsert(&mut self, schedule: CardSchedule) {
+        let id = schedule.cloze_id;
+        self.journal.touch(id, &self.schedules);
+        self.schedules.insert(id, schedule);
+        self.dirty.mark(id);
+    }
+
+    pub fn get(&self, cloze_id: ClozeId) -> Option<&CardSchedule> {
+        self.schedules.get(&cloze_id)
+    }
+
+    /// Returns the stored schedule or a fresh one for a never-reviewed cloze.
+    pub fn get_or_new(&self, cloze_id: ClozeId, now: DateTime<Utc>) -> CardSchedule {
+        self.schedules
+            .get(&cloze_id)
+            .cloned()
+            .unwrap_or_else(|| CardSchedule::builder().cloze_id(cloze_id).due(now).build())
+    }
+
+    pub fn delete(&mut self, cloze_id: ClozeId) -> bool {
+        self.journal.touch(cloze_id, &self.schedules);
+        if self.schedules.remove(&cloze_id).is_some() {
+            self.dirty.mark(cloze_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes schedules whose cloze no longer exists.
+    pub fn prune_orphans(&mut self, cloze_registry: &ClozeRegistry) -> usize {
+        let orphans: Vec<ClozeId> = self
+            .schedules
+            .keys()
+            .filter(|id| !cloze_registry.exists(**id))
+            .copied()
+            .collect();
+        for id in &orphans {
+            self.delete(*id);
+        }
+        orphans.len()
+    }
+
+    /// Puts a schedule back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, cloze_id: ClozeId, schedule: Option<CardSchedule>) {
+        self.delete(cloze_id);
+        if let Some(schedule) = schedule {
+            self.upsert(schedule);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ClozeId, &CardSchedule)> {
+        self.schedules.iter()
+    }
+
+    pub fn count(&self) -> usize {
+        self.schedules.len()
+    }
+
+    /// Returns true if the cloze is due at `now` (new clozes are always due).
+    pub fn is_due(&self, cloze_id: ClozeId, now: DateTime<Utc>) -> bool {
+        self.schedules
+            .get(&cloze_id)
+            .map(|s| s.is_due(now))
+            .unwrap_or(true)
+    }
+
+    // Persistence
+    /// Load all schedules from database
+    pub fn load_all(&mut self, db: &crate::persistence::Db) {
+        let count = self.schedules.len();
+        match db.iter_entities::<CardScheduleDto>(SCHEDULES_TABLE) {
+            Ok(items) => {
+                for (id, mut dto) in items {
+                    dto.cloze_id = id;
+                    let schedule = CardSchedule::from(dto);
+                    self.schedules.insert(schedule.cloze_id, schedule);
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, source = "schedule_registry", "Failed to load schedules from database");
+            }
+        }
+        let loaded = self.schedules.len() - count;
+        tracing::debug!(count = loaded, "Loaded schedules from database");
+    }
+
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
+            &self.schedules,
+            &self.dirty,
+            batch,
+            SCHEDULES_TABLE,
+            |s| CardScheduleDto::from(s),
+            "schedule",
+        )
+    }
+
+    /// Check if there are any dirty entities
+    pub fn has_dirty(&self) -> bool {
+        self.dirty.has_dirty()
+    }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
@@ -1,13 +1,15 @@
 use crate::models::{Tag, TagId};
 use crate::persistence::db::TAGS_TABLE;
-use crate::persistence::{DbError, TagDto};
-use crate::registry::dirty::{DirtyTracker, flush_registry};
+use crate::persistence::{DbError, TagDto, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
 use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Clone)]
 pub struct TagRegistry {
     pub(crate) tags: BTreeMap<TagId, Tag>,
     pub(crate) dirty: DirtyTracker<TagId>,
+    pub(crate) journal: Journal<TagId, Tag>,
 }
 
 impl TagRegistry {
@@ -15,10 +17,12 @@ impl TagRegistry {
         Self {
             tags: BTreeMap::new(),
             dirty: DirtyTracker::new(),
+            journal: Journal::new(),
         }
     }
 
     pub fn add(&mut self, tag: Tag) {
+        self.journal.touch(tag.id, &self.tags);
         self.tags.insert(tag.id, tag.clone());
         self.dirty.mark(tag.id);
     }
@@ -27,11 +31,16 @@ impl TagRegistry {
         self.tags.get(&id)
     }
 
+    /// Returns a tag for editing, marking it dirty.
     pub fn get_mut(&mut self, id: TagId) -> Option<&mut Tag> {
-        self.tags.get_mut(&id)
+        self.journal.touch(id, &self.tags);
+        let tag = self.tags.get_mut(&id)?;
+        self.dirty.mark(id);
+        Some(tag)
     }
 
     pub fn delete(&mut self, id: TagId) -> bool {
+        self.journal.touch(id, &self.tags);
         if self.tags.remove(&id).is_some() {
             self.dirty.mark(id);
             true
@@ -40,6 +49,14 @@ impl TagRegistry {
         }
     }
 
+    /// Puts a tag back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: TagId, tag: Option<Tag>) {
+        self.delete(id);
+        if let Some(tag) = tag {
+            self.add(tag);
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&TagId, &Tag)> {
         self.tags.iter()
     }
@@ -52,6 +69,88 @@ impl TagRegistry {
         self.tags.contains_key(&id)
     }
 
+    /// Finds a tag by its `/`-separated path of names, e.g. `Travel/Food`.
+    /// Names are matched case-insensitively.
+    pub fn find_by_path(&self, path: &str) -> Option<TagId> {
+        let mut current: Option<TagId> = None;
+        for name in path_segments(path) {
+            current = Some(self.find_child(current, name)?);
+        }
+        current
+    }
+
+    /// Returns the `/`-separated path of names from the root to a tag.
+    pub fn path(&self, id: TagId) -> Option<String> {
+        let mut names = Vec::new();
+        let mut current = Some(id);
+        while let Some(tag) = current.and_then(|id| self.tags.get(&id)) {
+            // Guard against parent cycles in corrupted data
+            if names.len() > self.tags.len() {
+                return None;
+            }
+            names.push(tag.name.as_str());
+            current = tag.parent_id;
+        }
+        if names.is_empty() {
+            return None;
+        }
+        names.reverse();
+        Some(names.join("/"))
+    }
+
+    /// Returns a tag followed by all tags nested under it, depth first.
+    /// Empty if the tag does not exist.
+    pub fn with_descendants(&self, id: TagId) -> Vec<TagId> {
+        let mut found = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            // Guard against child cycles in corrupted data
+            if found.contains(&id) {
+                continue;
+            }
+            if let Some(tag) = self.tags.get(&id) {
+                found.push(id);
+                stack.extend(tag.children_ids.iter().rev());
+            }
+        }
+        found
+    }
+
+    /// Resolves a `/`-separated tag path, creating any missing tags.
+    /// Returns `None` for an empty path.
+    pub fn get_or_create_path(&mut self, path: &str) -> Option<TagId> {
+        let mut current: Option<TagId> = None;
+        for name in path_segments(path) {
+            let id = match self.find_child(current, name) {
+                Some(id) => id,
+                None => {
+                    let tag = Tag::builder()
+                        .name(name.to_string())
+                        .parent_id(current)
+                        .build();
+                    let id = tag.id;
+                    self.add(tag);
+                    if let Some(parent) = current.and_then(|p| self.get_mut(p)) {
+                        parent.children_ids.insert(id);
+                    }
+                    id
+                }
+            };
+            current = Some(id);
+        }
+        current
+    }
+
+    /// Finds a direct child of `parent_id` (a root tag for `None`) by name,
+    /// case-insensitively.
+    pub(crate) fn find_child(&self, parent_id: Option<TagId>, name: &str) -> Option<TagId> {
+        let name = name.to_lowercase();
+        self.tags
+            .values()
+            .find(|t| t.parent_id == parent_id && t.name.to_lowercase() == name)
+            .map(|t| t.id)
+    }
+
     // Persistence
     /// Load all tags from database
     pub fn load_all(&mut self, db: &crate::persistence::Db) {
@@ -72,12 +171,12 @@ impl TagRegistry {
         tracing::debug!(count = loaded, "Loaded tags from database");
     }
 
-    /// Flush all dirty entities to the database
-    pub fn flush_dirty(&mut self, db: &crate::persistence::Db) -> Result<(), DbError> {
-        flush_registry(
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
             &self.tags,
-            &mut self.dirty,
-            db,
+            &self.dirty,
+            batch,
             TAGS_TABLE,
             |t| TagDto::from(t),
             "tag",
@@ -88,4 +187,46 @@ impl TagRegistry {
     pub fn has_dirty(&self) -> bool {
         self.dirty.has_dirty()
     }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
+
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').map(str::trim).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_descendants_walks_the_tree() {
+        let mut registry = TagRegistry::new();
+        let food = registry.get_or_create_path("Travel/Food").unwrap();
+        let street = registry.get_or_create_path("Travel/Food/Street").unwrap();
+        let transport = registry.get_or_create_path("Travel/Transport").unwrap();
+        let travel = registry.find_by_path("travel").unwrap();
+        registry.get_or_create_path("Work");
+
+        let mut expected = vec![travel, food, street, transport];
+        let mut found = registry.with_descendants(travel);
+        assert_eq!(found[0], travel);
+        expected.sort();
+        found.sort();
+        assert_eq!(found, expected);
+        assert_eq!(registry.with_descendants(street), vec![street]);
+        assert!(registry.with_descendants(TagId::new()).is_empty());
+    }
+
+    #[test]
+    fn test_with_descendants_survives_cycles() {
+        let mut registry = TagRegistry::new();
+        let a = registry.get_or_create_path("A").unwrap();
+        let b = registry.get_or_create_path("A/B").unwrap();
+        registry.get_mut(b).unwrap().children_ids.insert(a);
+        assert_eq!(registry.with_descendants(a), vec![a, b]);
+    }
 }
@@ -0,0 +1,123 @@
+use crate::models::{TrashEntry, TrashId};
+use crate::persistence::db::TRASH_TABLE;
+use crate::persistence::{DbError, TrashEntryDto, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Deleted entities awaiting restore or purge.
+#[derive(Debug, Clone, Default)]
+pub struct TrashRegistry {
+    pub(crate) entries: BTreeMap<TrashId, TrashEntry>,
+    pub(crate) dirty: DirtyTracker<TrashId>,
+    pub(crate) journal: Journal<TrashId, TrashEntry>,
+}
+
+impl TrashRegistry {
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            dirty: DirtyTracker::new(),
+            journal: Journal::new(),
+        }
+    }
+
+    pub fn add(&mut self, entry: TrashEntry) {
+        self.journal.touch(entry.id, &self.entries);
+        self.dirty.mark(entry.id);
+        self.entries.insert(entry.id, entry);
+    }
+
+    pub fn get(&self, id: TrashId) -> Option<&TrashEntry> {
+        self.entries.get(&id)
+    }
+
+    /// Returns all entries, most recently deleted first.
+    pub fn newest_first(&self) -> Vec<&TrashEntry> {
+        let mut entries: Vec<&TrashEntry> = self.entries.values().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+        entries
+    }
+
+    /// Removes an entry and returns it.
+    pub fn take(&mut self, id: TrashId) -> Option<TrashEntry> {
+        self.journal.touch(id, &self.entries);
+        let entry = self.entries.remove(&id)?;
+        self.dirty.mark(id);
+        Some(entry)
+    }
+
+    /// Permanently removes entries deleted before `cutoff` and returns how
+    /// many were removed.
+    pub fn purge_before(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let expired: Vec<TrashId> = self
+            .entries
+            .values()
+            .filter(|e| e.deleted_at < cutoff)
+            .map(|e| e.id)
+            .collect();
+        for id in &expired {
+            self.take(*id);
+        }
+        expired.len()
+    }
+
+    /// Puts an entry back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: TrashId, entry: Option<TrashEntry>) {
+        self.take(id);
+        if let Some(entry) = entry {
+            self.add(entry);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Persistence
+    /// Load all trash entries from database
+    pub fn load_all(&mut self, db: &crate::persistence::Db) {
+        let count = self.entries.len();
+        match db.iter_entities::<TrashEntryDto>(TRASH_TABLE) {
+            Ok(items) => {
+                for (id, mut dto) in items {
+                    dto.id = id;
+                    let entry = TrashEntry::from(dto);
+                    self.entries.insert(entry.id, entry);
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, source = "trash_registry", "Failed to load trash from database");
+            }
+        }
+        let loaded = self.entries.len() - count;
+        tracing::debug!(count = loaded, "Loaded trash entries from database");
+    }
+
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
+            &self.entries,
+            &self.dirty,
+            batch,
+            TRASH_TABLE,
+            |e| TrashEntryDto::from(e),
+            "trash entry",
+        )
+    }
+
+    /// Check if there are any dirty entities
+    pub fn has_dirty(&self) -> bool {
+        self.dirty.has_dirty()
+    }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
@@ -1,15 +1,18 @@
+use chrono::Utc;
 use langtag::LangTagBuf;
 
 use crate::models::{MeaningId, Word, WordId};
 use crate::persistence::db::WORDS_TABLE;
-use crate::persistence::{DbError, WordDto};
-use crate::registry::dirty::{DirtyTracker, flush_registry};
+use crate::persistence::{DbError, WordDto, WriteBatch};
+use crate::registry::dirty::{DirtyTracker, write_registry};
+use crate::registry::journal::Journal;
 use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Clone)]
 pub struct WordRegistry {
     pub(crate) words: BTreeMap<WordId, Word>,
     pub(crate) dirty: DirtyTracker<WordId>,
+    pub(crate) journal: Journal<WordId, Word>,
 }
 
 impl WordRegistry {
@@ -17,11 +20,13 @@ impl WordRegistry {
         Self {
             words: BTreeMap::new(),
             dirty: DirtyTracker::new(),
+            journal: Journal::new(),
         }
     }
 
     // CRUD
     pub fn add(&mut self, word: Word) {
+        self.journal.touch(word.id, &self.words);
         self.words.insert(word.id, word.clone());
         self.dirty.mark(word.id);
     }
@@ -30,11 +35,17 @@ impl WordRegistry {
         self.words.get(&id)
     }
 
+    /// Returns a word for editing, marking it dirty and bumping `updated_at`.
     pub fn get_mut(&mut self, id: WordId) -> Option<&mut Word> {
-        self.words.get_mut(&id)
+        self.journal.touch(id, &self.words);
+        let word = self.words.get_mut(&id)?;
+        word.updated_at = Utc::now();
+        self.dirty.mark(id);
+        Some(word)
     }
 
     pub fn delete(&mut self, id: WordId) -> bool {
+        self.journal.touch(id, &self.words);
         if self.words.remove(&id).is_some() {
             self.dirty.mark(id);
             true
@@ -43,6 +54,14 @@ impl WordRegistry {
         }
     }
 
+    /// Puts a word back into a recorded state; `None` removes it.
+    pub(crate) fn restore(&mut self, id: WordId, word: Option<Word>) {
+        self.delete(id);
+        if let Some(word) = word {
+            self.add(word);
+        }
+    }
+
     // Iterators
     pub fn iter(&self) -> impl Iterator<Item = (&WordId, &Word)> {
         self.words.iter()
@@ -58,9 +77,16 @@ impl WordRegistry {
     }
 
     pub fn exists_with_content(&self, content: &str) -> bool {
+        self.find_by_content(content).is_some()
+    }
+
+    /// Finds a word by content, ignoring case.
+    pub fn find_by_content(&self, content: &str) -> Option<WordId> {
+        let content = content.to_lowercase();
         self.words
             .iter()
-            .any(|(_, w)| w.content.to_lowercase() == content.to_lowercase())
+            .find(|(_, w)| w.content.to_lowercase() == content)
+            .map(|(id, _)| *id)
     }
 
     /// Create a new word with the given content and optional language.
@@ -91,8 +117,10 @@ impl WordRegistry {
 
     // Meaning ID management (syncs with MeaningRegistry)
     pub fn add_meaning(&mut self, word_id: WordId, meaning_id: MeaningId) -> bool {
+        self.journal.touch(word_id, &self.words);
         if let Some(word) = self.words.get_mut(&word_id) {
             word.meaning_ids.insert(meaning_id);
+            word.updated_at = Utc::now();
             self.dirty.mark(word_id);
             true
         } else {
@@ -101,9 +129,11 @@ impl WordRegistry {
     }
 
     pub fn remove_meaning(&mut self, word_id: WordId, meaning_id: MeaningId) -> bool {
+        self.journal.touch(word_id, &self.words);
         if let Some(word) = self.words.get_mut(&word_id) {
             let removed = word.meaning_ids.remove(&meaning_id);
             if removed {
+                word.updated_at = Utc::now();
                 self.dirty.mark(word_id);
             }
             removed
@@ -120,6 +150,10 @@ impl WordRegistry {
             Ok(items) => {
                 for (id, mut dto) in items {
                     dto.id = id;
+                    // Persist backfilled timestamps for legacy records
+                    if dto.created_at.is_none() || dto.updated_at.is_none() {
+                        self.dirty.mark(WordId(id));
+                    }
                     let word = Word::from(dto);
                     self.words.insert(word.id, word);
                 }
@@ -132,12 +166,12 @@ impl WordRegistry {
         tracing::debug!(count = loaded, "Loaded words from database");
     }
 
-    /// Flush all dirty entities to the database
-    pub fn flush_dirty(&mut self, db: &crate::persistence::Db) -> Result<(), DbError> {
-        flush_registry(
+    /// Write all dirty entities into a batch
+    pub fn write_dirty(&self, batch: &WriteBatch) -> Result<(), DbError> {
+        write_registry(
             &self.words,
-            &mut self.dirty,
-            db,
+            &self.dirty,
+            batch,
             WORDS_TABLE,
             |w| WordDto::from(w),
             "word",
@@ -148,4 +182,53 @@ impl WordRegistry {
     pub fn has_dirty(&self) -> bool {
         self.dirty.has_dirty()
     }
+
+    /// Forget dirty entities once the batch writing them has committed
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Db;
+    use crate::persistence::dto::LEGACY_TIMESTAMP;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_legacy_word_backfills_timestamps() {
+        let db = Db::in_memory().unwrap();
+        let id = Uuid::new_v4();
+        // Record layout before timestamps were added
+        let legacy = (
+            id,
+            "hello".to_string(),
+            Vec::<Uuid>::new(),
+            None::<LangTagBuf>,
+        );
+        db.save_entity(WORDS_TABLE, id, &legacy, "word").unwrap();
+
+        let mut registry = WordRegistry::new();
+        registry.load_all(&db);
+        let word = registry.get(WordId(id)).unwrap();
+        assert_eq!(word.created_at, LEGACY_TIMESTAMP);
+        assert_eq!(word.updated_at, LEGACY_TIMESTAMP);
+        assert!(registry.has_dirty());
+    }
+
+    #[test]
+    fn test_edit_bumps_updated_at() {
+        let mut registry = WordRegistry::new();
+        let mut word = Word::builder().content("hello".to_string()).build();
+        word.created_at = LEGACY_TIMESTAMP;
+        word.updated_at = LEGACY_TIMESTAMP;
+        let id = word.id;
+        registry.add(word);
+
+        registry.get_mut(id).unwrap().content = "hi".to_string();
+        let word = registry.get(id).unwrap();
+        assert_eq!(word.created_at, LEGACY_TIMESTAMP);
+        assert!(word.updated_at > LEGACY_TIMESTAMP);
+    }
 }
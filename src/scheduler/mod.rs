@@ -0,0 +1,183 @@
+//! Spaced-repetition scheduling for practice sessions.
+//!
+//! Each reviewed cloze carries a `CardSchedule` (due date, interval, ease,
+//! lapses). Practice outcomes are graded into a `ReviewGrade` and fed to a
+//! `Scheduler`, which computes the next schedule.
+
+pub mod sm2;
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::models::{CardSchedule, ClozeId, ReviewLog};
+use crate::registry::{ClozeRegistry, ScheduleRegistry};
+
+/// Answering faster than this per blank, all correct, counts as `Easy`.
+const EASY_TIME_PER_BLANK: Duration = Duration::from_secs(4);
+
+/// How well a cloze was recalled during review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReviewGrade {
+    /// Not recalled; the card lapses
+    Again,
+    /// Recalled with significant difficulty
+    Hard,
+    /// Recalled correctly
+    Good,
+    /// Recalled effortlessly
+    Easy,
+}
+
+impl ReviewGrade {
+    /// Grades a cloze from the number of correctly answered blanks.
+    ///
+    /// All blanks correct is `Good`, none correct is `Again`, and anything
+    /// in between is `Hard`.
+    pub fn from_blanks(correct: usize, total: usize) -> Self {
+        if total == 0 || correct == 0 {
+            ReviewGrade::Again
+        } else if correct >= total {
+            ReviewGrade::Good
+        } else {
+            ReviewGrade::Hard
+        }
+    }
+
+    /// Grades a logged review from its answers and response time.
+    ///
+    /// Like `from_blanks`, but a fully correct answer given quickly is `Easy`.
+    pub fn from_review(review: &ReviewLog) -> Self {
+        let total = review.answers.len();
+        let grade = Self::from_blanks(review.correct_count(), total);
+        if grade == ReviewGrade::Good && review.duration < EASY_TIME_PER_BLANK * total as u32 {
+            ReviewGrade::Easy
+        } else {
+            grade
+        }
+    }
+
+    /// Returns true if the grade counts as a successful recall.
+    pub fn is_pass(self) -> bool {
+        self != ReviewGrade::Again
+    }
+}
+
+/// Available scheduling algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scheduler {
+    /// SuperMemo 2
+    #[default]
+    Sm2,
+}
+
+impl Scheduler {
+    /// Computes the schedule that follows a review with the given grade.
+    pub fn review(
+        &self,
+        schedule: &CardSchedule,
+        grade: ReviewGrade,
+        now: DateTime<Utc>,
+    ) -> CardSchedule {
+        match self {
+            Scheduler::Sm2 => sm2::review(schedule, grade, now),
+        }
+    }
+}
+
+/// Returns the due clozes among `candidates`, most overdue first.
+///
+/// Never-reviewed clozes are due immediately and are ordered after
+/// previously reviewed cards so backlog reviews are not crowded out.
+pub fn due_clozes(
+    candidates: impl IntoIterator<Item = ClozeId>,
+    cloze_registry: &ClozeRegistry,
+    schedule_registry: &ScheduleRegistry,
+    now: DateTime<Utc>,
+) -> Vec<ClozeId> {
+    let mut due: Vec<(Option<DateTime<Utc>>, ClozeId)> = candidates
+        .into_iter()
+        .filter(|id| cloze_registry.exists(*id))
+        .filter(|id| schedule_registry.is_due(*id, now))
+        .map(|id| (schedule_registry.get(id).map(|s| s.due), id))
+        .collect();
+
+    due.sort_by(|(a_due, a_id), (b_due, b_id)| match (a_due, b_due) {
+        (Some(a), Some(b)) => a.cmp(b).then_with(|| a_id.cmp(b_id)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a_id.cmp(b_id),
+    });
+
+    due.into_iter().map(|(_, id)| id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Cloze, ClozeSegment, MeaningId, ReviewAnswer};
+    use test_case::test_case;
+
+    #[test_case(3, 3, ReviewGrade::Good; "all correct")]
+    #[test_case(1, 3, ReviewGrade::Hard; "partially correct")]
+    #[test_case(0, 3, ReviewGrade::Again; "none correct")]
+    #[test_case(0, 0, ReviewGrade::Again; "no blanks")]
+    fn test_grade_from_blanks(correct: usize, total: usize, expected: ReviewGrade) {
+        assert_eq!(ReviewGrade::from_blanks(correct, total), expected);
+    }
+
+    #[test_case(&[true, true], 3, ReviewGrade::Easy; "fast and correct")]
+    #[test_case(&[true, true], 20, ReviewGrade::Good; "slow and correct")]
+    #[test_case(&[true, false], 3, ReviewGrade::Hard; "fast but partial")]
+    #[test_case(&[], 1, ReviewGrade::Again; "no answers")]
+    fn test_grade_from_review(results: &[bool], secs: u64, expected: ReviewGrade) {
+        let review = ReviewLog::builder()
+            .cloze_id(ClozeId::new())
+            .answers(
+                results
+                    .iter()
+                    .map(|correct| ReviewAnswer {
+                        given: String::new(),
+                        correct: *correct,
+                    })
+                    .collect(),
+            )
+            .duration(Duration::from_secs(secs))
+            .build();
+        assert_eq!(ReviewGrade::from_review(&review), expected);
+    }
+
+    #[test]
+    fn test_due_clozes_orders_overdue_before_new() {
+        let now = Utc::now();
+        let mut clozes = ClozeRegistry::new();
+        let mut schedules = ScheduleRegistry::new();
+
+        let make = || {
+            Cloze::builder()
+                .meaning_id(MeaningId::new())
+                .segments(vec![ClozeSegment::Blank("a".to_string())])
+                .build()
+        };
+        let (new_cloze, overdue, future) = (make(), make(), make());
+        let ids = [new_cloze.id, overdue.id, future.id];
+        clozes.add(new_cloze);
+        clozes.add(overdue);
+        clozes.add(future);
+
+        schedules.upsert(
+            CardSchedule::builder()
+                .cloze_id(ids[1])
+                .due(now - chrono::Duration::days(2))
+                .build(),
+        );
+        schedules.upsert(
+            CardSchedule::builder()
+                .cloze_id(ids[2])
+                .due(now + chrono::Duration::days(2))
+                .build(),
+        );
+
+        let due = due_clozes(ids, &clozes, &schedules, now);
+        assert_eq!(due, vec![ids[1], ids[0]]);
+    }
+}
@@ -0,0 +1,100 @@
+//! SuperMemo 2 scheduling algorithm.
+//!
+//! See <https://super-memory.com/english/ol/sm2.htm>. Grades map to SM-2
+//! quality scores as Again = 1, Hard = 3, Good = 4, Easy = 5.
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::ReviewGrade;
+use crate::models::CardSchedule;
+
+/// Lower bound for the ease factor.
+pub const MIN_EASE: f32 = 1.3;
+
+fn quality(grade: ReviewGrade) -> f32 {
+    match grade {
+        ReviewGrade::Again => 1.0,
+        ReviewGrade::Hard => 3.0,
+        ReviewGrade::Good => 4.0,
+        ReviewGrade::Easy => 5.0,
+    }
+}
+
+/// Computes the next schedule after a review.
+pub fn review(schedule: &CardSchedule, grade: ReviewGrade, now: DateTime<Utc>) -> CardSchedule {
+    let q = quality(grade);
+    let mut next = schedule.clone();
+
+    if grade.is_pass() {
+        next.interval_days = match schedule.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => ((schedule.interval_days.max(1) as f32) * schedule.ease).round() as u32,
+        };
+        next.repetitions = schedule.repetitions + 1;
+    } else {
+        next.interval_days = 1;
+        next.repetitions = 0;
+        next.lapses = schedule.lapses + 1;
+    }
+
+    let ease = schedule.ease + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02));
+    next.ease = ease.max(MIN_EASE);
+    next.due = now + Duration::days(next.interval_days as i64);
+    next.last_reviewed = Some(now);
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ClozeId;
+    use test_case::test_case;
+
+    fn schedule(repetitions: u32, interval_days: u32, ease: f32) -> CardSchedule {
+        CardSchedule::builder()
+            .cloze_id(ClozeId::new())
+            .repetitions(repetitions)
+            .interval_days(interval_days)
+            .ease(ease)
+            .build()
+    }
+
+    #[test_case(0, 0, ReviewGrade::Good, 1; "first success")]
+    #[test_case(1, 1, ReviewGrade::Good, 6; "second success")]
+    #[test_case(2, 6, ReviewGrade::Good, 15; "third success grows by ease")]
+    #[test_case(5, 40, ReviewGrade::Again, 1; "lapse resets interval")]
+    fn test_review_interval(reps: u32, interval: u32, grade: ReviewGrade, expected: u32) {
+        let now = Utc::now();
+        let next = review(&schedule(reps, interval, 2.5), grade, now);
+        assert_eq!(next.interval_days, expected);
+        assert_eq!(next.due, now + Duration::days(expected as i64));
+        assert_eq!(next.last_reviewed, Some(now));
+    }
+
+    #[test_case(ReviewGrade::Again, 1.96; "again lowers ease")]
+    #[test_case(ReviewGrade::Hard, 2.36; "hard lowers ease")]
+    #[test_case(ReviewGrade::Good, 2.5; "good keeps ease")]
+    #[test_case(ReviewGrade::Easy, 2.6; "easy raises ease")]
+    fn test_review_ease(grade: ReviewGrade, expected: f32) {
+        let next = review(&schedule(2, 6, 2.5), grade, Utc::now());
+        assert!(
+            (next.ease - expected).abs() < 1e-4,
+            "ease was {}",
+            next.ease
+        );
+    }
+
+    #[test]
+    fn test_ease_never_below_minimum() {
+        let next = review(&schedule(0, 0, MIN_EASE), ReviewGrade::Again, Utc::now());
+        assert_eq!(next.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn test_lapse_counts() {
+        let next = review(&schedule(3, 15, 2.5), ReviewGrade::Again, Utc::now());
+        assert_eq!(next.lapses, 1);
+        assert_eq!(next.repetitions, 0);
+    }
+}
@@ -1,6 +1,9 @@
-use crate::config::AiConfig;
-use crate::models::{Cloze, Meaning, Model, ModelId, Provider, ProviderType, Word};
+use crate::config::AppConfig;
+use crate::models::{Cloze, Meaning, MeaningId, Model, ModelId, Provider, ProviderType, Word};
 use crate::registry::{ModelRegistry, ProviderRegistry};
+use crate::state::Model as StateModel;
+use crate::state::prompt::{self, PromptContext};
+use crate::state::validate::{self, ValidationError};
 use rig::agent::Agent;
 use rig::client::{self, CompletionClient};
 use rig::completion::Prompt;
@@ -22,8 +25,17 @@ pub enum GenerateError {
 
     #[error("No prompt response from LLM")]
     NoResponse,
+
+    #[error("Rejected after {attempts} attempt(s): {reason}")]
+    Rejected {
+        attempts: usize,
+        reason: ValidationError,
+    },
 }
 
+/// Requests made per generation before giving up on invalid output.
+pub const MAX_ATTEMPTS: usize = 3;
+
 #[derive(Clone)]
 pub enum AgentWrapper {
     OpenAI(Agent<openai::responses_api::ResponsesCompletionModel>),
@@ -51,13 +63,14 @@ impl GeneratorState {
         }
     }
 
-    /// Loads AI configuration (providers and models) into this state.
-    pub fn load_from_config(&mut self, config: &AiConfig) {
-        self.provider_registry.load_from_config(config);
-        self.model_registry.load_from_config(config);
+    /// Loads AI configuration (providers and models) into this state,
+    /// selecting the active profile's model.
+    pub fn load_from_config(&mut self, config: &AppConfig) {
+        self.provider_registry.load_from_config(&config.ai);
+        self.model_registry.load_from_config(&config.ai);
 
         // Use config's selected_model_id, or auto-select first model if none set
-        if let Some(selected_id) = config.selected_model_id {
+        if let Some(selected_id) = config.selected_model_id() {
             let model_id = ModelId(selected_id);
             // Validate the selected model exists
             if self.model_registry.get(model_id).is_some() {
@@ -106,6 +119,66 @@ impl GeneratorState {
     }
 }
 
+/// Everything needed to generate clozes for one meaning.
+#[derive(Debug, Clone)]
+pub struct GenerationRequest {
+    pub word: Word,
+    pub meaning: Meaning,
+    /// Template text with `prompt::PLACEHOLDERS`
+    pub template: String,
+    /// `/`-separated paths of the meaning's tags
+    pub tags: Vec<String>,
+    /// Candidates wanted
+    pub count: usize,
+}
+
+impl GenerationRequest {
+    /// Resolves the word, tags and prompt template for a meaning, using the
+    /// selected model's template settings.
+    pub fn new(model: &StateModel, meaning_id: MeaningId, count: usize) -> Result<Self, String> {
+        let Some(meaning) = model.meaning_registry.get(meaning_id) else {
+            tracing::error!(meaning_id = %meaning_id, "Meaning not found for queue item");
+            return Err("Meaning not found".to_string());
+        };
+        let Some(word) = model.word_registry.get(meaning.word_id) else {
+            tracing::error!(word_id = %meaning.word_id, "Word not found for meaning");
+            return Err("Word not found".to_string());
+        };
+        let tags: Vec<String> = meaning
+            .tag_ids
+            .iter()
+            .filter_map(|id| model.tag_registry.path(*id))
+            .collect();
+        let template = prompt::select_template(
+            &model.app_config.ai,
+            model.generator.selected_model_id.map(|id| id.0),
+            &tags,
+        );
+
+        Ok(Self {
+            word: word.clone(),
+            meaning: meaning.clone(),
+            template: template.to_string(),
+            tags,
+            count,
+        })
+    }
+
+    /// Renders the prompt asking for `count` sentences.
+    pub fn prompt(&self, count: usize) -> String {
+        let context = PromptContext {
+            word: self.word.content.clone(),
+            definition: self.meaning.definition.clone(),
+            pos: self.meaning.pos,
+            cefr: self.meaning.cefr_level,
+            language: self.word.language.as_ref().map(|l| l.to_string()),
+            tags: self.tags.clone(),
+            count,
+        };
+        prompt::render(&self.template, &context)
+    }
+}
+
 #[derive(Clone)]
 pub struct Generator {
     agent: AgentWrapper,
@@ -181,73 +254,111 @@ impl Generator {
         Self { agent }
     }
 
-    /// Generates a cloze deletion sentence for the given word and meaning.
+    /// Generates up to `request.count` candidate cloze sentences.
+    ///
+    /// Candidates failing validation are dropped and requested again, up to
+    /// `MAX_ATTEMPTS` requests. Fails only if no candidate was accepted.
     #[instrument(
-        skip(self),
+        skip(self, request),
         fields(
-            word.id = %word.id,
-            word.content = %word.content,
-            meaning.id = %meaning.id,
-            pos = %meaning.pos,
-            cefr_level = ?meaning.cefr_level
+            word.id = %request.word.id,
+            word.content = %request.word.content,
+            meaning.id = %request.meaning.id,
+            pos = %request.meaning.pos,
+            cefr_level = ?request.meaning.cefr_level
         )
     )]
-    pub async fn generate(&self, word: &Word, meaning: &Meaning) -> Result<Cloze, GenerateError> {
-        // Build CEFR level info if available
-        let cefr_info = match meaning.cefr_level {
-            Some(level) => format!(" (CEFR level: {})", level),
-            None => String::new(),
-        };
+    pub async fn generate(&self, request: &GenerationRequest) -> Result<Vec<Cloze>, GenerateError> {
+        let (word, meaning, count) = (&request.word, &request.meaning, request.count);
+        let mut accepted: Vec<Cloze> = Vec::new();
+        let mut last_error = None;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let prompt = request.prompt(count - accepted.len());
 
-        let prompt = format!(
-            r#"Generate a cloze deletion sentence for "{content}" with definition "{definition}" ({pos}){cefr_info}.
-Use brackets to mark the blank: [answer]
-Example: "The [cat] sat on the mat"
-Return ONLY the sentence."#,
-            content = word.content,
-            definition = meaning.definition,
-            pos = meaning.pos,
-            cefr_info = cefr_info
-        );
+            let start = std::time::Instant::now();
+            let response = self.prompt(&prompt).await?;
+            let elapsed = start.elapsed().as_millis();
+            tracing::debug!(elapsed_ms = elapsed, attempt, "LLM request completed");
 
-        let start = std::time::Instant::now();
-        let sentence = match &self.agent {
-            AgentWrapper::OpenAI(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::Anthropic(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::DeepSeek(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::Gemini(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::Ollama(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::Perplexity(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
-            AgentWrapper::XAI(a) => a
-                .prompt(&prompt)
-                .await
-                .map_err(|e| GenerateError::Provider(e.to_string()))?,
+            for sentence in parse_candidates(&response) {
+                let cloze = Cloze::builder()
+                    .meaning_id(meaning.id)
+                    .segments(Cloze::parse_from_sentence(validate::sanitize(&sentence)))
+                    .build();
+                match validate::validate(&cloze, word) {
+                    Ok(()) if accepted.len() < count => accepted.push(cloze),
+                    Ok(()) => {}
+                    Err(e) => {
+                        tracing::debug!(attempt, sentence = %sentence, reason = %e, "Rejected candidate");
+                        last_error = Some(e);
+                    }
+                }
+            }
+            if accepted.len() >= count {
+                break;
+            }
+        }
+
+        if accepted.is_empty() {
+            return Err(match last_error {
+                Some(reason) => GenerateError::Rejected {
+                    attempts: MAX_ATTEMPTS,
+                    reason,
+                },
+                None => GenerateError::NoResponse,
+            });
+        }
+        Ok(accepted)
+    }
+
+    async fn prompt(&self, prompt: &str) -> Result<String, GenerateError> {
+        let response = match &self.agent {
+            AgentWrapper::OpenAI(a) => a.prompt(prompt).await,
+            AgentWrapper::Anthropic(a) => a.prompt(prompt).await,
+            AgentWrapper::DeepSeek(a) => a.prompt(prompt).await,
+            AgentWrapper::Gemini(a) => a.prompt(prompt).await,
+            AgentWrapper::Ollama(a) => a.prompt(prompt).await,
+            AgentWrapper::Perplexity(a) => a.prompt(prompt).await,
+            AgentWrapper::XAI(a) => a.prompt(prompt).await,
         };
-        let elapsed = start.elapsed().as_millis();
-        tracing::debug!(elapsed_ms = elapsed, "LLM request completed");
-
-        let segments = Cloze::parse_from_sentence(&sentence);
-        Ok(Cloze::builder()
-            .meaning_id(meaning.id)
-            .segments(segments)
-            .build())
+        response.map_err(|e| GenerateError::Provider(e.to_string()))
+    }
+}
+
+/// Extracts candidate sentences from an LLM response.
+///
+/// Expects a JSON array of strings, possibly wrapped in prose or a code
+/// fence. Anything else is treated as a single sentence.
+fn parse_candidates(response: &str) -> Vec<String> {
+    let response = response.trim();
+    let array = response
+        .find('[')
+        .zip(response.rfind(']'))
+        .filter(|(start, end)| start < end)
+        .and_then(|(start, end)| serde_json::from_str::<Vec<String>>(&response[start..=end]).ok());
+
+    match array {
+        Some(sentences) => sentences
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None if response.is_empty() => Vec::new(),
+        None => vec![response.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(r#"["The [cat] sat.", "A [dog] ran."]"#, &["The [cat] sat.", "A [dog] ran."]; "json array")]
+    #[test_case("```json\n[\"I [run] daily.\"]\n```", &["I [run] daily."]; "fenced array")]
+    #[test_case("The [cat] sat on the mat", &["The [cat] sat on the mat"]; "plain sentence")]
+    #[test_case(r#"["", "  ", "We [swim]."]"#, &["We [swim]."]; "blank entries dropped")]
+    #[test_case("   ", &[]; "empty response")]
+    fn test_parse_candidates(response: &str, expected: &[&str]) {
+        assert_eq!(parse_candidates(response), expected);
     }
 }
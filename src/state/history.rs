@@ -0,0 +1,370 @@
+//! Undo/redo history of library edits.
+//!
+//! An edit is recorded by running a mutation inside `Model::record`: every
+//! registry journals the state of each entity before its first change, and
+//! the edit keeps those states together with the states afterwards. Undo
+//! puts the "before" states back, redo the "after" states, so cascades such
+//! as deleting a word with its meanings and clozes revert as one step.
+//! Queue items are journaled too, so undoing an accepted candidate puts it
+//! back up for review.
+
+use std::collections::BTreeMap;
+
+use crate::config::constants::history::MAX_UNDO;
+use crate::models::{CardSchedule, Cloze, ClozeId, Meaning, MeaningId, ReviewId, ReviewLog};
+use crate::models::{Tag, TagId, TrashEntry, TrashId, Word, WordId};
+use crate::registry::{Change, QueueItem};
+use crate::state::Model;
+
+/// Entity changes made by one undoable edit.
+#[derive(Debug, Clone, Default)]
+pub struct Edit {
+    pub words: BTreeMap<WordId, Change<Word>>,
+    pub meanings: BTreeMap<MeaningId, Change<Meaning>>,
+    pub tags: BTreeMap<TagId, Change<Tag>>,
+    pub clozes: BTreeMap<ClozeId, Change<Cloze>>,
+    pub schedules: BTreeMap<ClozeId, Change<CardSchedule>>,
+    pub reviews: BTreeMap<ReviewId, Change<ReviewLog>>,
+    pub trash: BTreeMap<TrashId, Change<TrashEntry>>,
+    pub queue: BTreeMap<WordId, Change<QueueItem>>,
+}
+
+impl Edit {
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+            && self.meanings.is_empty()
+            && self.tags.is_empty()
+            && self.clozes.is_empty()
+            && self.schedules.is_empty()
+            && self.reviews.is_empty()
+            && self.trash.is_empty()
+            && self.queue.is_empty()
+    }
+
+    /// Returns true if the edit removed a word, meaning, tag or cloze.
+    pub fn deletes(&self) -> bool {
+        fn removed<K, V>(changes: &BTreeMap<K, Change<V>>) -> bool {
+            changes
+                .values()
+                .any(|c| c.before.is_some() && c.after.is_none())
+        }
+        removed(&self.words)
+            || removed(&self.meanings)
+            || removed(&self.tags)
+            || removed(&self.clozes)
+    }
+
+    /// Counts of changed entities, e.g. "1 word(s), 3 cloze(s)".
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (self.words.len(), "word(s)"),
+            (self.meanings.len(), "meaning(s)"),
+            (self.tags.len(), "tag(s)"),
+            (self.clozes.len(), "cloze(s)"),
+            (self.queue.len(), "queue item(s)"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+        if parts.is_empty() && !self.trash.is_empty() {
+            "trash".to_string()
+        } else if parts.is_empty() {
+            "review history".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Undo and redo stacks. Recording a new edit clears the redo stack.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.undo.push(edit);
+        if self.undo.len() > MAX_UNDO {
+            self.undo.remove(0);
+        }
+    }
+}
+
+/// Which side of an edit to restore.
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Before,
+    After,
+}
+
+/// Drops entities that were created and removed within the same edit.
+fn changed<K: Ord, V>(changes: BTreeMap<K, Change<V>>) -> BTreeMap<K, Change<V>> {
+    changes
+        .into_iter()
+        .filter(|(_, c)| c.before.is_some() || c.after.is_some())
+        .collect()
+}
+
+fn pick<V: Clone>(change: &Change<V>, side: Side) -> Option<V> {
+    match side {
+        Side::Before => change.before.clone(),
+        Side::After => change.after.clone(),
+    }
+}
+
+impl Model {
+    /// Runs `f` as one undoable edit and returns its result along with the
+    /// recorded edit. Edits that change nothing are not recorded.
+    pub fn record<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> (R, Option<&Edit>) {
+        self.word_registry.journal.begin();
+        self.meaning_registry.journal.begin();
+        self.tag_registry.journal.begin();
+        self.cloze_registry.journal.begin();
+        self.schedule_registry.journal.begin();
+        self.review_registry.journal.begin();
+        self.trash_registry.journal.begin();
+        self.queue_registry.journal.begin();
+
+        let result = f(self);
+
+        let edit = Edit {
+            words: changed(self.word_registry.journal.finish(&self.word_registry.words)),
+            meanings: changed(
+                self.meaning_registry
+                    .journal
+                    .finish(&self.meaning_registry.meanings),
+            ),
+            tags: changed(self.tag_registry.journal.finish(&self.tag_registry.tags)),
+            clozes: changed(
+                self.cloze_registry
+                    .journal
+                    .finish(&self.cloze_registry.clozes),
+            ),
+            schedules: changed(
+                self.schedule_registry
+                    .journal
+                    .finish(&self.schedule_registry.schedules),
+            ),
+            reviews: changed(
+                self.review_registry
+                    .journal
+                    .finish(&self.review_registry.reviews),
+            ),
+            trash: changed(
+                self.trash_registry
+                    .journal
+                    .finish(&self.trash_registry.entries),
+            ),
+            queue: changed(
+                self.queue_registry
+                    .journal
+                    .finish(&self.queue_registry.items),
+            ),
+        };
+        if edit.is_empty() {
+            return (result, None);
+        }
+        tracing::debug!(summary = %edit.summary(), "Recorded undoable edit");
+        self.history.push(edit);
+        (result, self.history.undo.last())
+    }
+
+    /// Reverts the most recent edit and returns its summary.
+    pub fn undo(&mut self) -> Option<String> {
+        let edit = self.history.undo.pop()?;
+        self.apply(&edit, Side::Before);
+        let summary = edit.summary();
+        self.history.redo.push(edit);
+        tracing::info!(%summary, "Undid edit");
+        Some(summary)
+    }
+
+    /// Reapplies the most recently undone edit and returns its summary.
+    pub fn redo(&mut self) -> Option<String> {
+        let edit = self.history.redo.pop()?;
+        self.apply(&edit, Side::After);
+        let summary = edit.summary();
+        self.history.undo.push(edit);
+        tracing::info!(%summary, "Redid edit");
+        Some(summary)
+    }
+
+    fn apply(&mut self, edit: &Edit, side: Side) {
+        for (id, change) in &edit.words {
+            self.word_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.meanings {
+            self.meaning_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.tags {
+            self.tag_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.clozes {
+            self.cloze_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.schedules {
+            self.schedule_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.reviews {
+            self.review_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.trash {
+            self.trash_registry.restore(*id, pick(change, side));
+        }
+        for (id, change) in &edit.queue {
+            self.queue_registry.restore(*id, pick(change, side));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::models::{ClozeSegment, PartOfSpeech};
+    use crate::persistence::Db;
+    use crate::registry::QueueItemStatus;
+    use chrono::Utc;
+
+    fn library() -> (Model, WordId, MeaningId, ClozeId) {
+        let mut model = Model::new(Db::in_memory().unwrap(), AppConfig::default());
+        let word_id = model.word_registry.create_word("hello", None).unwrap();
+        let meaning_id = model
+            .meaning_registry
+            .create_meaning(word_id, "a greeting", PartOfSpeech::Noun, None)
+            .unwrap();
+        model.word_registry.add_meaning(word_id, meaning_id);
+        let cloze = Cloze::builder()
+            .meaning_id(meaning_id)
+            .segments(vec![ClozeSegment::Blank("hello".to_string())])
+            .build();
+        let cloze_id = cloze.id;
+        model.cloze_registry.add(cloze);
+        model.schedule_registry.upsert(
+            CardSchedule::builder()
+                .cloze_id(cloze_id)
+                .due(Utc::now())
+                .build(),
+        );
+        (model, word_id, meaning_id, cloze_id)
+    }
+
+    fn delete_word(model: &mut Model, word_id: WordId) {
+        model.record(|m| {
+            let meaning_ids: Vec<MeaningId> = m
+                .meaning_registry
+                .iter_by_word(word_id)
+                .map(|(id, _)| *id)
+                .collect();
+            for meaning_id in meaning_ids {
+                m.cloze_registry.delete_by_meaning(meaning_id);
+            }
+            m.meaning_registry.delete_by_word(word_id);
+            m.word_registry.delete(word_id);
+            m.schedule_registry.prune_orphans(&m.cloze_registry);
+        });
+    }
+
+    #[test]
+    fn test_undo_restores_cascaded_delete() {
+        let (mut model, word_id, meaning_id, cloze_id) = library();
+        delete_word(&mut model, word_id);
+        assert!(model.history.can_undo());
+        assert_eq!(model.cloze_registry.count(), 0);
+
+        model.undo().unwrap();
+        assert!(model.word_registry.exists(word_id));
+        assert_eq!(model.meaning_registry.iter_by_word(word_id).count(), 1);
+        assert_eq!(model.cloze_registry.count_by_meaning(meaning_id), 1);
+        assert!(model.schedule_registry.get(cloze_id).is_some());
+
+        model.redo().unwrap();
+        assert!(!model.word_registry.exists(word_id));
+        assert!(!model.meaning_registry.exists(meaning_id));
+        assert!(model.schedule_registry.get(cloze_id).is_none());
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let (mut model, word_id, meaning_id, _) = library();
+        let tag_id = model.tag_registry.get_or_create_path("Travel").unwrap();
+        model.record(|m| m.meaning_registry.add_tag(meaning_id, tag_id));
+        model.undo().unwrap();
+        assert!(model.meaning_registry.iter_by_tag(tag_id).next().is_none());
+        assert!(model.history.can_redo());
+
+        model.record(|m| {
+            m.word_registry
+                .get_mut(word_id)
+                .map(|w| w.content = "hi".into())
+        });
+        assert!(!model.history.can_redo());
+        model.undo().unwrap();
+        assert_eq!(model.word_registry.get(word_id).unwrap().content, "hello");
+    }
+
+    #[test]
+    fn test_undo_accepted_candidate_returns_it_to_review() {
+        let (mut model, _, meaning_id, _) = library();
+        let item = QueueItem::new(meaning_id);
+        let item_id = item.id;
+        model.queue_registry.insert(item);
+        let candidate = Cloze::builder()
+            .meaning_id(meaning_id)
+            .segments(vec![ClozeSegment::Blank("hello".to_string())])
+            .build();
+        let cloze_id = candidate.id;
+        model
+            .queue_registry
+            .set_candidates(item_id, vec![candidate]);
+
+        model.record(|m| {
+            let cloze = m.queue_registry.take_candidate(item_id, cloze_id).unwrap();
+            m.cloze_registry.add(cloze);
+        });
+        assert_eq!(
+            model.queue_registry.get_item(item_id).unwrap().status,
+            QueueItemStatus::Completed
+        );
+
+        model.undo().unwrap();
+        assert!(model.cloze_registry.get(cloze_id).is_none());
+        let item = model.queue_registry.get_item(item_id).unwrap();
+        assert_eq!(item.status, QueueItemStatus::Review);
+        assert_eq!(item.candidates[0].id, cloze_id);
+
+        model.redo().unwrap();
+        assert!(model.cloze_registry.get(cloze_id).is_some());
+        assert!(
+            model
+                .queue_registry
+                .get_item(item_id)
+                .unwrap()
+                .candidates
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_read_only_edit_is_not_recorded() {
+        let (mut model, word_id, _, _) = library();
+        model.record(|m| m.word_registry.exists(word_id));
+        assert!(!model.history.can_undo());
+    }
+}
@@ -3,9 +3,17 @@
 //! Contains Model (data + business logic) and sub-modules.
 
 pub mod generator;
+pub mod history;
 pub mod model;
+pub mod prompt;
 pub mod queue;
+pub mod session;
+pub mod trash;
+pub mod validate;
 
-pub use self::generator::{Generator, GeneratorState};
+pub use self::generator::{GenerationRequest, Generator, GeneratorState};
+pub use self::history::{Edit, History};
 pub use self::model::Model;
-pub use self::queue::{QueueGenerationResult, process};
+pub use self::queue::{QueueGenerationResult, process, regenerate};
+pub use self::session::{Recovery, SessionMarker};
+pub use self::trash::Restored;
@@ -2,10 +2,16 @@
 //!
 //! Contains all data registries and business logic, separated from UI state.
 
+use std::path::Path;
+
 use crate::config::AppConfig;
-use crate::persistence::Db;
-use crate::registry::{ClozeRegistry, MeaningRegistry, QueueRegistry, TagRegistry, WordRegistry};
+use crate::persistence::{Db, DbError, Snapshot, SnapshotReason, SnapshotStore};
+use crate::registry::{
+    ClozeRegistry, MeaningRegistry, QueueRegistry, ReviewRegistry, ScheduleRegistry, TagRegistry,
+    TrashRegistry, WordRegistry,
+};
 use crate::state::generator::GeneratorState;
+use crate::state::history::History;
 use std::sync::Arc;
 
 #[derive(Debug)]
@@ -15,7 +21,11 @@ pub struct Model {
     pub tag_registry: TagRegistry,
     pub cloze_registry: ClozeRegistry,
     pub queue_registry: QueueRegistry,
+    pub schedule_registry: ScheduleRegistry,
+    pub review_registry: ReviewRegistry,
+    pub trash_registry: TrashRegistry,
     pub generator: GeneratorState,
+    pub history: History,
     pub db: Db,
     pub app_config: Arc<AppConfig>,
 }
@@ -28,7 +38,11 @@ impl Model {
             tag_registry: TagRegistry::new(),
             cloze_registry: ClozeRegistry::new(),
             queue_registry: QueueRegistry::new(),
+            schedule_registry: ScheduleRegistry::new(),
+            review_registry: ReviewRegistry::new(),
+            trash_registry: TrashRegistry::new(),
             generator: GeneratorState::new(),
+            history: History::new(),
             db,
             app_config: Arc::new(app_config),
         }
@@ -40,14 +54,25 @@ impl Model {
         self.meaning_registry.load_all(&self.db);
         self.tag_registry.load_all(&self.db);
         self.cloze_registry.load_all(&self.db);
+        self.queue_registry.load_all(&self.db);
+        self.schedule_registry.load_all(&self.db);
+        self.review_registry.load_all(&self.db);
+        self.trash_registry.load_all(&self.db);
     }
 
-    /// Flush all dirty entities across registries to the database
-    pub fn flush_all(&mut self) -> Result<(), crate::persistence::DbError> {
+    /// Flush all dirty entities across registries to the database.
+    ///
+    /// Everything is written in one transaction: either all changes are
+    /// committed, or none are and the dirty flags stay set for a retry.
+    pub fn flush_all(&mut self) -> Result<(), DbError> {
         let total_dirty = self.word_registry.dirty.len()
             + self.meaning_registry.dirty.len()
             + self.tag_registry.dirty.len()
-            + self.cloze_registry.dirty.len();
+            + self.cloze_registry.dirty.len()
+            + self.queue_registry.dirty.len()
+            + self.schedule_registry.dirty.len()
+            + self.review_registry.dirty.len()
+            + self.trash_registry.dirty.len();
 
         if total_dirty == 0 {
             tracing::debug!("No dirty entities to flush");
@@ -56,20 +81,101 @@ impl Model {
 
         tracing::info!("Starting flush: {} total dirty entities", total_dirty);
 
-        self.word_registry.flush_dirty(&self.db)?;
-        self.meaning_registry.flush_dirty(&self.db)?;
-        self.tag_registry.flush_dirty(&self.db)?;
-        self.cloze_registry.flush_dirty(&self.db)?;
+        let batch = self.db.batch()?;
+        self.word_registry.write_dirty(&batch)?;
+        self.meaning_registry.write_dirty(&batch)?;
+        self.tag_registry.write_dirty(&batch)?;
+        self.cloze_registry.write_dirty(&batch)?;
+        self.queue_registry.write_dirty(&batch)?;
+        self.schedule_registry.write_dirty(&batch)?;
+        self.review_registry.write_dirty(&batch)?;
+        self.trash_registry.write_dirty(&batch)?;
+        batch.commit()?;
+
+        self.word_registry.clear_dirty();
+        self.meaning_registry.clear_dirty();
+        self.tag_registry.clear_dirty();
+        self.cloze_registry.clear_dirty();
+        self.queue_registry.clear_dirty();
+        self.schedule_registry.clear_dirty();
+        self.review_registry.clear_dirty();
+        self.trash_registry.clear_dirty();
 
         tracing::info!("Completed flush: {} total dirty entities", total_dirty);
         Ok(())
     }
 
+    /// Saves pending changes, snapshots the database and prunes snapshots
+    /// beyond the configured retention.
+    pub fn snapshot(&mut self, reason: SnapshotReason) -> Result<Snapshot, DbError> {
+        self.flush_all()?;
+        let store = SnapshotStore::new(&self.app_config.library_dir());
+        let snapshot = store.create(&self.db, reason)?;
+        store.prune(self.app_config.backup_retention);
+        Ok(snapshot)
+    }
+
+    /// Replaces the library with the snapshot at `path` and reloads every
+    /// registry. The current library is snapshotted first.
+    pub fn restore_snapshot(&mut self, path: &Path) -> Result<(), DbError> {
+        self.flush_all()?;
+        let store = SnapshotStore::new(&self.app_config.library_dir());
+        store.create(&self.db, SnapshotReason::Restore)?;
+        self.db.restore_from(path)?;
+        // Prune only now, so the snapshot being restored cannot be removed
+        store.prune(self.app_config.backup_retention);
+
+        self.word_registry = WordRegistry::new();
+        self.meaning_registry = MeaningRegistry::new();
+        self.tag_registry = TagRegistry::new();
+        self.cloze_registry = ClozeRegistry::new();
+        self.queue_registry = QueueRegistry::new();
+        self.schedule_registry = ScheduleRegistry::new();
+        self.review_registry = ReviewRegistry::new();
+        self.trash_registry = TrashRegistry::new();
+        self.history = History::new();
+        self.load_all();
+        Ok(())
+    }
+
     /// Check if any registry has dirty entities
     pub fn has_dirty(&self) -> bool {
         self.word_registry.has_dirty()
             || self.meaning_registry.has_dirty()
             || self.tag_registry.has_dirty()
             || self.cloze_registry.has_dirty()
+            || self.queue_registry.has_dirty()
+            || self.schedule_registry.has_dirty()
+            || self.review_registry.has_dirty()
+            || self.trash_registry.has_dirty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PartOfSpeech;
+
+    #[test]
+    fn test_flush_all_commits_and_clears_dirty() {
+        let mut model = Model::new(Db::in_memory().unwrap(), AppConfig::default());
+        let word_id = model.word_registry.create_word("hello", None).unwrap();
+        let meaning_id = model
+            .meaning_registry
+            .create_meaning(word_id, "a greeting", PartOfSpeech::Noun, None)
+            .unwrap();
+        model.word_registry.add_meaning(word_id, meaning_id);
+        let removed = model.word_registry.create_word("bye", None).unwrap();
+        model.flush_all().unwrap();
+        assert!(!model.has_dirty());
+
+        model.word_registry.delete(removed);
+        model.flush_all().unwrap();
+
+        let mut loaded = Model::new(model.db, AppConfig::default());
+        loaded.load_all();
+        assert_eq!(loaded.word_registry.count(), 1);
+        assert!(loaded.meaning_registry.exists(meaning_id));
+        assert!(!loaded.has_dirty());
     }
 }
@@ -0,0 +1,186 @@
+//! Prompt templates for cloze generation.
+//!
+//! Templates come from `AiConfig::templates` and are chosen per tag, then
+//! per model, falling back to `DEFAULT_TEMPLATE`.
+
+use uuid::Uuid;
+
+use crate::config::AiConfig;
+use crate::models::{CefrLevel, PartOfSpeech};
+
+/// Built-in prompt used when no template applies.
+pub const DEFAULT_TEMPLATE: &str = r#"Generate {count} different cloze deletion sentences for "{word}" with definition "{definition}" ({pos}, CEFR level: {cefr}).
+Use brackets to mark the blank: [answer]
+Example: "The [cat] sat on the mat"
+Return ONLY a JSON array of {count} strings."#;
+
+/// Placeholder names accepted in templates.
+pub const PLACEHOLDERS: &[&str] = &[
+    "word",
+    "definition",
+    "pos",
+    "cefr",
+    "language",
+    "tags",
+    "count",
+];
+
+/// Values substituted into a template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptContext {
+    pub word: String,
+    pub definition: String,
+    pub pos: PartOfSpeech,
+    pub cefr: Option<CefrLevel>,
+    pub language: Option<String>,
+    /// `/`-separated tag paths
+    pub tags: Vec<String>,
+    pub count: usize,
+}
+
+impl PromptContext {
+    /// Example values for previewing templates.
+    pub fn sample(count: usize) -> Self {
+        Self {
+            word: "serendipity".to_string(),
+            definition: "the occurrence of events by chance in a happy way".to_string(),
+            pos: PartOfSpeech::Noun,
+            cefr: Some(CefrLevel::C1),
+            language: Some("en".to_string()),
+            tags: vec!["Vocabulary/Rare".to_string()],
+            count,
+        }
+    }
+
+    /// Value of a placeholder. Unset CEFR level and language render as
+    /// `any`, no tags as an empty string.
+    fn value(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "word" => self.word.clone(),
+            "definition" => self.definition.clone(),
+            "pos" => self.pos.to_string(),
+            "cefr" => self
+                .cefr
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "any".to_string()),
+            "language" => self.language.clone().unwrap_or_else(|| "any".to_string()),
+            "tags" => self.tags.join(", "),
+            "count" => self.count.to_string(),
+            _ => return None,
+        })
+    }
+}
+
+/// Substitutes `{placeholder}`s in a single pass, so values containing
+/// braces are never expanded. Unknown placeholders are kept verbatim.
+pub fn render(template: &str, context: &PromptContext) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}') {
+            Some(close) => match context.value(&after[..close]) {
+                Some(value) => {
+                    out.push_str(&value);
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            },
+            None => {
+                out.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Picks the template for a meaning.
+///
+/// The first configured template with a tag covering one of `tag_paths`
+/// wins (a template tagged `Travel` also covers `Travel/Food`), then the
+/// model's template, then `DEFAULT_TEMPLATE`.
+pub fn select_template<'a>(
+    ai_config: &'a AiConfig,
+    model_id: Option<Uuid>,
+    tag_paths: &[String],
+) -> &'a str {
+    let by_tag = ai_config.templates.iter().find(|t| {
+        t.tags
+            .iter()
+            .any(|tag| tag_paths.iter().any(|path| covers(tag, path)))
+    });
+    let by_model = || {
+        let model = ai_config.models.iter().find(|m| Some(m.id) == model_id)?;
+        let template_id = model.template_id?;
+        ai_config.templates.iter().find(|t| t.id == template_id)
+    };
+    by_tag
+        .or_else(by_model)
+        .map(|t| t.template.as_str())
+        .unwrap_or(DEFAULT_TEMPLATE)
+}
+
+/// Returns true if tag path `tag` is `path` or one of its ancestors.
+fn covers(tag: &str, path: &str) -> bool {
+    let tag = tag.trim().trim_matches('/').to_lowercase();
+    let path = path.to_lowercase();
+    !tag.is_empty()
+        && (path == tag
+            || path
+                .strip_prefix(&tag)
+                .is_some_and(|rest| rest.starts_with('/')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ModelConfig, PromptTemplateConfig};
+    use test_case::test_case;
+
+    #[test_case("{word} ({pos})", "serendipity (Noun)"; "basic")]
+    #[test_case("{cefr}/{language}/{tags}/{count}", "C1/en/Vocabulary/Rare/2"; "all fields")]
+    #[test_case("{unknown} {word", "{unknown} {word"; "unknown and unclosed kept")]
+    fn test_render(template: &str, expected: &str) {
+        assert_eq!(render(template, &PromptContext::sample(2)), expected);
+    }
+
+    #[test]
+    fn test_render_does_not_expand_values() {
+        let mut context = PromptContext::sample(1);
+        context.definition = "uses {word} literally".to_string();
+        assert_eq!(render("{definition}", &context), "uses {word} literally");
+    }
+
+    fn template(template: &str, tags: &[&str]) -> PromptTemplateConfig {
+        PromptTemplateConfig {
+            id: Uuid::new_v4(),
+            name: template.to_string(),
+            template: template.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test_case(&["Travel/Food"], true, "travel"; "tag covers descendant")]
+    #[test_case(&["Travelling"], true, "model"; "tag prefix is not ancestor")]
+    #[test_case(&["Travelling"], false, DEFAULT_TEMPLATE; "default")]
+    fn test_select_template(paths: &[&str], model_has_template: bool, expected: &str) {
+        let by_model = template("model", &[]);
+        let model = ModelConfig {
+            template_id: model_has_template.then_some(by_model.id),
+            ..ModelConfig::default()
+        };
+        let ai = AiConfig {
+            templates: vec![template("travel", &["Travel"]), by_model],
+            models: vec![model.clone()],
+            ..AiConfig::default()
+        };
+        let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+        assert_eq!(select_template(&ai, Some(model.id), &paths), expected);
+    }
+}
@@ -1,67 +1,75 @@
 use crate::message::Message;
-use crate::models::{Cloze, WordId};
-use crate::registry::{QueueItemStatus, QueueRegistry, WordRegistry};
-use crate::state::generator::Generator;
+use crate::models::{Cloze, ClozeId, WordId};
+use crate::registry::QueueItemStatus;
+use crate::state::Model;
+use crate::state::generator::GenerationRequest;
 use iced::Task;
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum QueueGenerationResult {
-    Success { item_id: WordId, cloze: Cloze },
-    Failed { item_id: WordId, error: String },
+    Success {
+        item_id: WordId,
+        candidates: Vec<Cloze>,
+    },
+    Failed {
+        item_id: WordId,
+        error: String,
+    },
+    /// A single candidate was regenerated
+    Regenerated {
+        item_id: WordId,
+        replaces: ClozeId,
+        cloze: Cloze,
+    },
+    RegenerateFailed {
+        item_id: WordId,
+        error: String,
+    },
 }
 
-/// Process pending queue items using the LLM generator.
+/// Process pending queue items using the selected model's generator,
+/// requesting the configured number of candidates per item.
 /// Returns an empty task if no generator is available.
-pub fn process(
-    queue_registry: &mut QueueRegistry,
-    generator: &Option<Arc<Generator>>,
-    word_registry: &WordRegistry,
-    meaning_registry: &crate::registry::MeaningRegistry,
-) -> Task<Message> {
-    let Some(generator) = generator else {
+pub fn process(model: &mut Model) -> Task<Message> {
+    let Some(generator) = model.generator.generator() else {
         tracing::warn!("No generator available — cannot process queue");
         return Task::none();
     };
+    let count = model.app_config.ai.candidates();
 
-    let items: Vec<_> = queue_registry
+    let items: Vec<_> = model
+        .queue_registry
         .get_items()
         .filter(|item| item.status == QueueItemStatus::Pending)
         .cloned()
         .collect();
 
-    let count = items.len();
-    tracing::info!("Processing queue: {} pending items", count);
+    tracing::info!("Processing queue: {} pending items", items.len());
 
     // Mark items as processing before spawning tasks
     for item in &items {
-        queue_registry.set_processing(item.id);
+        model.queue_registry.set_processing(item.id);
     }
 
     let tasks = items.into_iter().map(|item| {
-        let meaning = match meaning_registry.get(item.meaning_id) {
-            Some(m) => m.clone(),
-            None => {
-                tracing::error!(meaning_id = %item.meaning_id, "Meaning not found for queue item");
-                queue_registry.set_failed(item.id, "Meaning not found".to_string());
+        let request = match GenerationRequest::new(model, item.meaning_id, count) {
+            Ok(request) => request,
+            Err(error) => {
+                model.queue_registry.set_failed(item.id, error);
                 return None;
             }
         };
-        let word = match word_registry.get(meaning.word_id) {
-            Some(w) => w.clone(),
-            None => {
-                tracing::error!(word_id = %meaning.word_id, "Word not found for meaning");
-                queue_registry.set_failed(item.id, "Word not found".to_string());
-                return None;
-            }
-        };
-        let generator = Arc::clone(generator);
+        let generator = Arc::clone(&generator);
         let item_id = item.id;
 
         Some(Task::perform(
             async move {
-                match generator.generate(&word, &meaning).await {
-                    Ok(cloze) => QueueGenerationResult::Success { item_id, cloze },
+                match generator.generate(&request).await {
+                    Ok(candidates) => QueueGenerationResult::Success {
+                        item_id,
+                        candidates,
+                    },
                     Err(e) => QueueGenerationResult::Failed {
                         item_id,
                         error: e.to_string(),
@@ -74,3 +82,43 @@ pub fn process(
 
     Task::batch(tasks.flatten())
 }
+
+/// Regenerates one candidate of a queue item under review.
+pub fn regenerate(model: &Model, item_id: WordId, replaces: ClozeId) -> Task<Message> {
+    let Some(generator) = model.generator.generator() else {
+        tracing::warn!("No generator available — cannot regenerate candidate");
+        return Task::none();
+    };
+    let Some(item) = model.queue_registry.get_item(item_id) else {
+        return Task::none();
+    };
+    let request = match GenerationRequest::new(model, item.meaning_id, 1) {
+        Ok(request) => request,
+        Err(error) => {
+            return Task::done(Message::QueueGenerationResult(
+                QueueGenerationResult::RegenerateFailed { item_id, error },
+            ));
+        }
+    };
+
+    Task::perform(
+        async move {
+            let result = generator
+                .generate(&request)
+                .await
+                .map(|mut clozes| clozes.remove(0));
+            match result {
+                Ok(cloze) => QueueGenerationResult::Regenerated {
+                    item_id,
+                    replaces,
+                    cloze,
+                },
+                Err(e) => QueueGenerationResult::RegenerateFailed {
+                    item_id,
+                    error: e.to_string(),
+                },
+            }
+        },
+        Message::QueueGenerationResult,
+    )
+}
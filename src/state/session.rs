@@ -0,0 +1,172 @@
+//! Session marker for detecting unclean shutdowns.
+//!
+//! The GUI writes a marker file into the data directory on startup, updates
+//! it after every autosave and removes it on a clean exit. A marker left
+//! behind means the previous session crashed or was killed.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::constants::paths;
+use crate::state::Model;
+
+/// Contents of the session marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub started_at: DateTime<Utc>,
+    /// Time of the last successful autosave
+    pub last_saved: Option<DateTime<Utc>>,
+}
+
+/// Handle to the session marker file.
+#[derive(Debug, Clone)]
+pub struct SessionMarker {
+    path: PathBuf,
+    info: SessionInfo,
+}
+
+impl SessionMarker {
+    /// Starts a session in `data_dir`.
+    ///
+    /// Returns the marker and, if the previous session did not shut down
+    /// cleanly, that session's info.
+    pub fn begin(data_dir: &Path) -> (Self, Option<SessionInfo>) {
+        let path = data_dir.join(paths::SESSION);
+        let previous = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(info) => Some(info),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Unreadable session marker, assuming unclean shutdown");
+                    Some(SessionInfo {
+                        started_at: DateTime::<Utc>::UNIX_EPOCH,
+                        last_saved: None,
+                    })
+                }
+            },
+            Err(_) => None,
+        };
+
+        let marker = Self {
+            path,
+            info: SessionInfo {
+                started_at: Utc::now(),
+                last_saved: None,
+            },
+        };
+        marker.write();
+        (marker, previous)
+    }
+
+    /// Returns true if a session marker exists in `data_dir`. Combined with
+    /// a locked database this suggests the GUI has the library open, but the
+    /// marker may be left over from a crash while another command holds the
+    /// lock, so handoffs wait for the GUI to answer.
+    pub fn is_present(data_dir: &Path) -> bool {
+        data_dir.join(paths::SESSION).exists()
+    }
+
+    /// Records a successful autosave.
+    pub fn saved(&mut self) {
+        self.info.last_saved = Some(Utc::now());
+        self.write();
+    }
+
+    /// Removes the marker after a clean shutdown.
+    pub fn end(&self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::warn!(error = %e, path = ?self.path, "Failed to remove session marker");
+        }
+    }
+
+    fn write(&self) {
+        let result = serde_json::to_string(&self.info)
+            .map_err(std::io::Error::other)
+            .and_then(|content| std::fs::write(&self.path, content));
+        if let Err(e) = result {
+            tracing::warn!(error = %e, path = ?self.path, "Failed to write session marker");
+        }
+    }
+}
+
+/// Changes from an interrupted session that made it to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recovery {
+    pub last_saved: Option<DateTime<Utc>>,
+    pub words: usize,
+    pub meanings: usize,
+    pub clozes: usize,
+}
+
+impl Recovery {
+    /// Counts loaded entities changed during the interrupted session.
+    pub fn from_session(model: &Model, session: &SessionInfo) -> Self {
+        let since = |time: DateTime<Utc>| time >= session.started_at;
+        Self {
+            last_saved: session.last_saved,
+            words: model
+                .word_registry
+                .iter()
+                .filter(|(_, w)| since(w.updated_at))
+                .count(),
+            meanings: model
+                .meaning_registry
+                .iter()
+                .filter(|(_, m)| since(m.updated_at))
+                .count(),
+            clozes: model
+                .cloze_registry
+                .iter()
+                .filter(|(_, c)| since(c.updated_at))
+                .count(),
+        }
+    }
+
+    /// Human-readable summary for the startup notification.
+    pub fn summary(&self) -> String {
+        let saved = match self.last_saved {
+            Some(time) => format!(
+                "last autosave {}",
+                time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            ),
+            None => "no autosave".to_string(),
+        };
+        format!(
+            "Previous session did not shut down cleanly ({}). Recovered {} word(s), {} meaning(s), {} cloze(s).",
+            saved, self.words, self.meanings, self.clozes
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::data_dir;
+
+    #[test]
+    fn test_clean_shutdown_leaves_no_marker() {
+        let dir = data_dir("session-clean");
+        let (marker, previous) = SessionMarker::begin(&dir);
+        assert_eq!(previous, None);
+        marker.end();
+        let (marker, previous) = SessionMarker::begin(&dir);
+        assert_eq!(previous, None);
+        marker.end();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unclean_shutdown_is_reported() {
+        let dir = data_dir("session-crash");
+        let (mut marker, _) = SessionMarker::begin(&dir);
+        marker.saved();
+        let crashed = marker.info;
+
+        let (marker, previous) = SessionMarker::begin(&dir);
+        assert_eq!(previous, Some(crashed));
+        assert!(crashed.last_saved.is_some());
+        marker.end();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
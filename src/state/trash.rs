@@ -0,0 +1,445 @@
+//! Soft delete into the trash, restore and purge.
+//!
+//! Deleting words, meanings, clozes or tags moves them into one
+//! `TrashEntry` together with everything that depends on them: meanings of
+//! a word, clozes of a meaning, schedules and review history of a cloze,
+//! the descendants of a tag and the meanings it was assigned to. Restoring
+//! re-links the entry into whatever exists at that point; parts whose word
+//! or meaning is gone for good stay in the trash as a new entry.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{TimeDelta, Utc};
+
+use crate::models::{ClozeId, MeaningId, TagId, TrashEntry, TrashId, WordId};
+use crate::state::Model;
+
+/// Outcome of restoring a trash entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restored {
+    /// Title of the restored entry
+    pub title: String,
+    /// Meanings and clozes that could not be re-linked and were left in
+    /// the trash
+    pub skipped: usize,
+}
+
+impl Model {
+    /// Moves words with their meanings and clozes to the trash.
+    pub fn trash_words(&mut self, word_ids: &[WordId]) -> Option<TrashId> {
+        let mut entry = TrashEntry::builder().build();
+        let mut meaning_ids = Vec::new();
+        for word_id in word_ids.iter().collect::<BTreeSet<_>>() {
+            if let Some(word) = self.word_registry.get(*word_id) {
+                entry.words.push(word.clone());
+                meaning_ids.extend(
+                    self.meaning_registry
+                        .iter_by_word(*word_id)
+                        .map(|(id, _)| *id),
+                );
+            }
+        }
+        self.collect_meanings(&mut entry, &meaning_ids);
+        self.move_to_trash(entry)
+    }
+
+    /// Moves meanings with their clozes to the trash.
+    pub fn trash_meanings(&mut self, meaning_ids: &[MeaningId]) -> Option<TrashId> {
+        let mut entry = TrashEntry::builder().build();
+        self.collect_meanings(&mut entry, meaning_ids);
+        self.move_to_trash(entry)
+    }
+
+    /// Moves clozes with their schedules and review history to the trash.
+    pub fn trash_clozes(&mut self, cloze_ids: &[ClozeId]) -> Option<TrashId> {
+        let mut entry = TrashEntry::builder().build();
+        self.collect_clozes(&mut entry, cloze_ids);
+        self.move_to_trash(entry)
+    }
+
+    /// Moves a tag and its descendants to the trash, remembering which
+    /// meanings carried them.
+    pub fn trash_tag(&mut self, tag_id: TagId) -> Option<TrashId> {
+        let mut entry = TrashEntry::builder().build();
+        let mut pending = vec![tag_id];
+        while let Some(id) = pending.pop() {
+            // Guard against parent cycles in corrupted data
+            if entry.tags.iter().any(|t| t.id == id) {
+                continue;
+            }
+            let Some(tag) = self.tag_registry.get(id) else {
+                continue;
+            };
+            entry.tagged.extend(
+                self.meaning_registry
+                    .iter_by_tag(id)
+                    .map(|(meaning_id, _)| (id, *meaning_id)),
+            );
+            pending.extend(tag.children_ids.iter().rev());
+            entry.tags.push(tag.clone());
+        }
+        self.move_to_trash(entry)
+    }
+
+    fn collect_meanings(&self, entry: &mut TrashEntry, meaning_ids: &[MeaningId]) {
+        let mut cloze_ids = Vec::new();
+        for meaning_id in meaning_ids.iter().collect::<BTreeSet<_>>() {
+            if let Some(meaning) = self.meaning_registry.get(*meaning_id) {
+                entry.meanings.push(meaning.clone());
+                cloze_ids.extend(
+                    self.cloze_registry
+                        .iter_by_meaning_id(*meaning_id)
+                        .map(|(id, _)| *id),
+                );
+            }
+        }
+        self.collect_clozes(entry, &cloze_ids);
+    }
+
+    fn collect_clozes(&self, entry: &mut TrashEntry, cloze_ids: &[ClozeId]) {
+        for cloze_id in cloze_ids.iter().collect::<BTreeSet<_>>() {
+            if let Some(cloze) = self.cloze_registry.get(*cloze_id) {
+                entry.clozes.push(cloze.clone());
+                entry
+                    .schedules
+                    .extend(self.schedule_registry.get(*cloze_id).cloned());
+                entry
+                    .reviews
+                    .extend(self.review_registry.history(*cloze_id).into_iter().cloned());
+            }
+        }
+    }
+
+    /// Removes everything in `entry` from the registries and keeps the entry.
+    fn move_to_trash(&mut self, entry: TrashEntry) -> Option<TrashId> {
+        if entry.is_empty() {
+            return None;
+        }
+        for cloze in &entry.clozes {
+            self.cloze_registry.delete(cloze.id);
+            self.schedule_registry.delete(cloze.id);
+            self.review_registry.delete_by_cloze(cloze.id);
+        }
+        for meaning in &entry.meanings {
+            self.word_registry
+                .remove_meaning(meaning.word_id, meaning.id);
+            self.meaning_registry.delete(meaning.id);
+        }
+        for (tag_id, meaning_id) in &entry.tagged {
+            self.meaning_registry.remove_tag(*meaning_id, *tag_id);
+        }
+        for tag in &entry.tags {
+            if let Some(parent) = tag.parent_id.and_then(|p| self.tag_registry.get_mut(p)) {
+                parent.children_ids.remove(&tag.id);
+            }
+            self.tag_registry.delete(tag.id);
+        }
+        for word in &entry.words {
+            self.word_registry.delete(word.id);
+        }
+
+        let id = entry.id;
+        tracing::info!(
+            trash_id = %id,
+            words = entry.words.len(),
+            meanings = entry.meanings.len(),
+            tags = entry.tags.len(),
+            clozes = entry.clozes.len(),
+            "Moved to trash"
+        );
+        self.trash_registry.add(entry);
+        Some(id)
+    }
+
+    /// Puts a trash entry back into the library and removes it from the
+    /// trash. Words and tags whose name was taken again in the meantime are
+    /// merged into the existing ones. Meanings and clozes that cannot be
+    /// re-linked go back into the trash as a new entry.
+    pub fn restore_trash(&mut self, id: TrashId) -> Option<Restored> {
+        let entry = self.trash_registry.take(id)?;
+        let mut leftover = TrashEntry::builder().deleted_at(entry.deleted_at).build();
+
+        let mut tag_ids: BTreeMap<TagId, TagId> = BTreeMap::new();
+        for tag in &entry.tags {
+            let parent_id = tag
+                .parent_id
+                .map(|p| tag_ids.get(&p).copied().unwrap_or(p))
+                .filter(|p| self.tag_registry.exists(*p));
+            let restored_id = match self.tag_registry.find_child(parent_id, &tag.name) {
+                Some(existing) => existing,
+                None => {
+                    let mut restored = tag.clone();
+                    restored.parent_id = parent_id;
+                    // Children are linked again as they are restored
+                    restored.children_ids.clear();
+                    self.tag_registry.add(restored);
+                    if let Some(parent) = parent_id.and_then(|p| self.tag_registry.get_mut(p)) {
+                        parent.children_ids.insert(tag.id);
+                    }
+                    tag.id
+                }
+            };
+            tag_ids.insert(tag.id, restored_id);
+        }
+        let tag_id = |id: &TagId| tag_ids.get(id).copied().unwrap_or(*id);
+
+        let mut word_ids: BTreeMap<WordId, WordId> = BTreeMap::new();
+        for word in &entry.words {
+            let restored_id = match self.word_registry.find_by_content(&word.content) {
+                Some(existing) => existing,
+                None => {
+                    let mut restored = word.clone();
+                    // Meanings are linked again as they are restored
+                    restored.meaning_ids.clear();
+                    self.word_registry.add(restored);
+                    word.id
+                }
+            };
+            word_ids.insert(word.id, restored_id);
+        }
+
+        for meaning in &entry.meanings {
+            let mut meaning = meaning.clone();
+            meaning.word_id = word_ids
+                .get(&meaning.word_id)
+                .copied()
+                .unwrap_or(meaning.word_id);
+            if !self.word_registry.exists(meaning.word_id) {
+                leftover.meanings.push(meaning);
+                continue;
+            }
+            meaning.tag_ids = meaning
+                .tag_ids
+                .iter()
+                .map(tag_id)
+                .filter(|id| self.tag_registry.exists(*id))
+                .collect();
+            self.word_registry.add_meaning(meaning.word_id, meaning.id);
+            self.meaning_registry.add(meaning);
+        }
+
+        for cloze in &entry.clozes {
+            if !self.meaning_registry.exists(cloze.meaning_id) {
+                leftover.clozes.push(cloze.clone());
+                continue;
+            }
+            self.cloze_registry.add(cloze.clone());
+        }
+        for schedule in &entry.schedules {
+            if self.cloze_registry.exists(schedule.cloze_id) {
+                self.schedule_registry.upsert(schedule.clone());
+            } else {
+                leftover.schedules.push(schedule.clone());
+            }
+        }
+        for review in &entry.reviews {
+            if self.cloze_registry.exists(review.cloze_id) {
+                self.review_registry.add(review.clone());
+            } else {
+                leftover.reviews.push(review.clone());
+            }
+        }
+
+        for (trashed_tag, meaning_id) in &entry.tagged {
+            let tag = tag_id(trashed_tag);
+            if self.tag_registry.exists(tag) {
+                self.meaning_registry.add_tag(*meaning_id, tag);
+            }
+        }
+
+        let skipped = leftover.meanings.len() + leftover.clozes.len();
+        if !leftover.is_empty() {
+            tracing::warn!(
+                trash_id = %id,
+                kept_id = %leftover.id,
+                skipped,
+                "Kept parts that could not be restored in the trash"
+            );
+            self.trash_registry.add(leftover);
+        }
+        tracing::info!(trash_id = %id, skipped, "Restored from trash");
+        Some(Restored {
+            title: entry.title(),
+            skipped,
+        })
+    }
+
+    /// Permanently deletes a trash entry.
+    pub fn delete_from_trash(&mut self, id: TrashId) -> bool {
+        self.trash_registry.take(id).is_some()
+    }
+
+    /// Permanently deletes every trash entry and returns how many there were.
+    pub fn empty_trash(&mut self) -> usize {
+        let ids: Vec<TrashId> = self.trash_registry.entries.keys().copied().collect();
+        for id in &ids {
+            self.trash_registry.take(*id);
+        }
+        ids.len()
+    }
+
+    /// Permanently deletes trash entries older than the configured retention.
+    pub fn purge_trash(&mut self) -> usize {
+        let cutoff = Utc::now() - TimeDelta::days(i64::from(self.app_config.trash_retention_days));
+        let purged = self.trash_registry.purge_before(cutoff);
+        if purged > 0 {
+            tracing::info!(purged, "Purged expired trash entries");
+        }
+        purged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::models::{CardSchedule, Cloze, ClozeSegment, PartOfSpeech};
+    use crate::persistence::Db;
+
+    fn library() -> (Model, WordId, MeaningId, ClozeId) {
+        let mut model = Model::new(Db::in_memory().unwrap(), AppConfig::default());
+        let word_id = model.word_registry.create_word("hello", None).unwrap();
+        let meaning_id = model
+            .meaning_registry
+            .create_meaning(word_id, "a greeting", PartOfSpeech::Noun, None)
+            .unwrap();
+        model.word_registry.add_meaning(word_id, meaning_id);
+        let cloze = Cloze::builder()
+            .meaning_id(meaning_id)
+            .segments(vec![ClozeSegment::Blank("hello".to_string())])
+            .build();
+        let cloze_id = cloze.id;
+        model.cloze_registry.add(cloze);
+        model
+            .schedule_registry
+            .upsert(CardSchedule::builder().cloze_id(cloze_id).build());
+        (model, word_id, meaning_id, cloze_id)
+    }
+
+    #[test]
+    fn test_restore_word_relinks_meanings_and_clozes() {
+        let (mut model, word_id, meaning_id, cloze_id) = library();
+        let trash_id = model.trash_words(&[word_id]).unwrap();
+        assert!(!model.word_registry.exists(word_id));
+        assert!(!model.meaning_registry.exists(meaning_id));
+        assert!(model.schedule_registry.get(cloze_id).is_none());
+
+        // Survives a reload from the database
+        model.flush_all().unwrap();
+        let mut model = Model::new(model.db, AppConfig::default());
+        model.load_all();
+
+        let restored = model.restore_trash(trash_id).unwrap();
+        assert_eq!(restored.title, "hello");
+        assert_eq!(restored.skipped, 0);
+        assert!(model.trash_registry.is_empty());
+        let word = model.word_registry.get(word_id).unwrap();
+        assert!(word.meaning_ids.contains(&meaning_id));
+        assert_eq!(model.cloze_registry.count_by_meaning(meaning_id), 1);
+        assert!(model.schedule_registry.get(cloze_id).is_some());
+    }
+
+    #[test]
+    fn test_restore_tag_relinks_hierarchy_and_meanings() {
+        let (mut model, _, meaning_id, _) = library();
+        let food = model
+            .tag_registry
+            .get_or_create_path("Travel/Food")
+            .unwrap();
+        let travel = model.tag_registry.find_by_path("Travel").unwrap();
+        model.meaning_registry.add_tag(meaning_id, food);
+
+        let trash_id = model.trash_tag(travel).unwrap();
+        assert_eq!(model.tag_registry.count(), 0);
+        assert!(
+            model
+                .meaning_registry
+                .get(meaning_id)
+                .unwrap()
+                .tag_ids
+                .is_empty()
+        );
+
+        model.restore_trash(trash_id).unwrap();
+        assert_eq!(model.tag_registry.find_by_path("Travel/Food"), Some(food));
+        assert!(
+            model
+                .tag_registry
+                .get(travel)
+                .unwrap()
+                .children_ids
+                .contains(&food)
+        );
+        assert!(
+            model
+                .meaning_registry
+                .get(meaning_id)
+                .unwrap()
+                .tag_ids
+                .contains(&food)
+        );
+    }
+
+    #[test]
+    fn test_restore_keeps_meaning_of_trashed_word_in_trash() {
+        let (mut model, word_id, meaning_id, cloze_id) = library();
+        let meaning_trash = model.trash_meanings(&[meaning_id]).unwrap();
+        assert!(
+            model
+                .word_registry
+                .get(word_id)
+                .unwrap()
+                .meaning_ids
+                .is_empty()
+        );
+        let word_trash = model.trash_words(&[word_id]).unwrap();
+
+        let restored = model.restore_trash(meaning_trash).unwrap();
+        // The meaning and its cloze
+        assert_eq!(restored.skipped, 2);
+        assert_eq!(model.meaning_registry.count(), 0);
+        assert_eq!(model.cloze_registry.count(), 0);
+        let (&kept, _) = model
+            .trash_registry
+            .entries
+            .iter()
+            .find(|(id, _)| **id != word_trash)
+            .unwrap();
+
+        model.restore_trash(word_trash).unwrap();
+        let restored = model.restore_trash(kept).unwrap();
+        assert_eq!(restored.skipped, 0);
+        assert!(model.trash_registry.is_empty());
+        assert!(
+            model
+                .word_registry
+                .get(word_id)
+                .unwrap()
+                .meaning_ids
+                .contains(&meaning_id)
+        );
+        assert!(model.schedule_registry.get(cloze_id).is_some());
+    }
+
+    #[test]
+    fn test_undo_delete_drops_trash_entry_and_purge_expires() {
+        let (mut model, _, _, cloze_id) = library();
+        model.record(|m| m.trash_clozes(&[cloze_id]));
+        assert_eq!(model.trash_registry.count(), 1);
+        model.undo().unwrap();
+        assert!(model.trash_registry.is_empty());
+        assert!(model.cloze_registry.exists(cloze_id));
+
+        let trash_id = model.trash_clozes(&[cloze_id]).unwrap();
+        let old =
+            Utc::now() - TimeDelta::days(i64::from(model.app_config.trash_retention_days) + 1);
+        model
+            .trash_registry
+            .entries
+            .get_mut(&trash_id)
+            .unwrap()
+            .deleted_at = old;
+        assert_eq!(model.purge_trash(), 1);
+        assert!(model.trash_registry.is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+//! Validation of LLM-generated cloze sentences.
+//!
+//! Responses are cleaned with `sanitize` before parsing, then each parsed
+//! cloze is checked with `validate`. Rejected candidates are retried by the
+//! generator.
+
+use thiserror::Error;
+
+use crate::models::{Cloze, ClozeSegment, Word};
+
+/// Longest accepted sentence, in characters.
+pub const MAX_SENTENCE_CHARS: usize = 250;
+
+/// Most characters an inflected blank may add past the stem of a word that
+/// is not matched by English suffix rules (comeríamos adds seven).
+const MAX_INFLECTION_SUFFIX: usize = 7;
+
+/// Regular English endings, after the stem has been adjusted.
+const SUFFIXES: &[&str] = &["s", "es", "d", "ed", "ing", "r", "er", "st", "est"];
+
+/// Lead-ins that mark the model talking about the sentence instead of
+/// returning it.
+const COMMENTARY_PREFIXES: &[&str] = &["here is", "here's", "sure,", "sure!", "note:", "sentence:"];
+
+/// Cloze delimiters and Markdown emphasis that should never survive into a
+/// sentence.
+const MARKUP: &[&str] = &["[", "]", "{{", "}}", "**", "__", "`"];
+
+/// Why a generated sentence was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("no blank marked")]
+    NoBlank,
+
+    #[error("empty blank")]
+    EmptyBlank,
+
+    #[error("blank \"{blank}\" does not match \"{word}\"")]
+    WrongTarget { blank: String, word: String },
+
+    #[error("leftover markup \"{0}\"")]
+    Markup(String),
+
+    #[error("contains commentary")]
+    Commentary,
+
+    #[error("longer than {MAX_SENTENCE_CHARS} characters")]
+    TooLong,
+}
+
+/// Trims whitespace and one pair of enclosing quotes from a sentence.
+pub fn sanitize(sentence: &str) -> &str {
+    let trimmed = sentence.trim();
+    const QUOTES: &[(char, char)] = &[
+        ('"', '"'),
+        ('\'', '\''),
+        ('“', '”'),
+        ('«', '»'),
+        ('「', '」'),
+    ];
+    for (open, close) in QUOTES {
+        if let Some(inner) = trimmed
+            .strip_prefix(*open)
+            .and_then(|s| s.strip_suffix(*close))
+        {
+            return inner.trim();
+        }
+    }
+    trimmed
+}
+
+/// Checks a parsed cloze against the word it was generated for.
+pub fn validate(cloze: &Cloze, word: &Word) -> Result<(), ValidationError> {
+    let blanks: Vec<&str> = cloze
+        .segments
+        .iter()
+        .filter_map(|s| match s {
+            ClozeSegment::Blank(answer) => Some(answer.trim()),
+            ClozeSegment::Text(_) => None,
+        })
+        .collect();
+    if blanks.is_empty() {
+        return Err(ValidationError::NoBlank);
+    }
+    if blanks.iter().any(|b| b.is_empty()) {
+        return Err(ValidationError::EmptyBlank);
+    }
+
+    let sentence = cloze.render_answers();
+    if sentence.chars().count() > MAX_SENTENCE_CHARS {
+        return Err(ValidationError::TooLong);
+    }
+    let lower = sentence.trim_start().to_lowercase();
+    if sentence.contains('\n') || COMMENTARY_PREFIXES.iter().any(|p| lower.starts_with(p)) {
+        return Err(ValidationError::Commentary);
+    }
+    for segment in &cloze.segments {
+        let text = match segment {
+            ClozeSegment::Text(text) | ClozeSegment::Blank(text) => text,
+        };
+        if let Some(markup) = MARKUP.iter().find(|m| text.contains(**m)) {
+            return Err(ValidationError::Markup(markup.to_string()));
+        }
+    }
+
+    // Separable phrases may be split over several blanks ("[give] it [up]")
+    let blank = blanks.join(" ");
+    if !matches_target(&blank, word) {
+        return Err(ValidationError::WrongTarget {
+            blank,
+            word: word.content.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns true if `blank` is `word` or a plausible inflection of it,
+/// compared word by word for phrases. English suffix rules apply to words
+/// in English or without a language; other words are matched by stem.
+fn matches_target(blank: &str, word: &Word) -> bool {
+    let english = word.language.as_ref().is_none_or(|language| {
+        let primary = language.as_str().split('-').next().unwrap_or_default();
+        primary.eq_ignore_ascii_case("en")
+    });
+    let blank = blank.to_lowercase();
+    let content = word.content.to_lowercase();
+    let blank_tokens: Vec<&str> = blank.split_whitespace().collect();
+    let word_tokens: Vec<&str> = content.split_whitespace().collect();
+    blank_tokens.len() == word_tokens.len()
+        && blank_tokens
+            .iter()
+            .zip(&word_tokens)
+            .all(|(b, w)| is_inflection(b, w, english))
+}
+
+/// Returns true if `blank` is `word` with a regular English suffix (-s,
+/// -es, -ed, -ing, -er, -est), allowing a dropped e (make → making), y to i
+/// (carry → carried) and a doubled final consonant (run → running). Words
+/// in other languages or outside ASCII are matched by stem instead.
+/// Irregular forms are not recognised.
+fn is_inflection(blank: &str, word: &str, english: bool) -> bool {
+    if blank == word {
+        return true;
+    }
+    if !english || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return is_stem_inflection(blank, word);
+    }
+
+    let mut stems = vec![word.to_string()];
+    if let Some(stem) = word.strip_suffix('e') {
+        stems.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('y') {
+        stems.push(format!("{}i", stem));
+    }
+    if let Some(last) = word.chars().last()
+        && !"aeiouwxy".contains(last)
+    {
+        stems.push(format!("{}{}", word, last));
+    }
+    stems.iter().any(|stem| {
+        blank
+            .strip_prefix(stem.as_str())
+            .is_some_and(|suffix| SUFFIXES.contains(&suffix))
+    })
+}
+
+/// Matches a blank that keeps the stem of `word` and adds a short ending.
+/// The stem drops the last two characters of words of five or more, which
+/// covers infinitive endings (hablar → hablo, parler → parlons,
+/// machen → machte), and the last character of shorter ones
+/// (食べる → 食べた).
+fn is_stem_inflection(blank: &str, word: &str) -> bool {
+    let len = word.chars().count();
+    let cut = if len >= 5 { 2 } else { 1 };
+    let stem_end = word
+        .char_indices()
+        .nth(len.saturating_sub(cut))
+        .map_or(word.len(), |(i, _)| i);
+    let stem = &word[..stem_end];
+    !stem.is_empty()
+        && blank
+            .strip_prefix(stem)
+            .is_some_and(|suffix| suffix.chars().count() <= MAX_INFLECTION_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MeaningId;
+    use test_case::test_case;
+
+    fn check(sentence: &str, word: &str) -> Result<(), ValidationError> {
+        check_in(sentence, word, None)
+    }
+
+    fn check_in(sentence: &str, word: &str, language: Option<&str>) -> Result<(), ValidationError> {
+        let cloze = Cloze::builder()
+            .meaning_id(MeaningId::new())
+            .segments(Cloze::parse_from_sentence(sanitize(sentence)))
+            .build();
+        let word = match language {
+            Some(language) => Word::builder()
+                .content(word.to_string())
+                .language(language.parse().unwrap())
+                .build(),
+            None => Word::builder().content(word.to_string()).build(),
+        };
+        validate(&cloze, &word)
+    }
+
+    #[test_case("She [runs] every morning.", "run"; "inflection")]
+    #[test_case("\"He was [making] tea.\"", "make"; "quoted and dropped e")]
+    #[test_case("They [carried] the boxes.", "carry"; "y to i")]
+    #[test_case("Don't [give] it [up] now.", "give up"; "split phrase")]
+    #[test_case("私は寿司を[食べた]。", "食べる"; "non latin")]
+    #[test_case("She [liked] it.", "like"; "final e kept")]
+    #[test_case("They [stopped] early.", "stop"; "doubled consonant")]
+    #[test_case("The [boxes] were heavy.", "box"; "es plural")]
+    #[test_case("It was the [happiest] day.", "happy"; "superlative y to i")]
+    #[test_case("Meet me at gate #4 at 3 > 2 o'clock, <3 [cats]", "cat"; "plain symbols")]
+    fn test_valid(sentence: &str, word: &str) {
+        assert_eq!(check(sentence, word), Ok(()));
+    }
+
+    #[test_case("Yo [hablo] español.", "hablar", "es"; "spanish present")]
+    #[test_case("Ayer [comí] pan.", "comer", "es"; "spanish preterite accent")]
+    #[test_case("Nosotros [comeríamos] más.", "comer", "es"; "spanish conditional")]
+    #[test_case("Nous [parlons] français.", "parler", "fr"; "french present")]
+    #[test_case("Ils [finissent] tôt.", "finir", "fr-CA"; "french region")]
+    #[test_case("Er [machte] das.", "machen", "de"; "german past")]
+    #[test_case("Sie [spielt] gern.", "spielen", "de"; "german present")]
+    #[test_case("She [runs] daily.", "run", "en-GB"; "english region")]
+    fn test_valid_in_language(sentence: &str, word: &str, language: &str) {
+        assert_eq!(check_in(sentence, word, Some(language)), Ok(()));
+    }
+
+    #[test_case("El [perro] come.", "gato", "es"; "spanish other word")]
+    #[test_case("The [car] sat.", "cat", "en"; "english same prefix")]
+    fn test_wrong_target_in_language(sentence: &str, word: &str, language: &str) {
+        assert!(matches!(
+            check_in(sentence, word, Some(language)),
+            Err(ValidationError::WrongTarget { .. })
+        ));
+    }
+
+    #[test_case("The cat sat on the mat.", "cat", ValidationError::NoBlank; "no blank")]
+    #[test_case("The [dog] sat on the mat.", "cat", ValidationError::WrongTarget { blank: "dog".to_string(), word: "cat".to_string() }; "wrong word")]
+    #[test_case("Here is a sentence: the [cat] sat.", "cat", ValidationError::Commentary; "commentary")]
+    #[test_case("The [cat] sat.\nI hope this helps!", "cat", ValidationError::Commentary; "trailing note")]
+    #[test_case("The [car] sat.", "cat", ValidationError::WrongTarget { blank: "car".to_string(), word: "cat".to_string() }; "same prefix")]
+    #[test_case("The [cab] sat.", "cat", ValidationError::WrongTarget { blank: "cab".to_string(), word: "cat".to_string() }; "same prefix shorter")]
+    #[test_case("The [catalog] sat.", "cat", ValidationError::WrongTarget { blank: "catalog".to_string(), word: "cat".to_string() }; "longer word")]
+    #[test_case("She [stood] up.", "stop", ValidationError::WrongTarget { blank: "stood".to_string(), word: "stop".to_string() }; "shared stem only")]
+    #[test_case("The **[cat]** sat.", "cat", ValidationError::Markup("**".to_string()); "bold markup")]
+    #[test_case("The {{cat}} [sat].", "sit", ValidationError::Markup("{{".to_string()); "anki cloze")]
+    #[test_case("The [cat]] sat.", "cat", ValidationError::Markup("]".to_string()); "stray bracket")]
+    fn test_invalid(sentence: &str, word: &str, expected: ValidationError) {
+        assert_eq!(check(sentence, word), Err(expected));
+    }
+
+    #[test]
+    fn test_too_long() {
+        let sentence = format!("The [cat] {}.", "sat ".repeat(70));
+        assert_eq!(check(&sentence, "cat"), Err(ValidationError::TooLong));
+    }
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by unit tests.
+
+use std::path::PathBuf;
+
+/// Creates an empty scratch data directory for the test `name`. Tests
+/// remove it themselves when done.
+pub(crate) fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("clozer-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -1,9 +1,11 @@
+use crate::i18n::I18nManager;
 use crate::message::Message;
+use crate::state::Edit;
 use crate::state::Model;
 use crate::ui::AppTheme;
 use crate::ui::layout::breakpoint::Breakpoint;
 use crate::ui::nav::NavItem;
-use crate::ui::notification::Notification as NotificationData;
+use crate::ui::notification::{Notification as NotificationData, NotificationAction};
 use crate::ui::practice::{
     PracticeMessage, PracticeState, update as practice_update, view as practice_view,
 };
@@ -19,7 +21,7 @@ use iced::{Element, Length, Task};
 pub fn view<'a>(state: &'a UiState, model: &'a Model) -> Element<'a, Message, AppTheme> {
     let breakpoint = Breakpoint::from_width(state.window_width as f32);
 
-    let sidebar_element = sidebar::sidebar(state, breakpoint);
+    let sidebar_element = sidebar::sidebar(state, breakpoint, &model.app_config);
     let sidebar_panel = Container::new(sidebar_element)
         .style(|theme: &AppTheme| iced::widget::container::Style {
             background: Some(theme.colors().semantic.surface.raised.into()),
@@ -38,6 +40,9 @@ pub fn view<'a>(state: &'a UiState, model: &'a Model) -> Element<'a, Message, Ap
         NavItem::Practice => {
             practice_view(&state.practice, model, &state.i18n).map(Message::Practice)
         }
+        NavItem::Trash => {
+            crate::ui::trash::view(&state.trash, model, &state.i18n).map(Message::Trash)
+        }
         NavItem::Settings => crate::ui::settings::view::view(&state.settings, model, &state.i18n)
             .map(Message::Settings),
     };
@@ -47,7 +52,7 @@ pub fn view<'a>(state: &'a UiState, model: &'a Model) -> Element<'a, Message, Ap
         Column::new()
             .push(content)
             .push(bottom)
-            .push(status_bar(&state.notifications))
+            .push(status_bar(&state.notifications, &state.i18n))
             .width(iced::Length::Fill)
             .height(iced::Length::Fill)
             .into()
@@ -60,7 +65,7 @@ pub fn view<'a>(state: &'a UiState, model: &'a Model) -> Element<'a, Message, Ap
                     .width(Length::Fill)
                     .height(Length::Fill),
             )
-            .push(status_bar(&state.notifications))
+            .push(status_bar(&state.notifications, &state.i18n))
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
@@ -72,7 +77,10 @@ pub fn update_words(
     message: WordsMessage,
     model: &mut Model,
 ) -> Task<Message> {
-    crate::ui::words::update(state, message, model).map(|msg| match msg {
+    let (task, edit) = model.record(|m| crate::ui::words::update(state, message, m));
+    // Offer to undo deletions from the notification reporting them
+    let undoable = edit.is_some_and(Edit::deletes);
+    task.map(move |msg| match msg {
         WordsMessage::Notify { level, message } => {
             let notification = match level {
                 crate::ui::words::message::NotificationLevel::Error => {
@@ -81,6 +89,9 @@ pub fn update_words(
                 crate::ui::words::message::NotificationLevel::Warning => {
                     NotificationData::warning(0, message)
                 }
+                crate::ui::words::message::NotificationLevel::Info if undoable => {
+                    NotificationData::info(0, message).with_action(NotificationAction::Undo)
+                }
                 crate::ui::words::message::NotificationLevel::Info => {
                     NotificationData::info(0, message)
                 }
@@ -99,6 +110,7 @@ pub fn update_settings(
     state: &mut SettingsState,
     message: crate::ui::settings::SettingsMessage,
     _model: &mut Model,
+    i18n: &I18nManager,
 ) -> Task<Message> {
     use crate::ui::settings::SettingsMessage;
     match message {
@@ -110,9 +122,24 @@ pub fn update_settings(
             {
                 return Task::done(crate::message::Message::LocaleChanged(*locale));
             }
-            crate::ui::settings::handlers::update(state, message, _model).map(Message::Settings)
+            crate::ui::settings::handlers::update(state, message, _model, i18n)
+                .map(Message::Settings)
+        }
+        SettingsMessage::Data(crate::ui::settings::message::DataMessage::OpenProfile(profile)) => {
+            Task::done(Message::SwitchProfile(profile))
+        }
+        _ => {
+            let task = crate::ui::settings::handlers::update(state, message, _model, i18n);
+            task.map(|msg| match msg {
+                SettingsMessage::Notify { level, message } => {
+                    Message::PushNotification(NotificationData::new(0, level, message))
+                }
+                SettingsMessage::Data(crate::ui::settings::message::DataMessage::OpenProfile(
+                    profile,
+                )) => Message::SwitchProfile(profile),
+                other => Message::Settings(other),
+            })
         }
-        _ => crate::ui::settings::handlers::update(state, message, _model).map(Message::Settings),
     }
 }
 
@@ -14,6 +14,7 @@ pub mod state;
 pub mod status_bar;
 pub mod tags;
 pub mod theme;
+pub mod trash;
 pub mod widgets;
 pub mod words;
 
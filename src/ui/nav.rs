@@ -8,17 +8,19 @@ pub enum NavItem {
     Queue,
     Tags,
     Practice,
+    Trash,
     Settings,
 }
 
 impl NavItem {
     /// Returns all navigation items in display order.
-    pub fn all() -> &'static [NavItem; 5] {
+    pub fn all() -> &'static [NavItem; 6] {
         &[
             NavItem::Words,
             NavItem::Queue,
             NavItem::Tags,
             NavItem::Practice,
+            NavItem::Trash,
             NavItem::Settings,
         ]
     }
@@ -34,8 +36,8 @@ impl NavItem {
     }
 
     /// Secondary navigation items (utility/views).
-    pub fn secondary() -> &'static [NavItem; 1] {
-        &[NavItem::Settings]
+    pub fn secondary() -> &'static [NavItem; 2] {
+        &[NavItem::Trash, NavItem::Settings]
     }
 
     /// Returns the i18n key for this navigation item's label.
@@ -45,6 +47,7 @@ impl NavItem {
             NavItem::Queue => "nav-queue",
             NavItem::Tags => "nav-tags",
             NavItem::Practice => "nav-practice",
+            NavItem::Trash => "nav-trash",
             NavItem::Settings => "nav-settings",
         }
     }
@@ -56,6 +59,7 @@ impl NavItem {
             NavItem::Queue => "sidebar-shortcut-queue",
             NavItem::Tags => "sidebar-shortcut-tags",
             NavItem::Practice => "sidebar-shortcut-practice",
+            NavItem::Trash => "sidebar-shortcut-trash",
             NavItem::Settings => "sidebar-shortcut-settings",
         }
     }
@@ -7,12 +7,20 @@ pub enum NotificationLevel {
     Info,
 }
 
+/// Action button offered next to a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// Revert the most recent edit
+    Undo,
+}
+
 #[derive(Debug, Clone)]
 pub struct Notification {
     pub id: usize,
     pub level: NotificationLevel,
     pub message: String,
     pub created_at: Instant,
+    pub action: Option<NotificationAction>,
 }
 
 impl Notification {
@@ -22,9 +30,15 @@ impl Notification {
             level,
             message: message.into(),
             created_at: Instant::now(),
+            action: None,
         }
     }
 
+    pub fn with_action(mut self, action: NotificationAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
     pub fn error(id: usize, message: impl Into<String>) -> Self {
         Self::new(id, NotificationLevel::Error, message)
     }
@@ -6,6 +6,7 @@ pub enum PracticeMessage {
     TagSearchChanged(String),
     TagFilterSelected(TagId),
     TagFilterCleared,
+    IncludeSubtagsToggled(bool),
     StartSession,
 
     EndSession,
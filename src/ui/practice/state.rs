@@ -1,12 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use chrono::Utc;
 
 use crate::models::cloze::{Cloze, ClozeSegment};
 use crate::models::types::{ClozeId, TagId};
+use crate::scheduler;
 use crate::state::Model;
 
 #[derive(Debug, Default)]
 pub struct PracticeState {
     pub tag_filter: Option<TagId>,
+    /// Whether the tag filter also covers the tags nested under it
+    pub include_subtags: bool,
     pub show_tag_picker: bool,
     pub tag_search: String,
 
@@ -18,9 +24,14 @@ pub struct PracticeState {
     pub answers: HashMap<usize, String>,
     pub submitted: bool,
     pub results: HashMap<usize, bool>,
+    /// When the current cloze was shown, for timing reviews
+    pub shown_at: Option<Instant>,
 
     pub correct_count: usize,
     pub total_attempted: usize,
+
+    /// Clozes already graded into the schedule this session
+    pub graded: HashSet<ClozeId>,
 }
 
 impl PracticeState {
@@ -28,29 +39,52 @@ impl PracticeState {
         Self::default()
     }
 
+    /// Clozes the tag filter allows, whether due or not.
+    pub fn candidate_clozes(&self, model: &Model) -> Vec<ClozeId> {
+        let Some(tag_id) = self.tag_filter else {
+            return model.cloze_registry.iter().map(|(id, _)| *id).collect();
+        };
+        let tags = if self.include_subtags {
+            model.tag_registry.with_descendants(tag_id)
+        } else {
+            vec![tag_id]
+        };
+        // A meaning tagged with several tags of the subtree counts once
+        let meanings: HashSet<_> = tags
+            .into_iter()
+            .flat_map(|tag_id| model.meaning_registry.iter_by_tag(tag_id))
+            .map(|(meaning_id, _)| *meaning_id)
+            .collect();
+        meanings
+            .into_iter()
+            .flat_map(|meaning_id| model.cloze_registry.iter_by_meaning_id(meaning_id))
+            .map(|(cloze_id, _)| *cloze_id)
+            .collect()
+    }
+
+    /// Builds a session from the clozes that are due for review,
+    /// optionally restricted to the selected tag.
     pub fn build_session(&mut self, model: &Model) {
         self.session_clozes.clear();
         self.total_blanks = 0;
+        self.graded.clear();
 
-        if let Some(tag_id) = self.tag_filter {
-            for (meaning_id, _) in model.meaning_registry.iter_by_tag(tag_id) {
-                for (cloze_id, cloze) in model.cloze_registry.iter_by_meaning_id(*meaning_id) {
-                    self.total_blanks += cloze
-                        .segments
-                        .iter()
-                        .filter(|s| matches!(s, ClozeSegment::Blank(_)))
-                        .count();
-                    self.session_clozes.push(*cloze_id);
-                }
-            }
-        } else {
-            for (cloze_id, cloze) in model.cloze_registry.iter() {
+        let candidates = self.candidate_clozes(model);
+
+        self.session_clozes = scheduler::due_clozes(
+            candidates,
+            &model.cloze_registry,
+            &model.schedule_registry,
+            Utc::now(),
+        );
+
+        for cloze_id in &self.session_clozes {
+            if let Some(cloze) = model.cloze_registry.get(*cloze_id) {
                 self.total_blanks += cloze
                     .segments
                     .iter()
                     .filter(|s| matches!(s, ClozeSegment::Blank(_)))
                     .count();
-                self.session_clozes.push(*cloze_id);
             }
         }
 
@@ -123,6 +157,7 @@ impl PracticeState {
         self.answers.clear();
         self.results.clear();
         self.submitted = false;
+        self.shown_at = Some(Instant::now());
     }
 
     pub fn score_percent(&self) -> f64 {
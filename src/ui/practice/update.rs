@@ -1,4 +1,6 @@
 use crate::models::cloze::ClozeSegment;
+use crate::models::{ReviewAnswer, ReviewLog};
+use crate::scheduler::{ReviewGrade, Scheduler};
 use crate::state::Model;
 
 use super::message::{NotificationLevel, PracticeMessage};
@@ -27,6 +29,9 @@ pub fn update(
             state.show_tag_picker = false;
             state.tag_search.clear();
         }
+        PracticeMessage::IncludeSubtagsToggled(include) => {
+            state.include_subtags = include;
+        }
         PracticeMessage::StartSession => {
             state.build_session(model);
             if !state.session_clozes.is_empty() {
@@ -36,7 +41,7 @@ pub fn update(
             } else {
                 return iced::Task::done(PracticeMessage::Notify {
                     level: NotificationLevel::Warning,
-                    message: "No clozes found for the selected filter.".to_string(),
+                    message: "No clozes are due for review.".to_string(),
                 });
             }
         }
@@ -76,6 +81,7 @@ pub fn update(
             state.total_attempted += 1;
             state.results.clear();
 
+            let mut review = None;
             if let Some(cloze) = state.current_cloze(model) {
                 let blanks: Vec<&str> = cloze
                     .segments
@@ -98,7 +104,46 @@ pub fn update(
                         state.correct_count += 1;
                     }
                 }
+
+                let answers = (0..blanks.len())
+                    .map(|blank_idx| ReviewAnswer {
+                        given: state
+                            .answers
+                            .get(&blank_idx)
+                            .map(|s| s.trim().to_string())
+                            .unwrap_or_default(),
+                        correct: state.results.get(&blank_idx).copied().unwrap_or(false),
+                    })
+                    .collect();
+                review = Some(
+                    ReviewLog::builder()
+                        .cloze_id(cloze.id)
+                        .answers(answers)
+                        .duration(state.shown_at.map(|t| t.elapsed()).unwrap_or_default())
+                        .build(),
+                );
+            }
+
+            let Some(review) = review else {
+                return iced::Task::none();
+            };
+            let cloze_id = review.cloze_id;
+
+            // Only the first attempt at a cloze in a session feeds the schedule
+            if state.graded.insert(cloze_id) {
+                let grade = ReviewGrade::from_review(&review);
+                let now = review.reviewed_at;
+                let current = model.schedule_registry.get_or_new(cloze_id, now);
+                let next = Scheduler::default().review(&current, grade, now);
+                tracing::debug!(
+                    cloze_id = %cloze_id,
+                    ?grade,
+                    interval_days = next.interval_days,
+                    "Scheduled cloze review"
+                );
+                model.schedule_registry.upsert(next);
             }
+            model.review_registry.add(review);
         }
         PracticeMessage::SkipCloze => {
             if state.current_index + 1 < state.session_clozes.len() {
@@ -1,3 +1,5 @@
+use chrono::Utc;
+
 use crate::i18n::I18nManager;
 use crate::models::cloze::ClozeSegment;
 use crate::state::Model;
@@ -7,6 +9,7 @@ use crate::ui::practice::state::PracticeState;
 use crate::ui::theme::{ButtonSize, FontSize, Spacing};
 use crate::ui::widgets::button;
 use crate::ui::widgets::container::card;
+use crate::ui::widgets::svg_checkbox;
 use crate::ui::widgets::text as txt;
 use iced::widget::{Button, Column, Container, Row, Scrollable, Text, TextInput, rule};
 use iced::{Alignment, Element, Length};
@@ -32,8 +35,7 @@ fn setup_view<'a>(
 ) -> Element<'a, PracticeMessage, AppTheme> {
     let tag_name = state
         .tag_filter
-        .and_then(|id| model.tag_registry.get(id))
-        .map(|t| t.name.clone())
+        .and_then(|id| model.tag_registry.path(id))
         .unwrap_or_else(|| i18n.tr("practice-all-clozes").to_string());
 
     let clozes_available = count_available_clozes(state, model);
@@ -128,17 +130,25 @@ fn build_tag_picker<'a>(
 
     let mut tag_items: Vec<Element<'a, PracticeMessage, AppTheme>> = vec![all_tags_btn.into()];
 
+    // Tags are listed by path so nested tags with the same name can be told
+    // apart, and `Travel/F` finds `Travel/Food`
     let search_lower = state.tag_search.to_lowercase();
-    for (id, tag) in model.tag_registry.iter() {
-        if !state.tag_search.is_empty() && !tag.name.to_lowercase().contains(&search_lower) {
+    let mut tags: Vec<_> = model
+        .tag_registry
+        .iter()
+        .filter_map(|(id, _)| Some((*id, model.tag_registry.path(*id)?)))
+        .collect();
+    tags.sort_by_key(|(_, path)| path.to_lowercase());
+    for (id, path) in tags {
+        if !state.tag_search.is_empty() && !path.to_lowercase().contains(&search_lower) {
             continue;
         }
-        let is_selected = state.tag_filter == Some(*id);
-        let meaning_count = model.meaning_registry.iter_by_tag(*id).count();
+        let is_selected = state.tag_filter == Some(id);
+        let meaning_count = model.meaning_registry.iter_by_tag(id).count();
 
         let btn = Button::new(
             Row::new()
-                .push(Text::new(&tag.name).size(FontSize::Body.px()))
+                .push(Text::new(path).size(FontSize::Body.px()))
                 .push(
                     Text::new(format!(" ({})", meaning_count))
                         .size(FontSize::Footnote.px())
@@ -153,16 +163,26 @@ fn build_tag_picker<'a>(
         })
         .padding(ButtonSize::Small.to_iced_padding())
         .width(Length::Fill)
-        .on_press(PracticeMessage::TagFilterSelected(*id));
+        .on_press(PracticeMessage::TagFilterSelected(id));
 
         tag_items.push(btn.into());
     }
 
     let list = Column::with_children(tag_items).spacing(Spacing::DEFAULT.xxs);
 
+    let subtags_toggle = Row::new()
+        .push(svg_checkbox(
+            state.include_subtags,
+            PracticeMessage::IncludeSubtagsToggled(!state.include_subtags),
+        ))
+        .push(Text::new(i18n.tr("practice-include-subtags")).size(FontSize::Body.px()))
+        .spacing(Spacing::DEFAULT.xs)
+        .align_y(Alignment::Center);
+
     Column::new()
         .spacing(Spacing::DEFAULT.s)
         .push(search_input)
+        .push(subtags_toggle)
         .push(Scrollable::new(list).height(Length::Fixed(200.0)))
         .into()
 }
@@ -259,7 +279,7 @@ fn practice_view<'a>(
                             iced::widget::text_input::Style {
                                 background: colors.semantic.surface.raised.into(),
                                 border: iced::Border {
-                                    color: colors.semantic.interactive.primary.into(),
+                                    color: colors.semantic.interactive.primary,
                                     width: 1.0,
                                     radius: Spacing::DEFAULT.xs.into(),
                                 },
@@ -354,6 +374,17 @@ fn practice_view<'a>(
                         .then_some(PracticeMessage::NextCloze),
                 ),
         );
+
+        if let Some(schedule) = cloze.and_then(|c| model.schedule_registry.get(c.id)) {
+            actions = actions.push(
+                Text::new(i18n.tr_with(
+                    "practice-next-review",
+                    &[&schedule.interval_days.to_string()],
+                ))
+                .size(FontSize::Footnote.px())
+                .style(txt::secondary),
+            );
+        }
     }
 
     let scrollable_content = Column::new()
@@ -417,15 +448,12 @@ fn complete_view<'a>(
 }
 
 fn count_available_clozes(state: &PracticeState, model: &Model) -> usize {
-    if let Some(tag_id) = state.tag_filter {
-        let mut count = 0;
-        for (meaning_id, _) in model.meaning_registry.iter_by_tag(tag_id) {
-            count += model.cloze_registry.count_by_meaning(*meaning_id);
-        }
-        count
-    } else {
-        model.cloze_registry.count()
-    }
+    let now = Utc::now();
+    state
+        .candidate_clozes(model)
+        .into_iter()
+        .filter(|cloze_id| model.schedule_registry.is_due(*cloze_id, now))
+        .count()
 }
 
 fn default_input_style(
@@ -436,7 +464,7 @@ fn default_input_style(
     iced::widget::text_input::Style {
         background: colors.semantic.surface.raised.into(),
         border: iced::Border {
-            color: colors.semantic.interactive.primary.into(),
+            color: colors.semantic.interactive.primary,
             width: 1.0,
             radius: Spacing::DEFAULT.xs.into(),
         },
@@ -455,7 +483,7 @@ fn correct_input_style(
     iced::widget::text_input::Style {
         background: colors.functional.success.w50().into(),
         border: iced::Border {
-            color: colors.functional.success.w200().into(),
+            color: colors.functional.success.w200(),
             width: 1.0,
             radius: Spacing::DEFAULT.xs.into(),
         },
@@ -474,7 +502,7 @@ fn incorrect_input_style(
     iced::widget::text_input::Style {
         background: colors.functional.danger.w50().into(),
         border: iced::Border {
-            color: colors.functional.danger.w200().into(),
+            color: colors.functional.danger.w200(),
             width: 1.0,
             radius: Spacing::DEFAULT.xs.into(),
         },
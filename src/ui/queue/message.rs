@@ -3,8 +3,9 @@
 //! Messages are organized hierarchically by domain:
 //! - Selection: Item selection
 //! - Action: Queue operations
+//! - Candidate: Reviewing generated candidates
 
-use crate::models::WordId;
+use crate::models::{ClozeId, WordId};
 
 /// Root message enum for Queue panel.
 ///
@@ -15,6 +16,18 @@ pub enum QueueMessage {
     Selection(QueueSelectionMessage),
     /// Action-related messages
     Action(QueueActionMessage),
+    /// Candidate review messages
+    Candidate(QueueCandidateMessage),
+}
+
+impl QueueMessage {
+    /// Returns true if the message changes the queue in a way worth undoing.
+    pub fn is_undoable(&self) -> bool {
+        !matches!(
+            self,
+            Self::Selection(_) | Self::Action(QueueActionMessage::Process)
+        )
+    }
 }
 
 /// Selection-related messages for queue items.
@@ -38,3 +51,14 @@ pub enum QueueActionMessage {
     /// Remove a specific item from queue
     Remove(WordId),
 }
+
+/// Review messages for generated candidates of a queue item.
+#[derive(Debug, Clone)]
+pub enum QueueCandidateMessage {
+    /// Add the candidate to the cloze registry
+    Accept(WordId, ClozeId),
+    /// Discard the candidate
+    Reject(WordId, ClozeId),
+    /// Replace the candidate with a newly generated one
+    Regenerate(WordId, ClozeId),
+}
@@ -1,9 +1,11 @@
 //! Queue panel update handler.
 
-use super::message::{QueueActionMessage, QueueMessage, QueueSelectionMessage};
+use super::message::{
+    QueueActionMessage, QueueCandidateMessage, QueueMessage, QueueSelectionMessage,
+};
 use crate::message::Message;
 use crate::state::Model;
-use crate::state::process;
+use crate::state::{process, regenerate};
 use iced::Task;
 
 /// Handles all queue-related messages.
@@ -31,13 +33,7 @@ pub fn update(message: QueueMessage, model: &mut Model) -> Task<Message> {
         },
         QueueMessage::Action(msg) => match msg {
             QueueActionMessage::Process => {
-                let generator = model.generator.generator();
-                return process(
-                    &mut model.queue_registry,
-                    &generator,
-                    &model.word_registry,
-                    &model.meaning_registry,
-                );
+                return process(model);
             }
             QueueActionMessage::ClearCompleted => {
                 model.queue_registry.clear_completed();
@@ -46,6 +42,19 @@ pub fn update(message: QueueMessage, model: &mut Model) -> Task<Message> {
                 model.queue_registry.remove(item_id);
             }
         },
+        QueueMessage::Candidate(msg) => match msg {
+            QueueCandidateMessage::Accept(item_id, cloze_id) => {
+                if let Some(cloze) = model.queue_registry.take_candidate(item_id, cloze_id) {
+                    model.cloze_registry.add(cloze);
+                }
+            }
+            QueueCandidateMessage::Reject(item_id, cloze_id) => {
+                model.queue_registry.take_candidate(item_id, cloze_id);
+            }
+            QueueCandidateMessage::Regenerate(item_id, cloze_id) => {
+                return regenerate(model, item_id, cloze_id);
+            }
+        },
     }
     Task::none()
 }
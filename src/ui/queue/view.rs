@@ -1,13 +1,16 @@
 //! Queue panel view function.
 
-use super::message::{QueueActionMessage, QueueMessage, QueueSelectionMessage};
+use super::message::{
+    QueueActionMessage, QueueCandidateMessage, QueueMessage, QueueSelectionMessage,
+};
 use crate::i18n::I18nManager;
 use crate::models::types::MeaningId;
-use crate::registry::QueueItemStatus;
+use crate::registry::{QueueItem, QueueItemStatus};
 use crate::state::Model;
 use crate::ui::theme::{AppTheme, ButtonSize, FontSize, Spacing};
 use crate::ui::widgets::svg_checkbox;
 use iced::Element;
+use iced::widget::button::{Status as ButtonStatus, Style as ButtonStyle};
 use iced::widget::{Button, Column, Row, Text};
 
 use crate::ui::widgets::button;
@@ -16,6 +19,7 @@ fn status_label(status: &QueueItemStatus, i18n: &I18nManager) -> String {
     match status {
         QueueItemStatus::Pending => i18n.tr("queue-pending").to_string(),
         QueueItemStatus::Processing => i18n.tr("queue-processing").to_string(),
+        QueueItemStatus::Review => i18n.tr("queue-review").to_string(),
         QueueItemStatus::Completed => i18n.tr("queue-done").to_string(),
         QueueItemStatus::Failed(err) => i18n.tr_with("queue-failed", &[err]),
     }
@@ -37,6 +41,47 @@ fn meaning_content(
     }
 }
 
+/// Candidate sentences of an item under review, each with accept, reject
+/// and regenerate buttons.
+fn candidate_list<'a>(
+    item: &'a QueueItem,
+    i18n: &'a I18nManager,
+) -> Element<'a, QueueMessage, AppTheme> {
+    let rows = item.candidates.iter().map(|cloze| {
+        let action = |label: &str, style: fn(&AppTheme, ButtonStatus) -> ButtonStyle, message| {
+            Button::new(Text::new(i18n.tr(label).to_string()).size(FontSize::Footnote.px()))
+                .style(style)
+                .padding(ButtonSize::Small.to_iced_padding())
+                .on_press(QueueMessage::Candidate(message))
+        };
+        Row::new()
+            .push(Text::new(cloze.render_answers()).width(iced::Length::Fill))
+            .push(action(
+                "queue-accept",
+                button::primary,
+                QueueCandidateMessage::Accept(item.id, cloze.id),
+            ))
+            .push(action(
+                "queue-regenerate",
+                button::secondary,
+                QueueCandidateMessage::Regenerate(item.id, cloze.id),
+            ))
+            .push(action(
+                "queue-reject",
+                button::danger,
+                QueueCandidateMessage::Reject(item.id, cloze.id),
+            ))
+            .spacing(Spacing::DEFAULT.xs)
+            .align_y(iced::Alignment::Center)
+            .into()
+    });
+
+    Column::with_children(rows)
+        .spacing(Spacing::DEFAULT.xs)
+        .padding(iced::Padding::ZERO.left(Spacing::DEFAULT.l))
+        .into()
+}
+
 pub fn view<'a>(model: &'a Model, i18n: &'a I18nManager) -> Element<'a, QueueMessage, AppTheme> {
     let queue_registry = &model.queue_registry;
     let meaning_registry = &model.meaning_registry;
@@ -69,14 +114,23 @@ pub fn view<'a>(model: &'a Model, i18n: &'a I18nManager) -> Element<'a, QueueMes
                 .padding(ButtonSize::Small.to_iced_padding())
                 .on_press(QueueMessage::Action(QueueActionMessage::Remove(item_id)));
 
-            Row::new()
+            let row = Row::new()
                 .push(select_indicator)
                 .push(Text::new(content).width(iced::Length::Fill))
                 .push(Text::new(status_text_for_row).size(FontSize::Footnote.px()))
                 .push(remove_btn)
                 .spacing(Spacing::DEFAULT.s)
-                .align_y(iced::Alignment::Center)
-                .into()
+                .align_y(iced::Alignment::Center);
+
+            if queue_item.candidates.is_empty() {
+                row.into()
+            } else {
+                Column::new()
+                    .push(row)
+                    .push(candidate_list(queue_item, i18n))
+                    .spacing(Spacing::DEFAULT.xs)
+                    .into()
+            }
         })
         .collect();
 
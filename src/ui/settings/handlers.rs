@@ -1,7 +1,14 @@
-use crate::state::Model;
+use crate::backup::{self, Archive, RestoreMode};
+use crate::config::ProfileConfig;
+use crate::i18n::I18nManager;
+use crate::integrity;
+use crate::persistence::{SnapshotReason, SnapshotStore};
+use crate::state::{History, Model};
+use crate::ui::notification::NotificationLevel;
 use crate::ui::settings::SettingsState;
 use crate::ui::settings::message::{
-    GeneralSettingsMessage, ModelMessage, ProviderMessage, SettingsMessage,
+    DataMessage, GeneralSettingsMessage, ModelMessage, ProviderMessage, SettingsMessage,
+    TemplateMessage,
 };
 use iced::Task;
 use std::sync::Arc;
@@ -119,6 +126,11 @@ pub fn model_handler(
                 if config.ai.selected_model_id == Some(uuid) {
                     config.ai.selected_model_id = None;
                 }
+                for profile in &mut config.profiles {
+                    if profile.selected_model_id == Some(uuid) {
+                        profile.selected_model_id = None;
+                    }
+                }
                 config.save_to_file();
             }
         }
@@ -154,8 +166,17 @@ pub fn model_handler(
         ModelMessage::Select(id) => {
             let uuid = Uuid::from(id);
             if let Some(config) = Arc::get_mut(&mut model.app_config) {
-                config.ai.selected_model_id = Some(uuid);
-                model.generator.load_from_config(&config.ai);
+                config.set_selected_model_id(Some(uuid));
+                model.generator.load_from_config(config);
+                config.save_to_file();
+            }
+        }
+        ModelMessage::TemplateChanged(template_id) => {
+            state.model_edit.data.template_id = template_id;
+        }
+        ModelMessage::CandidatesChanged(count) => {
+            if let Some(config) = Arc::get_mut(&mut model.app_config) {
+                config.ai.candidates_per_request = count;
                 config.save_to_file();
             }
         }
@@ -163,17 +184,246 @@ pub fn model_handler(
     Task::none()
 }
 
+/// Handle prompt template messages.
+pub fn template(state: &mut SettingsState, message: TemplateMessage) -> Task<SettingsMessage> {
+    match message {
+        TemplateMessage::Preview(template_id) => {
+            state.preview_template = template_id;
+        }
+    }
+    Task::none()
+}
+
+/// Handle data management messages.
+pub fn data(
+    state: &mut SettingsState,
+    message: DataMessage,
+    model: &mut Model,
+    i18n: &I18nManager,
+) -> Task<SettingsMessage> {
+    let notify = |level, message: String| Task::done(SettingsMessage::Notify { level, message });
+
+    match message {
+        DataMessage::Backup => {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("Clozer backup", &["json"])
+                .set_file_name("clozer-backup.json")
+                .save_file()
+            else {
+                return Task::none();
+            };
+            let archive = Archive::from_model(model);
+            if let Err(e) = archive.save(&path) {
+                tracing::error!(error = %e, "Failed to write backup");
+                return notify(
+                    NotificationLevel::Error,
+                    i18n.tr_with("notification-backup-failed", &[&e.to_string()]),
+                );
+            }
+            notify(
+                NotificationLevel::Info,
+                i18n.tr_with(
+                    "notification-backup-done",
+                    &[&archive.words.len().to_string()],
+                ),
+            )
+        }
+        DataMessage::Restore(mode) => {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("Clozer backup", &["json"])
+                .pick_file()
+            else {
+                return Task::none();
+            };
+            let archive = match Archive::load(&path)
+                .and_then(|a| backup::validate(&a, mode, model).map(|()| a))
+            {
+                Ok(archive) => archive,
+                Err(e) => {
+                    tracing::error!(error = %e, path = ?path, "Failed to read backup");
+                    return notify(
+                        NotificationLevel::Error,
+                        i18n.tr_with("notification-restore-failed", &[&e.to_string()]),
+                    );
+                }
+            };
+            if let Err(e) = model.snapshot(SnapshotReason::Import) {
+                tracing::error!(error = %e, "Failed to snapshot before restore");
+                return notify(
+                    NotificationLevel::Error,
+                    i18n.tr_with("notification-restore-snapshot-failed", &[&e.to_string()]),
+                );
+            }
+            state.snapshots = SnapshotStore::new(&model.app_config.library_dir()).list();
+            let result = backup::restore(model, archive, mode);
+            match result {
+                Ok(summary) => {
+                    // Earlier edits may refer to entities the archive replaced
+                    model.history = History::new();
+                    // Replace discards data, so persist immediately rather than on exit
+                    if mode == RestoreMode::Replace
+                        && let Err(e) = model.flush_all()
+                    {
+                        tracing::error!(error = %e, "Failed to flush after restore");
+                    }
+                    let counts = [
+                        summary.words,
+                        summary.meanings,
+                        summary.tags,
+                        summary.clozes,
+                        summary.queue_items,
+                    ]
+                    .map(|n| n.to_string());
+                    let args: Vec<&str> = counts.iter().map(String::as_str).collect();
+                    notify(
+                        NotificationLevel::Info,
+                        i18n.tr_with("notification-restore-done", &args),
+                    )
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, path = ?path, "Failed to restore backup");
+                    notify(
+                        NotificationLevel::Error,
+                        i18n.tr_with("notification-restore-failed", &[&e.to_string()]),
+                    )
+                }
+            }
+        }
+        DataMessage::Check => {
+            let issues = integrity::check(model);
+            if issues.is_empty() {
+                return notify(NotificationLevel::Info, "No problems found".to_string());
+            }
+            let orphans = issues.iter().filter(|i| i.is_orphan()).count();
+            let first = issues[0];
+            notify(
+                NotificationLevel::Warning,
+                format!(
+                    "Found {} problem(s), {} of them orphaned data (e.g. {})",
+                    issues.len(),
+                    orphans,
+                    first
+                ),
+            )
+        }
+        DataMessage::RefreshSnapshots => {
+            state.snapshots = SnapshotStore::new(&model.app_config.library_dir()).list();
+            Task::none()
+        }
+        DataMessage::Snapshot => {
+            let result = model.snapshot(SnapshotReason::Manual);
+            state.snapshots = SnapshotStore::new(&model.app_config.library_dir()).list();
+            match result {
+                Ok(_) => notify(NotificationLevel::Info, "Snapshot saved".to_string()),
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to create snapshot");
+                    notify(NotificationLevel::Error, format!("Snapshot failed: {}", e))
+                }
+            }
+        }
+        DataMessage::RestoreSnapshot(path) => {
+            state.pending_restore = Some(path);
+            Task::none()
+        }
+        DataMessage::CancelRestoreSnapshot => {
+            state.pending_restore = None;
+            Task::none()
+        }
+        DataMessage::ConfirmRestoreSnapshot => {
+            let Some(path) = state.pending_restore.take() else {
+                return Task::none();
+            };
+            let result = model.restore_snapshot(&path);
+            state.snapshots = SnapshotStore::new(&model.app_config.library_dir()).list();
+            match result {
+                Ok(()) => notify(
+                    NotificationLevel::Info,
+                    format!("Restored {} words", model.word_registry.count()),
+                ),
+                Err(e) => {
+                    tracing::error!(error = %e, path = ?path, "Failed to restore snapshot");
+                    notify(NotificationLevel::Error, format!("Restore failed: {}", e))
+                }
+            }
+        }
+        DataMessage::ProfileNameChanged(name) => {
+            state.new_profile = name;
+            Task::none()
+        }
+        DataMessage::AddProfile => {
+            let name = state.new_profile.trim().to_string();
+            if !ProfileConfig::is_valid_name(&name) {
+                return notify(
+                    NotificationLevel::Warning,
+                    format!("Invalid profile name: {:?}", name),
+                );
+            }
+            let Some(config) = Arc::get_mut(&mut model.app_config) else {
+                return Task::none();
+            };
+            if config.profiles.iter().any(|p| p.name == name) {
+                return notify(
+                    NotificationLevel::Warning,
+                    format!("Profile {} already exists", name),
+                );
+            }
+            config.profiles.push(ProfileConfig::new(&name));
+            config.save_to_file();
+            state.new_profile.clear();
+            Task::done(SettingsMessage::Data(DataMessage::OpenProfile(Some(name))))
+        }
+        // Handled by compositor::update_settings which routes to Message::SwitchProfile
+        DataMessage::OpenProfile(_) => Task::none(),
+        DataMessage::RepairAll => {
+            state.pending_repair_all = true;
+            Task::none()
+        }
+        DataMessage::CancelRepairAll => {
+            state.pending_repair_all = false;
+            Task::none()
+        }
+        DataMessage::ConfirmRepairAll => {
+            state.pending_repair_all = false;
+            data(
+                state,
+                DataMessage::Repair(integrity::RepairOptions::all()),
+                model,
+                i18n,
+            )
+        }
+        DataMessage::Repair(options) => {
+            let (fixed, _) = model.record(|m| integrity::repair(m, options));
+            if let Err(e) = model.flush_all() {
+                tracing::error!(error = %e, "Failed to flush after repair");
+                return notify(NotificationLevel::Error, format!("Repair failed: {}", e));
+            }
+            let remaining = integrity::check(model).len();
+            notify(
+                NotificationLevel::Info,
+                format!(
+                    "Repaired {} problem(s), {} remaining",
+                    fixed.len(),
+                    remaining
+                ),
+            )
+        }
+    }
+}
+
 /// Handle all settings-related messages.
 pub fn update(
     state: &mut SettingsState,
     message: SettingsMessage,
     model: &mut Model,
+    i18n: &I18nManager,
 ) -> Task<SettingsMessage> {
     use SettingsMessage::*;
     match message {
         General(msg) => general(state, msg, model),
         Provider(msg) => provider(state, msg, model),
         Model(msg) => model_handler(state, msg, model),
-        ThemeChanged(_) => Task::none(),
+        Template(msg) => template(state, msg),
+        Data(msg) => data(state, msg, model, i18n),
+        ThemeChanged(_) | Notify { .. } => Task::none(),
     }
 }
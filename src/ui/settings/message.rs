@@ -4,12 +4,19 @@
 //! - General: General settings
 //! - Provider: AI provider CRUD
 //! - Model: AI model CRUD and selection
+//! - Template: Prompt template preview
+//! - Data: Backup, restore, snapshots, integrity repair and profiles
 
+use crate::backup::RestoreMode;
 use crate::config::LogLevel;
 use crate::config::file::ai::ProviderTypeDto;
 use crate::i18n::LocaleDto;
+use crate::integrity::RepairOptions;
 use crate::models::types::{ModelId, ProviderId};
 use crate::ui::AppTheme;
+use crate::ui::notification::NotificationLevel;
+use std::path::PathBuf;
+use uuid::Uuid;
 
 /// Root message enum for Settings panel.
 ///
@@ -22,8 +29,17 @@ pub enum SettingsMessage {
     Provider(ProviderMessage),
     /// AI Model messages
     Model(ModelMessage),
+    /// Prompt template messages
+    Template(TemplateMessage),
+    /// Data management messages
+    Data(DataMessage),
     /// Theme changed
     ThemeChanged(AppTheme),
+    /// Trigger a notification (converted by compositor to PushNotification)
+    Notify {
+        level: NotificationLevel,
+        message: String,
+    },
 }
 
 /// General settings messages.
@@ -79,4 +95,50 @@ pub enum ModelMessage {
     ModelIdChanged(String),
     /// Select a model as active
     Select(ModelId),
+    /// Candidate sentences per generation request changed
+    CandidatesChanged(usize),
+    /// Model prompt template changed (None = built-in default)
+    TemplateChanged(Option<Uuid>),
+}
+
+/// Prompt template messages.
+#[derive(Debug, Clone)]
+pub enum TemplateMessage {
+    /// Show the rendered prompt of a template (None = built-in default)
+    Preview(Option<Uuid>),
+}
+
+/// Data management messages.
+#[derive(Debug, Clone)]
+pub enum DataMessage {
+    /// Write a backup archive
+    Backup,
+    /// Restore a backup archive
+    Restore(RestoreMode),
+    /// Report integrity issues without changing anything
+    Check,
+    /// Repair integrity issues
+    Repair(RepairOptions),
+    /// Ask to repair and delete orphaned data
+    RepairAll,
+    /// Repair and delete orphaned data after confirmation
+    ConfirmRepairAll,
+    /// Cancel the pending orphan deletion
+    CancelRepairAll,
+    /// Reload the snapshot list
+    RefreshSnapshots,
+    /// Take a snapshot now
+    Snapshot,
+    /// Ask to restore a snapshot
+    RestoreSnapshot(PathBuf),
+    /// Restore the snapshot awaiting confirmation
+    ConfirmRestoreSnapshot,
+    /// Cancel the pending snapshot restore
+    CancelRestoreSnapshot,
+    /// New profile name changed
+    ProfileNameChanged(String),
+    /// Create a profile with the entered name and open it
+    AddProfile,
+    /// Open a profile (None = default library), routed to Message::SwitchProfile
+    OpenProfile(Option<String>),
 }
@@ -3,9 +3,15 @@
 //! State is organized with focused sub-states:
 //! - ProviderEditState: Provider editing state
 //! - ModelEditState: Model editing state
+//! - preview_template: Prompt template preview selection
+//! - snapshots: Database snapshots and pending restore confirmation
+//! - pending_repair_all: Pending orphan deletion confirmation
+//! - new_profile: Name entered for a new profile
 
 use crate::config::file::ai::{ModelConfig, ProviderConfig};
 use crate::models::types::{ModelId, ProviderId};
+use crate::persistence::Snapshot;
+use std::path::PathBuf;
 use uuid::Uuid;
 
 /// Editing state for providers.
@@ -99,6 +105,7 @@ impl Default for ModelEditState {
                 name: String::new(),
                 provider_id: Uuid::nil(),
                 model_id: String::new(),
+                template_id: None,
             },
             is_new: false,
         }
@@ -115,6 +122,7 @@ impl ModelEditState {
                 name: String::new(),
                 provider_id: Uuid::nil(),
                 model_id: String::new(),
+                template_id: None,
             },
             is_new: true,
         }
@@ -142,6 +150,7 @@ impl ModelEditState {
             name: String::new(),
             provider_id: Uuid::nil(),
             model_id: String::new(),
+            template_id: None,
         };
         self.is_new = false;
     }
@@ -154,6 +163,16 @@ pub struct SettingsState {
     pub provider_edit: ProviderEditState,
     /// Model editing state
     pub model_edit: ModelEditState,
+    /// Prompt template shown in the preview (None = built-in default)
+    pub preview_template: Option<Uuid>,
+    /// Database snapshots, newest first (refreshed when the panel opens)
+    pub snapshots: Vec<Snapshot>,
+    /// Snapshot awaiting restore confirmation
+    pub pending_restore: Option<PathBuf>,
+    /// Orphan deletion awaiting confirmation
+    pub pending_repair_all: bool,
+    /// Name entered for a new profile
+    pub new_profile: String,
 }
 
 impl SettingsState {
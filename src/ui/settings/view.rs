@@ -1,9 +1,16 @@
-use super::message::{GeneralSettingsMessage, ModelMessage, ProviderMessage, SettingsMessage};
+use super::message::{
+    DataMessage, GeneralSettingsMessage, ModelMessage, ProviderMessage, SettingsMessage,
+    TemplateMessage,
+};
 use super::state::SettingsState;
-use crate::config::file::ai::{AiConfig, ProviderTypeDto};
+use crate::backup::RestoreMode;
+use crate::config::AppConfig;
+use crate::config::file::ai::{AiConfig, MAX_CANDIDATES_PER_REQUEST, ProviderTypeDto};
 use crate::i18n::{I18nManager, LocaleDto};
+use crate::integrity::RepairOptions;
 use crate::models::types::{ModelId, ProviderId};
 use crate::state::Model;
+use crate::state::prompt::{self, PromptContext};
 use crate::ui::theme::{AppTheme, ButtonSize, FontSize, Spacing};
 use crate::ui::widgets::AdvancedInput;
 use iced::Element;
@@ -48,6 +55,34 @@ impl ProviderOption {
     }
 }
 
+/// Prompt template choice; `id: None` is the built-in default.
+#[derive(Clone, PartialEq)]
+struct TemplateOption {
+    id: Option<Uuid>,
+    name: String,
+}
+
+impl std::fmt::Display for TemplateOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl TemplateOption {
+    /// The built-in default followed by every configured template.
+    fn all(ai_config: &AiConfig, i18n: &I18nManager) -> Vec<Self> {
+        std::iter::once(Self {
+            id: None,
+            name: i18n.tr("settings-template-default").to_string(),
+        })
+        .chain(ai_config.templates.iter().map(|t| Self {
+            id: Some(t.id),
+            name: t.name.clone(),
+        }))
+        .collect()
+    }
+}
+
 pub fn view<'a>(
     state: &'a SettingsState,
     model: &'a Model,
@@ -105,11 +140,12 @@ pub fn view<'a>(
     let models_section = if state.model_edit.is_new || state.model_edit.editing_id.is_some() {
         render_model_form(state, ai_config, i18n)
     } else {
-        render_model_list(ai_config, i18n)
+        render_model_list(ai_config, model.app_config.selected_model_id(), i18n)
     };
 
-    let (selected_name, _) = ai_config
-        .selected_model_id
+    let (selected_name, _) = model
+        .app_config
+        .selected_model_id()
         .and_then(|id| ai_config.models.iter().find(|m| m.id == id))
         .map(|m| (m.name.clone(), true))
         .unwrap_or((i18n.tr("settings-none").to_string(), false));
@@ -122,17 +158,105 @@ pub fn view<'a>(
                 .push(text(selected_name))
                 .spacing(Spacing::DEFAULT.s),
         )
+        .push(
+            Row::new()
+                .push(text(i18n.tr("settings-candidates")))
+                .push(
+                    PickList::new(
+                        (1..=MAX_CANDIDATES_PER_REQUEST).collect::<Vec<_>>(),
+                        Some(ai_config.candidates()),
+                        |count| SettingsMessage::Model(ModelMessage::CandidatesChanged(count)),
+                    )
+                    .width(iced::Length::Fixed(80.0)),
+                )
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center),
+        )
         .spacing(Spacing::DEFAULT.s);
 
+    let templates_section = render_templates(state, ai_config, i18n);
+
     let data_dir_section = Column::new()
         .push(text(i18n.tr("settings-data")).size(FontSize::Title.px()))
         .push(text(format!(
             "{} {:?}",
             i18n.tr("settings-directory"),
-            model.app_config.data_dir
+            model.app_config.library_dir()
         )))
+        .push(
+            Row::new()
+                .push(
+                    Button::new(text(i18n.tr("settings-backup")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::Backup)),
+                )
+                .push(
+                    Button::new(text(i18n.tr("settings-restore-merge")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::Restore(
+                            RestoreMode::Merge,
+                        ))),
+                )
+                .push(
+                    Button::new(text(i18n.tr("settings-restore-replace")))
+                        .style(button::danger)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::Restore(
+                            RestoreMode::Replace,
+                        ))),
+                )
+                .spacing(Spacing::DEFAULT.s),
+        )
+        .push(
+            Row::new()
+                .push(
+                    Button::new(text(i18n.tr("settings-integrity-check")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::Check)),
+                )
+                .push(
+                    Button::new(text(i18n.tr("settings-integrity-repair-links")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::Repair(RepairOptions {
+                            fix_links: true,
+                            delete_orphans: false,
+                        }))),
+                )
+                .push(
+                    Button::new(text(i18n.tr("settings-integrity-repair-all")))
+                        .style(button::danger)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::RepairAll)),
+                )
+                .spacing(Spacing::DEFAULT.s),
+        )
+        .push(state.pending_repair_all.then(|| {
+            Row::new()
+                .push(text(i18n.tr("settings-integrity-repair-all-confirm")))
+                .push(
+                    Button::new(text(i18n.tr("settings-integrity-repair-all")))
+                        .style(button::danger)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::ConfirmRepairAll)),
+                )
+                .push(
+                    Button::new(text(i18n.tr("settings-cancel")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::CancelRepairAll)),
+                )
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center)
+        }))
         .spacing(Spacing::DEFAULT.s);
 
+    let snapshots_section = render_snapshots(state, i18n);
+    let profiles_section = render_profiles(state, &model.app_config, i18n);
+
     let content = Column::new()
         .push(text(i18n.tr("settings-title")).size(FontSize::Display.px()))
         .push(general_section)
@@ -143,7 +267,12 @@ pub fn view<'a>(
         .push(rule::horizontal(1))
         .push(selected_model_section)
         .push(rule::horizontal(1))
+        .push(templates_section)
+        .push(rule::horizontal(1))
         .push(data_dir_section)
+        .push(snapshots_section)
+        .push(rule::horizontal(1))
+        .push(profiles_section)
         .spacing(Spacing::DEFAULT.l)
         .padding(Spacing::DEFAULT.l);
 
@@ -275,8 +404,232 @@ fn render_provider_form(
         .spacing(Spacing::DEFAULT.s)
 }
 
+/// Profiles with an open action, plus a name field for creating one.
+fn render_profiles<'a>(
+    state: &'a SettingsState,
+    config: &AppConfig,
+    i18n: &I18nManager,
+) -> Column<'a, SettingsMessage, AppTheme> {
+    let default_name = i18n.tr("profile-default").to_string();
+    let names = std::iter::once((None, default_name)).chain(
+        config
+            .profiles
+            .iter()
+            .map(|p| (Some(p.name.clone()), p.name.clone())),
+    );
+
+    let items = names.map(|(profile, label)| {
+        let is_active = profile == config.profile;
+        let row = Row::new()
+            .push(text(label).width(iced::Length::Fill))
+            .spacing(Spacing::DEFAULT.s)
+            .align_y(iced::Alignment::Center);
+        let row = if is_active {
+            row.push(text(i18n.tr("settings-profile-active")))
+        } else {
+            row.push(
+                Button::new(text(i18n.tr("settings-profile-open")))
+                    .style(button::secondary)
+                    .padding(ButtonSize::Standard.to_iced_padding())
+                    .on_press(SettingsMessage::Data(DataMessage::OpenProfile(profile))),
+            )
+        };
+        Element::from(row)
+    });
+
+    let name_input = AdvancedInput::new(i18n.tr("settings-profile-name"))
+        .value(&state.new_profile)
+        .on_input(|s| SettingsMessage::Data(DataMessage::ProfileNameChanged(s)))
+        .on_submit(SettingsMessage::Data(DataMessage::AddProfile))
+        .width(iced::Length::Fill)
+        .padding(Spacing::DEFAULT.s);
+
+    Column::new()
+        .push(text(i18n.tr("settings-profiles")).size(FontSize::Title.px()))
+        .extend(items)
+        .push(
+            Row::new()
+                .push(Element::new(name_input))
+                .push(
+                    Button::new(text(i18n.tr("settings-profile-add")))
+                        .style(button::primary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::AddProfile)),
+                )
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center),
+        )
+        .spacing(Spacing::DEFAULT.s)
+}
+
+/// Database snapshots, newest first, each with a restore action that asks
+/// for confirmation inline.
+fn render_snapshots(
+    state: &SettingsState,
+    i18n: &I18nManager,
+) -> Column<'static, SettingsMessage, AppTheme> {
+    let header = Row::new()
+        .push(
+            text(i18n.tr("settings-snapshots"))
+                .size(FontSize::Subtitle.px())
+                .width(iced::Length::Fill),
+        )
+        .push(
+            Button::new(text(i18n.tr("settings-snapshot-refresh")))
+                .style(button::secondary)
+                .padding(ButtonSize::Standard.to_iced_padding())
+                .on_press(SettingsMessage::Data(DataMessage::RefreshSnapshots)),
+        )
+        .push(
+            Button::new(text(i18n.tr("settings-snapshot-now")))
+                .style(button::secondary)
+                .padding(ButtonSize::Standard.to_iced_padding())
+                .on_press(SettingsMessage::Data(DataMessage::Snapshot)),
+        )
+        .spacing(Spacing::DEFAULT.s)
+        .align_y(iced::Alignment::Center);
+
+    if state.snapshots.is_empty() {
+        return Column::new()
+            .push(header)
+            .push(text(i18n.tr("settings-snapshot-empty")).size(FontSize::Footnote.px()))
+            .spacing(Spacing::DEFAULT.s);
+    }
+
+    let items: Vec<Element<'static, SettingsMessage, AppTheme>> = state
+        .snapshots
+        .iter()
+        .map(|snapshot| {
+            let label = format!(
+                "{}  {}  {} KB",
+                snapshot
+                    .created_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                i18n.tr(&format!("settings-snapshot-reason-{}", snapshot.reason)),
+                snapshot.size.div_ceil(1024)
+            );
+            let row = Row::new()
+                .push(text(label).width(iced::Length::Fill))
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center);
+            let row = if state.pending_restore.as_ref() == Some(&snapshot.path) {
+                row.push(text(i18n.tr("settings-snapshot-confirm")))
+                    .push(
+                        Button::new(text(i18n.tr("settings-snapshot-restore")))
+                            .style(button::danger)
+                            .padding(ButtonSize::Standard.to_iced_padding())
+                            .on_press(SettingsMessage::Data(DataMessage::ConfirmRestoreSnapshot)),
+                    )
+                    .push(
+                        Button::new(text(i18n.tr("settings-cancel")))
+                            .style(button::secondary)
+                            .padding(ButtonSize::Standard.to_iced_padding())
+                            .on_press(SettingsMessage::Data(DataMessage::CancelRestoreSnapshot)),
+                    )
+            } else {
+                row.push(
+                    Button::new(text(i18n.tr("settings-snapshot-restore")))
+                        .style(button::secondary)
+                        .padding(ButtonSize::Standard.to_iced_padding())
+                        .on_press(SettingsMessage::Data(DataMessage::RestoreSnapshot(
+                            snapshot.path.clone(),
+                        ))),
+                )
+            };
+            row.into()
+        })
+        .collect();
+
+    Column::new()
+        .push(header)
+        .push(Column::with_children(items).spacing(Spacing::DEFAULT.xs))
+        .spacing(Spacing::DEFAULT.s)
+}
+
+/// Prompt templates with their scopes and a rendered preview of the
+/// selected one, using sample values.
+fn render_templates(
+    state: &SettingsState,
+    ai_config: &AiConfig,
+    i18n: &I18nManager,
+) -> Column<'static, SettingsMessage, AppTheme> {
+    let items: Vec<Element<'static, SettingsMessage, AppTheme>> = ai_config
+        .templates
+        .iter()
+        .map(|t| {
+            let models: Vec<&str> = ai_config
+                .models
+                .iter()
+                .filter(|m| m.template_id == Some(t.id))
+                .map(|m| m.name.as_str())
+                .collect();
+            let scope = t
+                .tags
+                .iter()
+                .map(|tag| format!("tag:{}", tag))
+                .chain(models.iter().map(|m| format!("model:{}", m)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Row::new()
+                .push(text(t.name.clone()).width(iced::Length::Fill))
+                .push(text(scope).size(FontSize::Footnote.px()))
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center)
+                .into()
+        })
+        .collect();
+
+    let options = TemplateOption::all(ai_config, i18n);
+    let selected = options
+        .iter()
+        .find(|t| t.id == state.preview_template)
+        .or(options.first())
+        .cloned();
+    let template = selected
+        .as_ref()
+        .and_then(|o| o.id)
+        .and_then(|id| ai_config.templates.iter().find(|t| t.id == id))
+        .map(|t| t.template.as_str())
+        .unwrap_or(prompt::DEFAULT_TEMPLATE);
+    let rendered = prompt::render(template, &PromptContext::sample(ai_config.candidates()));
+
+    let placeholders = prompt::PLACEHOLDERS
+        .iter()
+        .map(|p| format!("{{{}}}", p))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Column::new()
+        .push(text(i18n.tr("settings-templates")).size(FontSize::Title.px()))
+        .push(
+            text(i18n.tr_with("settings-template-placeholders", &[&placeholders]))
+                .size(FontSize::Footnote.px()),
+        )
+        .push(Column::with_children(items).spacing(Spacing::DEFAULT.xs))
+        .push(
+            Row::new()
+                .push(text(i18n.tr("settings-template-preview")))
+                .push(
+                    PickList::new(options, selected, |t| {
+                        SettingsMessage::Template(TemplateMessage::Preview(t.id))
+                    })
+                    .width(iced::Length::Fixed(200.0)),
+                )
+                .spacing(Spacing::DEFAULT.s)
+                .align_y(iced::Alignment::Center),
+        )
+        .push(
+            text(rendered)
+                .font(iced::Font::MONOSPACE)
+                .size(FontSize::Footnote.px()),
+        )
+        .spacing(Spacing::DEFAULT.s)
+}
+
 fn render_model_list(
     ai_config: &AiConfig,
+    selected_model_id: Option<uuid::Uuid>,
     i18n: &I18nManager,
 ) -> Column<'static, SettingsMessage, AppTheme> {
     let edit_label = i18n.tr("settings-edit");
@@ -300,7 +653,7 @@ fn render_model_list(
             let edit_id = ModelId::from(m.id);
             let delete_id = ModelId::from(m.id);
             let select_id = ModelId::from(m.id);
-            let is_selected = ai_config.selected_model_id == Some(m.id);
+            let is_selected = selected_model_id == Some(m.id);
 
             let select_element: Element<'static, SettingsMessage, AppTheme> = if is_selected {
                 text(active_label.clone()).into()
@@ -403,6 +756,22 @@ fn render_model_form(
         .spacing(Spacing::DEFAULT.s)
         .align_y(iced::Alignment::Center);
 
+    let template_options = TemplateOption::all(ai_config, i18n);
+    let current_template = template_options
+        .iter()
+        .find(|t| t.id == edit.data.template_id)
+        .cloned();
+    let template_picker = Row::new()
+        .push(text(i18n.tr("settings-template")))
+        .push(
+            PickList::new(template_options, current_template, |t| {
+                SettingsMessage::Model(ModelMessage::TemplateChanged(t.id))
+            })
+            .width(iced::Length::Fixed(160.0)),
+        )
+        .spacing(Spacing::DEFAULT.s)
+        .align_y(iced::Alignment::Center);
+
     let buttons = Row::new()
         .push(
             Button::new(text(save_label))
@@ -423,6 +792,7 @@ fn render_model_form(
         .push(Element::new(name_input))
         .push(provider_picker)
         .push(Element::new(model_id_input))
+        .push(template_picker)
         .push(buttons)
         .spacing(Spacing::DEFAULT.s)
 }
@@ -3,6 +3,7 @@
 //! Vertical tab strip with icon + label. Collapses to icon-only
 //! on medium screens, recedes to bottom tab bar on small screens.
 
+use crate::config::AppConfig;
 use crate::i18n::I18nManager;
 use crate::message::Message;
 use crate::ui::design_tokens::{FontSize, Spacing, TouchTargetSize};
@@ -10,12 +11,29 @@ use crate::ui::layout::breakpoint::Breakpoint;
 use crate::ui::nav::NavItem;
 use crate::ui::state::UiState;
 use crate::ui::theme::{AppTheme, BorderRadiusValues};
-use iced::widget::{Button, Column, Container, Row, Space, Text, Tooltip, tooltip};
+use iced::widget::{Button, Column, Container, PickList, Row, Space, Text, Tooltip, tooltip};
 use iced::{Alignment, Element, Font, Length, Padding};
 
+/// Profile choice in the switcher; `name: None` is the default library.
+#[derive(Clone, PartialEq)]
+struct ProfileOption {
+    name: Option<String>,
+    label: String,
+}
+
+impl std::fmt::Display for ProfileOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
 /// Build the full sidebar panel for the current state.
 /// Returns a no-op element if the bottom bar is being used instead.
-pub fn sidebar<'a>(state: &'a UiState, breakpoint: Breakpoint) -> Element<'a, Message, AppTheme> {
+pub fn sidebar<'a>(
+    state: &'a UiState,
+    breakpoint: Breakpoint,
+    config: &AppConfig,
+) -> Element<'a, Message, AppTheme> {
     if breakpoint.use_bottom_bar() {
         return Space::new().into();
     }
@@ -35,7 +53,7 @@ pub fn sidebar<'a>(state: &'a UiState, breakpoint: Breakpoint) -> Element<'a, Me
         })
         .collect();
 
-    // Secondary navigation items (e.g., Settings)
+    // Secondary navigation items (e.g., Trash, Settings)
     let secondary_items: Vec<Element<'a, Message, AppTheme>> = NavItem::secondary()
         .iter()
         .map(|&item| {
@@ -44,8 +62,12 @@ pub fn sidebar<'a>(state: &'a UiState, breakpoint: Breakpoint) -> Element<'a, Me
         })
         .collect();
 
-    Column::new()
-        .push(header)
+    let mut column = Column::new().push(header);
+    if expanded && !config.profiles.is_empty() {
+        column = column.push(profile_switcher(config, &state.i18n));
+    }
+
+    column
         .push(separator())
         .push(Space::new().height(Length::Fixed(Spacing::DEFAULT.s)))
         .push(Column::with_children(main_items).spacing(Spacing::DEFAULT.xxs))
@@ -89,6 +111,31 @@ fn sidebar_header<'a>(expanded: bool, i18n: &I18nManager) -> Element<'a, Message
     }
 }
 
+/// Pick list for switching between the default library and named profiles.
+fn profile_switcher<'a>(config: &AppConfig, i18n: &I18nManager) -> Element<'a, Message, AppTheme> {
+    let options: Vec<ProfileOption> = std::iter::once(ProfileOption {
+        name: None,
+        label: i18n.tr("profile-default").to_string(),
+    })
+    .chain(config.profiles.iter().map(|p| ProfileOption {
+        name: Some(p.name.clone()),
+        label: p.name.clone(),
+    }))
+    .collect();
+    let selected = options.iter().find(|o| o.name == config.profile).cloned();
+
+    Container::new(
+        PickList::new(options, selected, |o: ProfileOption| {
+            Message::SwitchProfile(o.name)
+        })
+        .placeholder(i18n.tr("sidebar-profile"))
+        .text_size(FontSize::Caption.px())
+        .width(Length::Fill),
+    )
+    .padding(Padding::from([0.0, Spacing::DEFAULT.s]).bottom(Spacing::DEFAULT.s))
+    .into()
+}
+
 /// Build a single sidebar navigation button.
 fn nav_button<'a>(
     item: NavItem,
@@ -152,6 +199,7 @@ fn nav_icon<'a>(item: NavItem, size: f32) -> Element<'a, Message, AppTheme> {
         NavItem::Queue => "\u{1F4CB}",
         NavItem::Tags => "\u{1F3F7}\u{FE0F}",
         NavItem::Practice => "\u{1F3AF}",
+        NavItem::Trash => "\u{1F5D1}\u{FE0F}",
         NavItem::Settings => "\u{2699}\u{FE0F}",
     };
     Text::new(icon_text)
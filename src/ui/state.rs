@@ -7,6 +7,7 @@ use crate::ui::notification::{Notification, NotificationLevel};
 use crate::ui::practice::state::PracticeState;
 use crate::ui::settings::state::SettingsState;
 use crate::ui::tags::state::TagsState;
+use crate::ui::trash::TrashState;
 use crate::ui::words::state::WordsState;
 
 /// UI presentation state for the single-window application.
@@ -21,6 +22,8 @@ pub struct UiState {
     pub tags: TagsState,
     /// Practice panel state
     pub practice: PracticeState,
+    /// Trash panel state
+    pub trash: TrashState,
     /// Settings panel state
     pub settings: SettingsState,
     /// Current navigation view
@@ -45,6 +48,7 @@ impl Default for UiState {
             words: WordsState::new(),
             tags: TagsState::new(),
             practice: PracticeState::new(),
+            trash: TrashState::new(),
             settings: SettingsState::new(),
             current_view: NavItem::default(),
             window_width: 1024,
@@ -69,13 +73,27 @@ impl UiState {
         level: NotificationLevel,
         message: impl Into<String>,
     ) -> usize {
+        self.push(Notification::new(0, level, message))
+    }
+
+    /// Push a prepared notification, replacing its ID with a unique one.
+    pub fn push(&mut self, mut notification: Notification) -> usize {
         let id = self.next_notification_id;
         self.next_notification_id += 1;
-        self.notifications
-            .push(Notification::new(id, level, message));
+        notification.id = id;
+        self.notifications.push(notification);
         id
     }
 
+    /// Resets panel state that refers to entities of the open library.
+    pub fn reset_panels(&mut self) {
+        self.words = WordsState::new();
+        self.tags = TagsState::new();
+        self.practice = PracticeState::new();
+        self.trash = TrashState::new();
+        self.settings = SettingsState::new();
+    }
+
     /// Remove a notification by ID.
     pub fn dismiss_notification(&mut self, id: usize) {
         self.notifications.retain(|n| n.id != id);
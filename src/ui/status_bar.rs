@@ -1,14 +1,18 @@
 //! Status bar — persistent notification bar at the window bottom.
 
+use crate::i18n::I18nManager;
 use crate::message::Message;
-use crate::ui::notification::{Notification, NotificationLevel};
+use crate::ui::notification::{Notification, NotificationAction, NotificationLevel};
 use crate::ui::theme::{AppTheme, Spacing};
 use crate::ui::widgets::button;
 use iced::widget::{Button, Column, Container, Row, Text};
 use iced::{Alignment, Element, Length};
 
 /// Renders a persistent status bar. Shows all active notifications stacked.
-pub fn status_bar<'a>(notifications: &'a [Notification]) -> Element<'a, Message, AppTheme> {
+pub fn status_bar<'a>(
+    notifications: &'a [Notification],
+    i18n: &I18nManager,
+) -> Element<'a, Message, AppTheme> {
     let notification_spacing = Spacing::DEFAULT.xxs;
     let padding = [Spacing::DEFAULT.xs, Spacing::DEFAULT.m];
     let height = 28.0;
@@ -30,10 +34,18 @@ pub fn status_bar<'a>(notifications: &'a [Notification]) -> Element<'a, Message,
                 NotificationLevel::Info => "\u{2139}",
             };
 
+            let action = n.action.map(|action| match action {
+                NotificationAction::Undo => Button::new(Text::new(i18n.tr("notification-undo")))
+                    .style(button::secondary)
+                    .padding([0.0, Spacing::DEFAULT.xs])
+                    .on_press(Message::Undo),
+            });
+
             Container::new(
                 Row::new()
                     .push(Text::new(icon))
                     .push(Text::new(&n.message).width(Length::Fill))
+                    .push(action)
                     .push(
                         Button::new(Text::new("\u{2715}"))
                             .style(button::secondary)
@@ -141,7 +141,7 @@ pub fn update(state: &mut TagsState, message: TagsMessage, model: &mut Model) ->
             state.reparenting = None;
         }
         TagsMessage::DeleteConfirmed(tag_id) => {
-            delete_tag_recursively(tag_id, &mut model.tag_registry, &mut model.meaning_registry);
+            model.trash_tag(tag_id);
             if state.selected == Some(tag_id) {
                 state.selected = None;
             }
@@ -214,44 +214,6 @@ pub fn update(state: &mut TagsState, message: TagsMessage, model: &mut Model) ->
     Task::none()
 }
 
-/// Recursively delete a tag and all its descendants.
-/// Removes tag references from all meanings.
-fn delete_tag_recursively(
-    tag_id: TagId,
-    tag_registry: &mut TagRegistry,
-    meaning_registry: &mut crate::registry::MeaningRegistry,
-) {
-    let children: Vec<TagId> = if let Some(tag) = tag_registry.get(tag_id) {
-        tag.children_ids.iter().copied().collect()
-    } else {
-        return;
-    };
-
-    // Recursively delete children first
-    for child_id in children {
-        delete_tag_recursively(child_id, tag_registry, meaning_registry);
-    }
-
-    // Remove this tag from all meanings
-    let meaning_ids: Vec<crate::models::types::MeaningId> = meaning_registry
-        .iter_by_tag(tag_id)
-        .map(|(id, _)| *id)
-        .collect();
-    for meaning_id in meaning_ids {
-        meaning_registry.remove_tag(meaning_id, tag_id);
-    }
-
-    // Remove from parent
-    let parent_id = tag_registry.get(tag_id).and_then(|t| t.parent_id);
-    if let Some(parent_id) = parent_id
-        && let Some(parent) = tag_registry.get_mut(parent_id)
-    {
-        parent.children_ids.remove(&tag_id);
-    }
-
-    tag_registry.delete(tag_id);
-}
-
 /// Check if `candidate` is a descendant of `ancestor`.
 fn is_descendant(ancestor: TagId, candidate: TagId, registry: &TagRegistry) -> bool {
     if let Some(tag) = registry.get(candidate)
@@ -0,0 +1,17 @@
+//! Trash panel message types.
+
+use crate::models::TrashId;
+
+/// Messages for the Trash panel.
+#[derive(Debug, Clone)]
+pub enum TrashMessage {
+    /// Put an entry back into the library
+    Restore(TrashId),
+    /// Permanently delete an entry
+    Delete(TrashId),
+    /// Ask for confirmation before emptying the trash
+    EmptyRequested,
+    /// Permanently delete every entry
+    EmptyConfirmed,
+    EmptyCancelled,
+}
@@ -0,0 +1,11 @@
+//! Trash panel module for restoring or purging deleted items.
+
+pub mod message;
+pub mod state;
+pub mod update;
+pub mod view;
+
+pub use self::message::TrashMessage;
+pub use self::state::TrashState;
+pub use self::update::update;
+pub use self::view::view;
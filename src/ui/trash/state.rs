@@ -0,0 +1,15 @@
+//! Trash panel UI state.
+
+/// State for the Trash panel.
+#[derive(Debug, Default)]
+pub struct TrashState {
+    /// Emptying the trash awaits confirmation
+    pub pending_empty: bool,
+}
+
+impl TrashState {
+    /// Creates a new TrashState.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,54 @@
+//! Trash panel update handler.
+
+use super::message::TrashMessage;
+use super::state::TrashState;
+use crate::message::Message;
+use crate::state::Model;
+use crate::ui::notification::Notification;
+use iced::Task;
+
+/// Handles all trash-related messages.
+///
+/// Returns `Task<Message>` so results can be reported as notifications.
+pub fn update(state: &mut TrashState, message: TrashMessage, model: &mut Model) -> Task<Message> {
+    let notification = match message {
+        TrashMessage::Restore(id) => model.restore_trash(id).map(|restored| {
+            if restored.skipped > 0 {
+                Notification::warning(
+                    0,
+                    format!(
+                        "Restored \"{}\"; {} item(s) stay in the trash because their word or meaning no longer exists",
+                        restored.title, restored.skipped
+                    ),
+                )
+            } else {
+                Notification::info(0, format!("Restored \"{}\"", restored.title))
+            }
+        }),
+        TrashMessage::Delete(id) => {
+            model.delete_from_trash(id);
+            None
+        }
+        TrashMessage::EmptyRequested => {
+            state.pending_empty = true;
+            None
+        }
+        TrashMessage::EmptyConfirmed => {
+            state.pending_empty = false;
+            let count = model.empty_trash();
+            tracing::info!(count, "Emptied trash");
+            Some(Notification::info(
+                0,
+                format!("Permanently deleted {} item(s)", count),
+            ))
+        }
+        TrashMessage::EmptyCancelled => {
+            state.pending_empty = false;
+            None
+        }
+    };
+    match notification {
+        Some(notification) => Task::done(Message::PushNotification(notification)),
+        None => Task::none(),
+    }
+}
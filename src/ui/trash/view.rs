@@ -0,0 +1,128 @@
+//! Trash panel view function.
+
+use super::message::TrashMessage;
+use super::state::TrashState;
+use crate::i18n::I18nManager;
+use crate::models::TrashEntry;
+use crate::state::Model;
+use crate::ui::theme::{AppTheme, ButtonSize, FontSize, Spacing};
+use crate::ui::widgets::button;
+use iced::Element;
+use iced::widget::{Button, Column, Row, Text};
+
+/// Counts of the entities in an entry, e.g. "1 word(s), 2 meaning(s)".
+fn contents(entry: &TrashEntry, i18n: &I18nManager) -> String {
+    [
+        (entry.words.len(), "trash-words"),
+        (entry.meanings.len(), "trash-meanings"),
+        (entry.tags.len(), "trash-tags"),
+        (entry.clozes.len(), "trash-clozes"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, key)| i18n.tr_with(key, &[&count.to_string()]))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+fn entry_row<'a>(entry: &TrashEntry, i18n: &I18nManager) -> Element<'a, TrashMessage, AppTheme> {
+    let deleted_at = entry
+        .deleted_at
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+
+    let details = Column::new()
+        .push(Text::new(entry.title()))
+        .push(
+            Text::new(format!("{}  {}", deleted_at, contents(entry, i18n)))
+                .size(FontSize::Footnote.px()),
+        )
+        .width(iced::Length::Fill);
+
+    Row::new()
+        .push(details)
+        .push(
+            Button::new(Text::new(i18n.tr("trash-restore")))
+                .style(button::primary)
+                .padding(ButtonSize::Small.to_iced_padding())
+                .on_press(TrashMessage::Restore(entry.id)),
+        )
+        .push(
+            Button::new(Text::new(i18n.tr("trash-delete")))
+                .style(button::danger)
+                .padding(ButtonSize::Small.to_iced_padding())
+                .on_press(TrashMessage::Delete(entry.id)),
+        )
+        .spacing(Spacing::DEFAULT.s)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+pub fn view<'a>(
+    state: &'a TrashState,
+    model: &'a Model,
+    i18n: &'a I18nManager,
+) -> Element<'a, TrashMessage, AppTheme> {
+    let trash = &model.trash_registry;
+
+    let status = i18n.tr_with(
+        "trash-status",
+        &[
+            &trash.count().to_string(),
+            &model.app_config.trash_retention_days.to_string(),
+        ],
+    );
+
+    let empty_controls: Element<'a, TrashMessage, AppTheme> = if state.pending_empty {
+        Row::new()
+            .push(Text::new(i18n.tr("trash-empty-confirm")))
+            .push(
+                Button::new(Text::new(i18n.tr("trash-empty")))
+                    .style(button::danger)
+                    .padding(ButtonSize::Standard.to_iced_padding())
+                    .on_press(TrashMessage::EmptyConfirmed),
+            )
+            .push(
+                Button::new(Text::new(i18n.tr("settings-cancel")))
+                    .style(button::secondary)
+                    .padding(ButtonSize::Standard.to_iced_padding())
+                    .on_press(TrashMessage::EmptyCancelled),
+            )
+            .spacing(Spacing::DEFAULT.s)
+            .align_y(iced::Alignment::Center)
+            .into()
+    } else {
+        Button::new(Text::new(i18n.tr("trash-empty")))
+            .style(button::danger)
+            .padding(ButtonSize::Standard.to_iced_padding())
+            .on_press_maybe((!trash.is_empty()).then_some(TrashMessage::EmptyRequested))
+            .into()
+    };
+
+    let list: Element<'a, TrashMessage, AppTheme> = if trash.is_empty() {
+        Text::new(i18n.tr("trash-none"))
+            .size(FontSize::Footnote.px())
+            .into()
+    } else {
+        let rows: Vec<Element<'a, TrashMessage, AppTheme>> = trash
+            .newest_first()
+            .into_iter()
+            .map(|entry| entry_row(entry, i18n))
+            .collect();
+        iced::widget::scrollable(Column::with_children(rows).spacing(Spacing::DEFAULT.xs))
+            .height(iced::Length::Fill)
+            .into()
+    };
+
+    Column::new()
+        .push(Text::new(i18n.tr("trash-title")).size(FontSize::Display.px()))
+        .push(Text::new(status).size(FontSize::Footnote.px()))
+        .push(iced::widget::rule::horizontal(1))
+        .push(empty_controls)
+        .push(list)
+        .spacing(Spacing::DEFAULT.s)
+        .padding(Spacing::DEFAULT.s)
+        .height(iced::Length::Fill)
+        .into()
+}
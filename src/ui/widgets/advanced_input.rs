@@ -1,5 +1,6 @@
 //! Text input widget with inline ghost text (auto-completion) support.
 
+use crate::query::normalize::strip_folded_prefix;
 use crate::ui::widgets::text_input_local::{self as text_input, Editor, Value as TextValue};
 use iced::advanced::Shell;
 use iced::advanced::clipboard::{self, Clipboard};
@@ -19,6 +20,8 @@ use iced::time::{Duration, Instant};
 use iced::touch;
 use iced::widget::text_input::Catalog;
 use iced::{Color, Event, Length, Padding, Pixels, Point, Rectangle, Size, Vector};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub struct AdvancedInput<'a, Message, Theme, Renderer>
 where
@@ -29,6 +32,7 @@ where
     placeholder: String,
     value: String,
     ghost_text: Option<String>,
+    underline: Option<(Range<usize>, Color)>,
     is_secure: bool,
     font: Option<Renderer::Font>,
     width: Length,
@@ -55,6 +59,7 @@ where
             placeholder: placeholder.into(),
             value: String::new(),
             ghost_text: None,
+            underline: None,
             is_secure: false,
             font: None,
             width: Length::Fill,
@@ -85,6 +90,13 @@ where
         self
     }
 
+    /// Underlines the characters in `span` (character indices into the
+    /// value), e.g. to mark the part of a query that has an error
+    pub fn underline(mut self, span: Range<usize>, color: Color) -> Self {
+        self.underline = Some((span, color));
+        self
+    }
+
     pub fn on_input(mut self, on_input: impl Fn(String) -> Message + 'a) -> Self {
         self.on_input = Some(Box::new(on_input));
         self
@@ -306,18 +318,9 @@ fn compute_ghost_suffix(value: &str, suggestion: Option<&str>, secure: bool) ->
         return String::new();
     }
 
+    // Matched like search text, so `cafe` still completes to `café`
     suggestion
-        .and_then(|suggestion| {
-            let lower_suggestion = suggestion.to_lowercase();
-            let lower_value = value.to_lowercase();
-            if lower_suggestion.starts_with(&lower_value)
-                && lower_suggestion.len() > lower_value.len()
-            {
-                Some(&suggestion[lower_value.len()..])
-            } else {
-                None
-            }
-        })
+        .and_then(|suggestion| strip_folded_prefix(suggestion, value))
         .unwrap_or("")
         .to_string()
 }
@@ -432,6 +435,17 @@ fn update_placeholder_and_ghost<Renderer>(
     });
 }
 
+/// Converts a character index into `text` to a grapheme index
+fn grapheme_index(text: &str, char_index: usize) -> usize {
+    let mut chars = 0;
+    text.graphemes(true)
+        .take_while(|grapheme| {
+            chars += grapheme.chars().count();
+            chars <= char_index
+        })
+        .count()
+}
+
 fn measure_cursor_and_scroll_offset(
     paragraph: &impl ParagraphTrait,
     text_bounds: Rectangle,
@@ -949,6 +963,36 @@ where
                 clip_bounds,
             );
 
+            if !text.is_empty()
+                && !self.is_secure
+                && let Some((span, color)) = &self.underline
+            {
+                let value = state.value_paragraph.raw();
+                let start = grapheme_index(&text, span.start);
+                let end = grapheme_index(&text, span.end);
+                let (left, _) = measure_cursor_and_scroll_offset(value, text_bounds, start);
+                let (right, _) = measure_cursor_and_scroll_offset(value, text_bounds, end);
+                if right > left {
+                    renderer.with_translation(
+                        Vector::new(align_offset - cursor_display.scroll_offset, 0.0),
+                        |renderer| {
+                            renderer.fill_quad(
+                                renderer::Quad {
+                                    bounds: Rectangle {
+                                        x: text_bounds.x + left,
+                                        y: text_bounds.y + text_bounds.height - 2.0,
+                                        width: right - left,
+                                        height: 2.0,
+                                    },
+                                    ..renderer::Quad::default()
+                                },
+                                *color,
+                            );
+                        },
+                    );
+                }
+            }
+
             if !text.is_empty() && !self.is_secure {
                 let ghost = state.ghost_paragraph.raw();
                 if ghost.min_width() > 0.0 {
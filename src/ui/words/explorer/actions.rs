@@ -31,6 +31,12 @@ pub fn build_action_bar<'a>(
             .padding(ButtonSize::Standard.to_iced_padding())
             .on_press(WordsMessage::ExportPlaintext);
 
+        let anki_btn =
+            Button::new(Text::new(i18n.tr("words-export-anki")).size(FontSize::Body.px()))
+                .style(button::secondary)
+                .padding(ButtonSize::Standard.to_iced_padding())
+                .on_press(WordsMessage::ExportAnki);
+
         let delete_btn =
             Button::new(Text::new(i18n.tr("words-delete-clozes")).size(FontSize::Body.px()))
                 .style(button::danger)
@@ -41,6 +47,7 @@ pub fn build_action_bar<'a>(
             .push(selection_info)
             .push(Space::new())
             .push(export_btn)
+            .push(anki_btn)
             .push(delete_btn)
             .spacing(Spacing::DEFAULT.s2)
             .align_y(iced::Alignment::Center)
@@ -53,7 +60,16 @@ pub fn build_action_bar<'a>(
             .padding(ButtonSize::Standard.to_iced_padding())
             .on_press(WordsMessage::NewWordStarted);
 
-        return Row::new().push(add_btn).spacing(Spacing::DEFAULT.s2).into();
+        let import_btn = Button::new(Text::new(i18n.tr("words-import")).size(FontSize::Body.px()))
+            .style(button::secondary)
+            .padding(ButtonSize::Standard.to_iced_padding())
+            .on_press(WordsMessage::ImportStarted);
+
+        return Row::new()
+            .push(add_btn)
+            .push(import_btn)
+            .spacing(Spacing::DEFAULT.s2)
+            .into();
     }
 
     let selection_info = Text::new(i18n.tr_with(
@@ -0,0 +1,141 @@
+use crate::i18n::I18nManager;
+use crate::import::ImportRow;
+use crate::ui::AppTheme;
+use crate::ui::theme::{ButtonSize, FontSize, Spacing};
+use crate::ui::widgets::container::card;
+use crate::ui::widgets::text as txt;
+use crate::ui::widgets::{button, svg_checkbox};
+use crate::ui::words::message::WordsMessage;
+use crate::ui::words::state::PendingImport;
+use iced::Element;
+use iced::widget::{Button, Column, Container, Row, Text, scrollable};
+
+/// Rows listed per section; the rest are summarised by the counts.
+const MAX_LISTED_ROWS: usize = 50;
+
+pub(in crate::ui::words) fn build_import_preview<'a>(
+    pending: &'a PendingImport,
+    i18n: &'a I18nManager,
+) -> Element<'a, WordsMessage, AppTheme> {
+    let preview = &pending.preview;
+
+    let summary = Text::new(i18n.tr_with(
+        "words-import-summary",
+        &[
+            &preview.creates.len().to_string(),
+            &preview.additions.len().to_string(),
+            &preview.duplicates.len().to_string(),
+            &preview.errors.len().to_string(),
+        ],
+    ))
+    .size(FontSize::Body.px());
+
+    let mut sections = Column::new().spacing(Spacing::DEFAULT.m);
+    if !preview.creates.is_empty() {
+        sections = sections.push(row_section(
+            i18n.tr("words-import-new").to_string(),
+            &preview.creates,
+            txt::primary,
+        ));
+    }
+    if !preview.additions.is_empty() {
+        sections = sections.push(row_section(
+            i18n.tr("words-import-additions").to_string(),
+            &preview.additions,
+            txt::primary,
+        ));
+    }
+    if !preview.duplicates.is_empty() {
+        sections = sections.push(row_section(
+            i18n.tr("words-import-duplicates").to_string(),
+            &preview.duplicates,
+            txt::secondary,
+        ));
+    }
+    if !preview.errors.is_empty() {
+        let lines = preview.errors.iter().take(MAX_LISTED_ROWS).map(|issue| {
+            Text::new(i18n.tr_with(
+                "words-import-error-line",
+                &[&issue.line.to_string(), &issue.message],
+            ))
+            .size(FontSize::Footnote.px())
+            .style(txt::error)
+            .into()
+        });
+        sections = sections.push(
+            Column::new()
+                .push(Text::new(i18n.tr("words-import-errors")).size(FontSize::Body.px()))
+                .extend(lines)
+                .spacing(Spacing::DEFAULT.xxs),
+        );
+    }
+
+    let enqueue_row = Row::new()
+        .push(svg_checkbox(
+            pending.enqueue,
+            WordsMessage::ImportEnqueueToggled,
+        ))
+        .push(Text::new(i18n.tr("words-import-enqueue")).size(FontSize::Body.px()))
+        .spacing(Spacing::DEFAULT.s)
+        .align_y(iced::Alignment::Center);
+
+    let actions = Row::new()
+        .push(
+            Button::new(Text::new(i18n.tr("words-import-confirm")).size(FontSize::Body.px()))
+                .style(button::primary)
+                .padding(ButtonSize::Standard.to_iced_padding())
+                .on_press_maybe((!preview.is_empty()).then_some(WordsMessage::ImportConfirmed)),
+        )
+        .push(
+            Button::new(Text::new(i18n.tr("words-cancel")).size(FontSize::Body.px()))
+                .style(button::secondary)
+                .padding(ButtonSize::Standard.to_iced_padding())
+                .on_press(WordsMessage::ImportCancelled),
+        )
+        .spacing(Spacing::DEFAULT.s);
+
+    let content = Column::new()
+        .push(
+            Text::new(i18n.tr_with("words-import-title", &[&pending.file_name]))
+                .size(FontSize::Heading.px()),
+        )
+        .push(summary)
+        .push(scrollable(sections).height(iced::Length::Fill))
+        .push(enqueue_row)
+        .push(actions)
+        .spacing(Spacing::DEFAULT.l)
+        .padding(Spacing::DEFAULT.l);
+
+    Container::new(content)
+        .padding(Spacing::DEFAULT.l2)
+        .width(iced::Length::Fill)
+        .height(iced::Length::Fill)
+        .style(card)
+        .into()
+}
+
+fn row_section<'a>(
+    title: String,
+    rows: &'a [ImportRow],
+    style: fn(&AppTheme) -> iced::widget::text::Style,
+) -> Element<'a, WordsMessage, AppTheme> {
+    let lines = rows.iter().take(MAX_LISTED_ROWS).map(|row| {
+        let mut line = row.word.clone();
+        if !row.definition.is_empty() {
+            line.push_str(&format!(" ({}) — {}", row.pos, row.definition));
+        }
+        if !row.tags.is_empty() {
+            line.push_str(&format!("  [{}]", row.tags.join(", ")));
+        }
+        Text::new(line)
+            .size(FontSize::Footnote.px())
+            .style(style)
+            .into()
+    });
+
+    Column::new()
+        .push(Text::new(title).size(FontSize::Body.px()))
+        .extend(lines)
+        .spacing(Spacing::DEFAULT.xxs)
+        .into()
+}
@@ -1,4 +1,5 @@
 mod actions;
+mod import;
 mod meaning;
 mod search;
 mod tags;
@@ -36,6 +37,9 @@ pub fn view<'a>(
         if let Some(ref target) = words_state.pending_delete {
             return build_delete_confirmation(target, model, i18n);
         }
+        if let Some(ref pending) = words_state.pending_import {
+            return import::build_import_preview(pending, i18n);
+        }
         Column::new()
             .push(search_bar)
             .push(iced::widget::rule::horizontal(1))
@@ -62,6 +66,11 @@ pub fn view<'a>(
                     .height(iced::Length::Fill)
                     .style(card)
                     .into()
+            } else if let Some(ref pending) = words_state.pending_import {
+                Container::new(import::build_import_preview(pending, i18n))
+                    .width(iced::Length::FillPortion((right_ratio * 10.0) as u16))
+                    .height(iced::Length::Fill)
+                    .into()
             } else {
                 Container::new(crate::ui::words::detail::view(
                     words_state.panel.state(),
@@ -5,12 +5,12 @@ use crate::query::SortType;
 use crate::state::Model;
 use crate::ui::AppTheme;
 use crate::ui::layout::breakpoint::Breakpoint;
-use crate::ui::theme::{ButtonSize, Spacing};
-use crate::ui::widgets::button;
+use crate::ui::theme::{ButtonSize, FontSize, Spacing};
+use crate::ui::widgets::{button, text};
 use crate::ui::words::message::WordsMessage;
 use crate::ui::words::state::WordsState;
 use iced::Element;
-use iced::widget::{Button, PickList, Row, Text};
+use iced::widget::{Button, Column, PickList, Row, Text};
 
 pub fn build_search_bar<'a>(
     words_state: &'a WordsState,
@@ -20,7 +20,9 @@ pub fn build_search_bar<'a>(
 ) -> Element<'a, WordsMessage, AppTheme> {
     let query = &words_state.search.query;
     let suggestion = if !query.is_empty() {
-        words_state.search.get_suggestion(&model.word_registry)
+        words_state
+            .search
+            .get_suggestion(&model.word_registry, &model.tag_registry)
     } else {
         None
     };
@@ -37,6 +39,15 @@ pub fn build_search_bar<'a>(
         search_input = search_input.ghost_text(sug);
     }
 
+    // Only the first problem is shown to keep the bar compact
+    let diagnostic = words_state.search.diagnostics.first();
+    if let Some(diagnostic) = diagnostic {
+        search_input = search_input.underline(
+            diagnostic.span.clone(),
+            model.app_config.theme.colors().semantic.text.error,
+        );
+    }
+
     let search_with_ghost: Element<'a, WordsMessage, AppTheme> = Element::new(search_input);
 
     let sort_width = if breakpoint.is_single_column() {
@@ -67,5 +78,16 @@ pub fn build_search_bar<'a>(
         );
     }
 
-    row.into()
+    match diagnostic {
+        Some(diagnostic) => Column::new()
+            .push(row)
+            .push(
+                Text::new(i18n.tr_with(diagnostic.key(), &diagnostic.args()))
+                    .size(FontSize::Caption.px())
+                    .style(text::error),
+            )
+            .spacing(Spacing::DEFAULT.xs)
+            .into(),
+        None => row.into(),
+    }
 }
@@ -1,7 +1,8 @@
 //! Search and filter state management.
 
 use crate::models::types::WordId;
-use crate::query::{Query, QueryEngine, SortType, TagResolver};
+use crate::query::normalize::fold;
+use crate::query::{Diagnostic, Query, QueryEngine, SortType, TagResolver};
 use crate::registry::{ClozeRegistry, MeaningRegistry, QueueRegistry, TagRegistry, WordRegistry};
 
 /// Search and filter state manager.
@@ -21,6 +22,9 @@ pub struct SearchManager {
 
     /// Cached search results
     cached_results: Option<Vec<(WordId, i32)>>,
+
+    /// Problems found in the query on the last execution
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl SearchManager {
@@ -31,6 +35,7 @@ impl SearchManager {
             sort: SortType::default(),
             dirty: true,
             cached_results: None,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -66,6 +71,13 @@ impl SearchManager {
         self.cached_results = None;
     }
 
+    /// Marks the cached results as stale after the library changed, so the
+    /// next `execute()` re-runs the query. The old results stay visible
+    /// until then.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
     /// Checks if any filters are active.
     pub fn has_active_filters(&self) -> bool {
         !self.query.is_empty()
@@ -87,9 +99,13 @@ impl SearchManager {
             let mut resolver = TagResolver::new(tag_registry);
             let query = if self.query.trim().is_empty() {
                 // Empty query matches everything
+                self.diagnostics.clear();
                 Query::empty()
             } else {
-                crate::query::parse::parse_query(&self.query, &mut resolver)
+                let (query, diagnostics) =
+                    crate::query::parse_query_with_diagnostics(&self.query, &mut resolver);
+                self.diagnostics = diagnostics;
+                query
             };
 
             let engine = QueryEngine::new(
@@ -129,13 +145,61 @@ impl SearchManager {
             .unwrap_or(false)
     }
 
-    /// Returns the best matching word content as a suggestion for autocomplete.
-    pub fn get_suggestion(&self, word_registry: &WordRegistry) -> Option<String> {
+    /// Returns a completed query as a suggestion for autocomplete: the tag
+    /// path being typed after `#` or `tag:`, otherwise the best matching word.
+    pub fn get_suggestion(
+        &self,
+        word_registry: &WordRegistry,
+        tag_registry: &TagRegistry,
+    ) -> Option<String> {
+        if let Some(suggestion) = self.tag_suggestion(tag_registry) {
+            return Some(suggestion);
+        }
         self.cached_results
             .as_ref()
             .and_then(|results| results.first())
             .and_then(|(word_id, _)| word_registry.get(*word_id).map(|w| w.content.clone()))
     }
+
+    /// Completes a tag filter at the end of the query to the shortest tag
+    /// path starting with what has been typed.
+    fn tag_suggestion(&self, tag_registry: &TagRegistry) -> Option<String> {
+        let start = self
+            .query
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace() || *c == '(')
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        let last = &self.query[start..];
+        let prefix_len = ["-tag:", "tag:", "-#", "#"]
+            .iter()
+            .find(|prefix| last.starts_with(*prefix))?
+            .len();
+        let value = &last[prefix_len..];
+        let quoted = value.starts_with('"');
+        let typed = fold(value.trim_start_matches('"'));
+        if typed.is_empty() {
+            return None;
+        }
+
+        let path = tag_registry
+            .iter()
+            .filter_map(|(id, _)| tag_registry.path(*id))
+            // Unquoted paths end at whitespace, so only quoted ones may have it
+            .filter(|path| quoted || !path.contains(char::is_whitespace))
+            .filter(|path| {
+                let folded = fold(path);
+                folded.starts_with(&typed) && folded.len() > typed.len()
+            })
+            .min_by_key(|path| (path.chars().count(), fold(path)))?;
+
+        let head = &self.query[..start + prefix_len];
+        Some(if quoted {
+            format!("{}\"{}\"", head, path)
+        } else {
+            format!("{}{}", head, path)
+        })
+    }
 }
 
 impl Default for SearchManager {
@@ -181,4 +245,43 @@ mod tests {
         assert_eq!(manager.sort, SortType::BestMatch);
         assert!(!manager.has_active_filters());
     }
+
+    #[test]
+    fn test_search_manager_invalidate_reruns_query() {
+        let mut words = WordRegistry::new();
+        let (meanings, clozes) = (MeaningRegistry::new(), ClozeRegistry::new());
+        let (queue, tags) = (QueueRegistry::new(), TagRegistry::new());
+        let mut manager = SearchManager::new();
+        manager.execute(&words, &meanings, &clozes, &queue, &tags);
+        assert!(manager.get_results().unwrap().is_empty());
+
+        words.create_word("hello", None).unwrap();
+        manager.execute(&words, &meanings, &clozes, &queue, &tags);
+        assert!(manager.get_results().unwrap().is_empty());
+
+        manager.invalidate();
+        assert_eq!(
+            manager
+                .execute(&words, &meanings, &clozes, &queue, &tags)
+                .len(),
+            1
+        );
+    }
+
+    #[test_case("#tra", Some("#Travel"); "hash prefix")]
+    #[test_case("run -tag:travel/f", Some("run -tag:Travel/Food"); "nested path")]
+    #[test_case("(tag:\"exam", Some("(tag:\"Exam Prep\""); "quoted path")]
+    #[test_case("tag:exam", None; "unquoted path with space")]
+    #[test_case("#travel/food", None; "already complete")]
+    #[test_case("travel", None; "not a tag filter")]
+    #[test_case("#ETU", Some("#Étude"); "case and accents folded")]
+    fn test_search_manager_tag_suggestion(query: &str, expected: Option<&str>) {
+        let mut tags = TagRegistry::new();
+        tags.get_or_create_path("Travel/Food");
+        tags.get_or_create_path("Exam Prep");
+        tags.get_or_create_path("Étude");
+        let mut manager = SearchManager::new();
+        manager.set_query(query.to_string());
+        assert_eq!(manager.tag_suggestion(&tags).as_deref(), expected);
+    }
 }
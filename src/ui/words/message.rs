@@ -172,8 +172,20 @@ pub enum WordsMessage {
     // Export operations
     /// Export to plaintext
     ExportPlaintext,
+    /// Export to Anki-compatible TSV
+    ExportAnki,
     /// Export failed with error message
     ExportFailed(String),
+
+    // Import operations
+    /// Pick a CSV/TSV file and preview it
+    ImportStarted,
+    /// Toggle queueing imported meanings for generation
+    ImportEnqueueToggled,
+    /// Create the previewed entries
+    ImportConfirmed,
+    /// Discard the preview
+    ImportCancelled,
 }
 
 /// Notification severity level.
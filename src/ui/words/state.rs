@@ -6,6 +6,7 @@
 //! - ExpansionManager: Expansion state management
 //! - DetailPanelManager: Detail panel state (view/edit unified)
 
+use crate::import::ImportPreview;
 use crate::ui::words::manager::{
     DetailPanelManager, ExpansionManager, SearchManager, SelectionManager,
 };
@@ -26,6 +27,18 @@ pub struct WordsState {
     pub last_language: Option<langtag::LangTagBuf>,
     /// Pending delete confirmation
     pub pending_delete: Option<DeleteTarget>,
+    /// Import preview awaiting confirmation
+    pub pending_import: Option<PendingImport>,
+}
+
+/// A parsed word list awaiting confirmation.
+#[derive(Debug, Clone)]
+pub struct PendingImport {
+    /// Source file name, for display
+    pub file_name: String,
+    pub preview: ImportPreview,
+    /// Queue imported meanings for cloze generation
+    pub enqueue: bool,
 }
 
 impl WordsState {
@@ -2,10 +2,11 @@
 
 use crate::models::types::{ClozeId, MeaningId};
 use crate::models::{Meaning, Tag, Word};
+use crate::persistence::SnapshotReason;
 use crate::state::Model;
 use crate::ui::words::manager::{DetailPanelState, TagDropdownTarget};
 use crate::ui::words::message::{DeleteTarget, NotificationLevel, WordsMessage};
-use crate::ui::words::state::WordsState;
+use crate::ui::words::state::{PendingImport, WordsState};
 use iced::Task;
 
 #[allow(deprecated)]
@@ -50,7 +51,10 @@ pub fn update(
             );
         }
         WordsMessage::SuggestionAccepted => {
-            if let Some(suggestion) = state.search.get_suggestion(&model.word_registry) {
+            if let Some(suggestion) = state
+                .search
+                .get_suggestion(&model.word_registry, &model.tag_registry)
+            {
                 state.search.set_query(suggestion);
                 state.search.execute(
                     &model.word_registry,
@@ -415,9 +419,7 @@ pub fn update(
 
             if let Some(word_id) = word_id {
                 tracing::debug!("Deleting meaning: {} from word {}", meaning_id, word_id);
-                model.cloze_registry.delete_by_meaning(meaning_id);
-                model.word_registry.remove_meaning(word_id, meaning_id);
-                model.meaning_registry.delete(meaning_id);
+                model.trash_meanings(&[meaning_id]);
                 state.selection.remove_meaning(&meaning_id);
             }
         }
@@ -538,50 +540,35 @@ pub fn update(
             let notify_msg = match &target {
                 DeleteTarget::Word(word_id) => {
                     tracing::debug!("Deleting word: {}", word_id);
-                    if let Some(word) = model.word_registry.get(*word_id) {
-                        for meaning_id in &word.meaning_ids {
-                            model.cloze_registry.delete_by_meaning(*meaning_id);
-                        }
-                    }
-                    model.meaning_registry.delete_by_word(*word_id);
-                    model.word_registry.delete(*word_id);
                     if let Some(word) = model.word_registry.get(*word_id) {
                         for mid in &word.meaning_ids {
                             state.selection.remove_meaning(mid);
                         }
                     }
+                    model.trash_words(&[*word_id]);
                     Some(WordsMessage::Notify {
                         level: NotificationLevel::Info,
-                        message: "Word deleted".to_string(),
+                        message: "Word moved to trash".to_string(),
                     })
                 }
                 DeleteTarget::Meanings(meaning_ids) => {
                     let count = meaning_ids.len();
-                    for meaning_id in meaning_ids {
-                        let word_id = model.meaning_registry.get(*meaning_id).map(|m| m.word_id);
-                        if let Some(word_id) = word_id {
-                            model.cloze_registry.delete_by_meaning(*meaning_id);
-                            model.word_registry.remove_meaning(word_id, *meaning_id);
-                            model.meaning_registry.delete(*meaning_id);
-                        }
-                    }
+                    model.trash_meanings(meaning_ids);
                     tracing::info!("Deleted {} meanings", count);
                     state.selection.clear_all();
                     Some(WordsMessage::Notify {
                         level: NotificationLevel::Info,
-                        message: format!("Deleted {} meaning(s)", count),
+                        message: format!("Moved {} meaning(s) to trash", count),
                     })
                 }
                 DeleteTarget::Clozes(cloze_ids) => {
                     let count = cloze_ids.len();
-                    for cloze_id in cloze_ids {
-                        model.cloze_registry.delete(*cloze_id);
-                    }
+                    model.trash_clozes(cloze_ids);
                     tracing::info!("Deleted {} clozes", count);
                     state.selection.clear_clozes();
                     Some(WordsMessage::Notify {
                         level: NotificationLevel::Info,
-                        message: format!("Deleted {} cloze(s)", count),
+                        message: format!("Moved {} cloze(s) to trash", count),
                     })
                 }
             };
@@ -618,6 +605,95 @@ pub fn update(
                 }
             }
         }
+        WordsMessage::ExportAnki => {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Anki TSV", &["txt", "tsv"])
+                .set_file_name("clozes-anki.txt")
+                .save_file()
+            {
+                let selected = state.selection.selected_clozes();
+                let clozes = selected
+                    .iter()
+                    .filter_map(|cloze_id| model.cloze_registry.get(*cloze_id));
+                let tsv = crate::export::to_anki_tsv(
+                    clozes,
+                    &model.word_registry,
+                    &model.meaning_registry,
+                    &model.tag_registry,
+                );
+
+                if let Err(e) = std::fs::write(&path, tsv) {
+                    tracing::error!(error = %e, "Failed to write Anki export");
+                    return Task::done(WordsMessage::ExportFailed(e.to_string()));
+                } else {
+                    tracing::info!(count = selected.len(), path = ?path, "Exported clozes to Anki TSV");
+                }
+            }
+        }
+        WordsMessage::ImportStarted => {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("Word list", &["csv", "tsv", "txt"])
+                .pick_file()
+            else {
+                return Task::none();
+            };
+            match std::fs::read_to_string(&path) {
+                Ok(text) => {
+                    state.pending_delete = None;
+                    state.pending_import = Some(PendingImport {
+                        file_name: path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        preview: crate::import::preview(
+                            &text,
+                            &model.word_registry,
+                            &model.meaning_registry,
+                        ),
+                        enqueue: false,
+                    });
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, path = ?path, "Failed to read import file");
+                    return Task::done(WordsMessage::Notify {
+                        level: NotificationLevel::Error,
+                        message: format!("Import failed: {}", e),
+                    });
+                }
+            }
+        }
+        WordsMessage::ImportEnqueueToggled => {
+            if let Some(pending) = state.pending_import.as_mut() {
+                pending.enqueue = !pending.enqueue;
+            }
+        }
+        WordsMessage::ImportConfirmed => {
+            if let Some(pending) = state.pending_import.take() {
+                if let Err(e) = model.snapshot(SnapshotReason::Import) {
+                    tracing::error!(error = %e, "Failed to snapshot before import");
+                    return Task::done(WordsMessage::Notify {
+                        level: NotificationLevel::Error,
+                        message: format!("Import aborted, snapshot failed: {}", e),
+                    });
+                }
+                let summary = crate::import::apply(&pending.preview, model, pending.enqueue);
+                state.search.invalidate();
+                state.search.execute(
+                    &model.word_registry,
+                    &model.meaning_registry,
+                    &model.cloze_registry,
+                    &model.queue_registry,
+                    &model.tag_registry,
+                );
+                return Task::done(WordsMessage::Notify {
+                    level: NotificationLevel::Info,
+                    message: format!("Imported {}", summary),
+                });
+            }
+        }
+        WordsMessage::ImportCancelled => {
+            state.pending_import = None;
+        }
         // Export failure — converted to PushNotification in compositor layer
         WordsMessage::ExportFailed(_) => {}
         // Notify — consumed by compositor, no local state change needed